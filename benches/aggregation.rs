@@ -1,9 +1,10 @@
 use ccstat::{
+    Cost,
     aggregation::{Aggregator, DailyUsage},
     cost_calculator::CostCalculator,
     pricing_fetcher::PricingFetcher,
     timezone::TimezoneConfig,
-    types::{CostMode, ISOTimestamp, ModelName, SessionId, TokenCounts, UsageEntry},
+    types::{CostMode, ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry},
 };
 use chrono::Utc;
 use criterion::{Criterion, criterion_group, criterion_main};
@@ -36,6 +37,7 @@ fn create_test_entries(count: usize) -> Vec<UsageEntry> {
             total_cost: Some((i as f64) * 0.01),
             project: Some(format!("project-{}", i % 5)),
             instance_id: Some(format!("instance-{}", i % 3)),
+            provider: ProviderId::new("claude"),
         });
     }
 
@@ -98,8 +100,9 @@ fn benchmark_monthly_aggregation(c: &mut Criterion) {
         daily_data.push(DailyUsage {
             date: ccstat::types::DailyDate::new(date),
             tokens: TokenCounts::new(1000, 500, 100, 50),
-            total_cost: 0.025,
+            total_cost: Cost::from_usd(0.025),
             models_used: vec!["claude-3-opus".to_string()],
+            model_breakdowns: Vec::new(),
             entries: None,
         });
     }
@@ -1,8 +1,10 @@
 use ccstat::{
+    Cost,
     cost_calculator::CostCalculator,
     pricing_fetcher::PricingFetcher,
     types::{CostMode, ModelName, ModelPricing, TokenCounts},
 };
+use chrono::Utc;
 use criterion::{Criterion, criterion_group, criterion_main};
 use std::hint::black_box;
 use std::sync::Arc;
@@ -13,6 +15,7 @@ fn create_test_pricing() -> ModelPricing {
         output_cost_per_token: Some(0.00002),
         cache_creation_input_token_cost: Some(0.000015),
         cache_read_input_token_cost: Some(0.000001),
+        ..Default::default()
     }
 }
 
@@ -76,6 +79,7 @@ fn benchmark_cost_modes(c: &mut Criterion) {
                     .calculate_with_mode(
                         black_box(&tokens),
                         black_box(&model),
+                        black_box(Utc::now()),
                         black_box(pre_calculated),
                         CostMode::Auto,
                     )
@@ -97,6 +101,7 @@ fn benchmark_cost_modes(c: &mut Criterion) {
                     .calculate_with_mode(
                         black_box(&tokens),
                         black_box(&model),
+                        black_box(Utc::now()),
                         black_box(pre_calculated),
                         CostMode::Calculate,
                     )
@@ -127,7 +132,7 @@ fn benchmark_batch_cost_calculation(c: &mut Criterion) {
             .collect();
 
         b.iter(|| {
-            let mut total_cost = 0.0;
+            let mut total_cost = Cost::ZERO;
             for tokens in &entries {
                 total_cost +=
                     CostCalculator::calculate_from_pricing(black_box(tokens), black_box(&pricing));
@@ -1,4 +1,4 @@
-use ccstat::types::{ISOTimestamp, ModelName, SessionId, TokenCounts, UsageEntry};
+use ccstat::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry};
 use chrono::Utc;
 use criterion::{Criterion, criterion_group, criterion_main};
 use std::hint::black_box;
@@ -14,6 +14,7 @@ fn create_test_entry(session_num: u32) -> UsageEntry {
         total_cost: Some(0.025),
         project: Some("test-project".to_string()),
         instance_id: Some("instance-1".to_string()),
+        provider: ProviderId::new("claude"),
     }
 }
 
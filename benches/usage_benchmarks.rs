@@ -7,7 +7,7 @@ use ccstat::{
     cost_calculator::CostCalculator,
     pricing_fetcher::PricingFetcher,
     timezone::TimezoneConfig,
-    types::{CostMode, ISOTimestamp, ModelName, SessionId, TokenCounts, UsageEntry},
+    types::{CostMode, ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry},
 };
 use chrono::{TimeZone, Utc};
 use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
@@ -44,6 +44,7 @@ fn generate_usage_entries(count: usize) -> Vec<UsageEntry> {
                 total_cost: None,
                 project: Some("benchmark-project".to_string()),
                 instance_id: Some(format!("instance-{}", i % 5)),
+                provider: ProviderId::new("claude"),
             }
         })
         .collect()
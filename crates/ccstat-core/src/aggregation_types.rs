@@ -3,7 +3,8 @@
 //! Pure data structures used for aggregated usage summaries.
 //! These types have no dependencies on cost_calculator or data_loader.
 
-use crate::types::{DailyDate, ModelName, SessionId, TokenCounts};
+use crate::cost::Cost;
+use crate::types::{DailyDate, ModelName, ProviderId, SessionId, TokenCounts};
 use serde::{Deserialize, Serialize};
 
 /// Daily usage summary
@@ -14,14 +15,30 @@ pub struct DailyUsage {
     /// Token counts for the day
     pub tokens: TokenCounts,
     /// Total cost for the day in USD
-    pub total_cost: f64,
+    pub total_cost: Cost,
     /// List of unique models used during the day
     pub models_used: Vec<String>,
+    /// Usage per provider and model, sorted by provider then model
+    #[serde(default)]
+    pub model_breakdowns: Vec<ModelBreakdown>,
     /// Individual entries for verbose mode (only populated when verbose flag is set)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub entries: Option<Vec<VerboseEntry>>,
 }
 
+/// Usage of a single model from a single provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelBreakdown {
+    /// Provider that reported the usage
+    pub provider: ProviderId,
+    /// Model name
+    pub model: String,
+    /// Token counts for this model
+    pub tokens: TokenCounts,
+    /// Cost for this model in USD
+    pub cost: Cost,
+}
+
 /// Verbose entry for detailed token information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerboseEntry {
@@ -34,7 +51,7 @@ pub struct VerboseEntry {
     /// Token counts
     pub tokens: TokenCounts,
     /// Calculated cost for this entry
-    pub cost: f64,
+    pub cost: Cost,
 }
 
 /// Daily usage grouped by instance
@@ -47,7 +64,7 @@ pub struct DailyInstanceUsage {
     /// Token counts for the day
     pub tokens: TokenCounts,
     /// Total cost for the day
-    pub total_cost: f64,
+    pub total_cost: Cost,
     /// Models used during the day
     pub models_used: Vec<String>,
 }
@@ -64,9 +81,11 @@ pub struct SessionUsage {
     /// Token counts for the session
     pub tokens: TokenCounts,
     /// Total cost for the session
-    pub total_cost: f64,
+    pub total_cost: Cost,
     /// Primary model used
     pub model: ModelName,
+    /// Provider that recorded the session
+    pub provider: ProviderId,
 }
 
 /// Monthly usage summary
@@ -77,7 +96,7 @@ pub struct MonthlyUsage {
     /// Total token counts for the month
     pub tokens: TokenCounts,
     /// Total cost for the month in USD
-    pub total_cost: f64,
+    pub total_cost: Cost,
     /// Number of days with usage in this month
     pub active_days: usize,
 }
@@ -90,11 +109,22 @@ pub struct WeeklyUsage {
     /// Total token counts for the week
     pub tokens: TokenCounts,
     /// Total cost for the week in USD
-    pub total_cost: f64,
+    pub total_cost: Cost,
     /// Number of days with usage in this week
     pub active_days: usize,
 }
 
+/// One provider's rows within a combined multi-provider report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderUsage<T> {
+    /// Provider identifier (e.g. "claude", "codex")
+    pub provider: ProviderId,
+    /// Report rows for this provider
+    pub data: Vec<T>,
+    /// Subtotal across this provider's rows
+    pub totals: Totals,
+}
+
 /// 5-hour billing block
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionBlock {
@@ -113,7 +143,7 @@ pub struct SessionBlock {
     /// Total tokens used in this block
     pub tokens: TokenCounts,
     /// Total cost for this block in USD
-    pub total_cost: f64,
+    pub total_cost: Cost,
     /// List of unique models used in this block
     pub models_used: Vec<String>,
     /// List of unique projects used in this block
@@ -133,7 +163,7 @@ pub struct SessionBlock {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Totals {
     pub tokens: TokenCounts,
-    pub total_cost: f64,
+    pub total_cost: Cost,
 }
 
 impl Totals {
@@ -182,6 +212,15 @@ impl Totals {
         totals
     }
 
+    pub fn from_providers<T>(providers: &[ProviderUsage<T>]) -> Self {
+        let mut totals = Self::default();
+        for provider in providers {
+            totals.tokens += provider.totals.tokens;
+            totals.total_cost += provider.totals.total_cost;
+        }
+        totals
+    }
+
     pub fn from_blocks(blocks: &[SessionBlock]) -> Self {
         let mut totals = Self::default();
         for block in blocks {
@@ -0,0 +1,147 @@
+//! Fixed-point money for costs
+//!
+//! Costs are summed over tens of thousands of usage entries. Summing `f64`
+//! dollars drifts in the last cents and depends on the order entries are
+//! added in, so daily, monthly, and session reports of the same usage could
+//! disagree. A [`Cost`] holds a whole number of micro-dollars instead: each
+//! entry's cost is rounded once when it is priced, and every sum after that
+//! is exact and independent of order.
+//!
+//! Costs serialize as USD numbers, so JSON output keeps its format.
+//!
+//! # Examples
+//!
+//! ```
+//! use ccstat_core::cost::Cost;
+//!
+//! let entries = vec![Cost::from_usd(0.1); 10];
+//! let total: Cost = entries.iter().sum();
+//! assert_eq!(total, Cost::from_usd(1.0));
+//! assert_eq!(total.to_usd(), 1.0);
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+
+/// Micro-dollars per USD
+const MICROS_PER_USD: f64 = 1_000_000.0;
+
+/// An amount of money in USD, stored as whole micro-dollars
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cost(i64);
+
+impl Cost {
+    /// No cost
+    pub const ZERO: Self = Self(0);
+
+    /// Cost of `usd` dollars, rounded to the nearest micro-dollar
+    pub fn from_usd(usd: f64) -> Self {
+        Self((usd * MICROS_PER_USD).round() as i64)
+    }
+
+    /// Cost of `micros` micro-dollars
+    pub const fn from_micros(micros: i64) -> Self {
+        Self(micros)
+    }
+
+    /// Amount in micro-dollars
+    pub const fn micros(self) -> i64 {
+        self.0
+    }
+
+    /// Amount in dollars
+    pub fn to_usd(self) -> f64 {
+        self.0 as f64 / MICROS_PER_USD
+    }
+
+    /// Whether the amount is zero
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Add for Cost {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Cost {
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0;
+    }
+}
+
+impl Sum for Cost {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Cost> for Cost {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Formats the amount in dollars, honoring precision: `{:.2}` gives cents
+impl fmt::Display for Cost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_usd(), f)
+    }
+}
+
+impl Serialize for Cost {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_usd())
+    }
+}
+
+impl<'de> Deserialize<'de> for Cost {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        f64::deserialize(deserializer).map(Self::from_usd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sums_are_exact_and_order_independent() {
+        let costs: Vec<Cost> = (1..=10_000)
+            .map(|i| Cost::from_usd(0.000_123 * i as f64))
+            .collect();
+        let forward: Cost = costs.iter().sum();
+        let backward: Cost = costs.iter().rev().sum();
+        let chunked: Cost = costs
+            .chunks(7)
+            .map(|chunk| chunk.iter().sum::<Cost>())
+            .sum();
+        assert_eq!(forward, backward);
+        assert_eq!(forward, chunked);
+        assert_eq!(forward.micros(), 123 * 10_000 * 10_001 / 2);
+    }
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(Cost::from_usd(0.000_000_4), Cost::ZERO);
+        assert_eq!(Cost::from_usd(0.000_000_6), Cost::from_micros(1));
+        assert_eq!(Cost::from_usd(1.234_567_89).micros(), 1_234_568);
+        assert_eq!(Cost::from_usd(0.1).to_usd(), 0.1);
+        assert!(Cost::default().is_zero());
+        assert_eq!(format!("{:.2}", Cost::from_usd(1.005_1)), "1.01");
+    }
+
+    #[test]
+    fn test_serializes_as_usd() {
+        let cost = Cost::from_usd(0.1) + Cost::from_usd(0.2);
+        assert_eq!(serde_json::to_string(&cost).unwrap(), "0.3");
+        let parsed: Cost = serde_json::from_str("1.5").unwrap();
+        assert_eq!(parsed, Cost::from_micros(1_500_000));
+    }
+}
@@ -0,0 +1,170 @@
+//! Display currency for costs
+//!
+//! Costs are always computed in USD. A [`Currency`] converts them for
+//! display at a fixed rate taken from an offline exchange rate table, and
+//! formats them with the currency's symbol and minor units.
+//!
+//! # Examples
+//!
+//! ```
+//! use ccstat_core::currency::Currency;
+//!
+//! let usd = Currency::usd();
+//! assert_eq!(usd.format(12.345), "$12.35");
+//!
+//! let jpy = Currency::new("jpy", 150.0);
+//! assert_eq!(jpy.code(), "JPY");
+//! assert_eq!(jpy.convert(2.0), 300.0);
+//! assert_eq!(jpy.format(2.0), "¥300");
+//! ```
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// Symbols of common currencies, by ISO 4217 code
+const SYMBOLS: &[(&str, &str)] = &[
+    ("USD", "$"),
+    ("EUR", "€"),
+    ("GBP", "£"),
+    ("JPY", "¥"),
+    ("CNY", "CN¥"),
+    ("TWD", "NT$"),
+    ("HKD", "HK$"),
+    ("KRW", "₩"),
+    ("INR", "₹"),
+    ("AUD", "A$"),
+    ("CAD", "CA$"),
+    ("SGD", "S$"),
+];
+
+/// Currencies without minor units
+const ZERO_DECIMAL: &[&str] = &["JPY", "KRW"];
+
+/// Currency costs are displayed in, with its rate against USD
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Currency {
+    /// ISO 4217 code
+    code: String,
+    /// Units of the currency per USD
+    rate: f64,
+    /// Date of the exchange rate, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    as_of: Option<NaiveDate>,
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Self::usd()
+    }
+}
+
+impl Currency {
+    /// US dollars, in which costs are computed
+    pub fn usd() -> Self {
+        Self::new("USD", 1.0)
+    }
+
+    /// A currency worth `rate` units per USD
+    pub fn new(code: &str, rate: f64) -> Self {
+        Self {
+            code: code.to_ascii_uppercase(),
+            rate,
+            as_of: None,
+        }
+    }
+
+    /// Set the date of the exchange rate
+    pub fn with_as_of(mut self, as_of: Option<NaiveDate>) -> Self {
+        self.as_of = as_of;
+        self
+    }
+
+    /// ISO 4217 code
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Units of the currency per USD
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Date of the exchange rate, if known
+    pub fn as_of(&self) -> Option<NaiveDate> {
+        self.as_of
+    }
+
+    /// Whether this is USD, so amounts need no conversion
+    pub fn is_usd(&self) -> bool {
+        self.code == "USD"
+    }
+
+    /// Convert a USD amount
+    pub fn convert(&self, usd: f64) -> f64 {
+        usd * self.rate
+    }
+
+    /// Symbol shown before amounts, or the code for unlisted currencies
+    pub fn symbol(&self) -> String {
+        SYMBOLS
+            .iter()
+            .find(|(code, _)| *code == self.code)
+            .map_or_else(
+                || format!("{} ", self.code),
+                |(_, symbol)| symbol.to_string(),
+            )
+    }
+
+    /// Number of decimals amounts are shown with
+    pub fn decimals(&self) -> usize {
+        if ZERO_DECIMAL.contains(&self.code.as_str()) {
+            0
+        } else {
+            2
+        }
+    }
+
+    /// Convert and format a USD amount
+    pub fn format(&self, usd: f64) -> String {
+        self.format_with_decimals(usd, self.decimals())
+    }
+
+    /// Convert and format a USD amount with `decimals` decimals
+    pub fn format_with_decimals(&self, usd: f64, decimals: usize) -> String {
+        format!("{}{:.*}", self.symbol(), decimals, self.convert(usd))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(Currency::usd().format(0.001), "$0.00");
+        assert_eq!(Currency::new("EUR", 0.5).format(3.0), "€1.50");
+        assert_eq!(Currency::new("TWD", 30.0).format(1.5), "NT$45.00");
+        assert_eq!(Currency::new("JPY", 150.0).format(0.01), "¥2");
+        assert_eq!(Currency::new("chf", 0.8).format(10.0), "CHF 8.00");
+        assert_eq!(Currency::usd().format_with_decimals(0.0125, 3), "$0.013");
+    }
+
+    #[test]
+    fn test_usd_is_identity() {
+        let usd = Currency::default();
+        assert!(usd.is_usd());
+        assert_eq!(usd.convert(1.23), 1.23);
+        assert!(!Currency::new("EUR", 0.9).is_usd());
+    }
+
+    #[test]
+    fn test_serialize() {
+        let currency = Currency::new("EUR", 0.9).with_as_of(NaiveDate::from_ymd_opt(2025, 6, 2));
+        assert_eq!(
+            serde_json::to_value(&currency).unwrap(),
+            serde_json::json!({"code": "EUR", "rate": 0.9, "as_of": "2025-06-02"})
+        );
+        let json = serde_json::to_value(Currency::usd()).unwrap();
+        assert!(json.get("as_of").is_none());
+    }
+}
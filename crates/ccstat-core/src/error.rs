@@ -43,6 +43,15 @@ pub enum CcstatError {
     #[error("Unknown model: {0}")]
     UnknownModel(ModelName),
 
+    /// Model pricing found only by guessing, with strict pricing enabled
+    #[error("No exact pricing for model {model}, closest match is {candidate}")]
+    InexactPricing {
+        /// The model whose pricing was looked up
+        model: ModelName,
+        /// The pricing key it would have been priced as
+        candidate: String,
+    },
+
     /// Invalid date format
     #[error("Invalid date format: {0}")]
     InvalidDate(String),
@@ -29,7 +29,7 @@ use chrono_tz::Tz;
 /// # Examples
 /// ```
 /// use ccstat_core::filters::UsageFilter;
-/// use ccstat_core::types::{UsageEntry, SessionId, ISOTimestamp, ModelName, TokenCounts};
+/// use ccstat_core::types::{UsageEntry, SessionId, ISOTimestamp, ModelName, ProviderId, TokenCounts};
 /// use chrono::{NaiveDate, Utc};
 ///
 /// let filter = UsageFilter::new()
@@ -43,6 +43,7 @@ use chrono_tz::Tz;
 ///     total_cost: None,
 ///     project: Some("my-project".to_string()),
 ///     instance_id: None,
+///     provider: ProviderId::new("claude"),
 /// };
 ///
 /// assert!(filter.matches(&entry));
@@ -252,7 +253,7 @@ impl MonthFilter {
 mod tests {
     use super::*;
     use crate::ISOTimestamp;
-    use crate::types::{ModelName, SessionId, TokenCounts};
+    use crate::types::{ModelName, ProviderId, SessionId, TokenCounts};
     use chrono::{DateTime, Utc};
 
     #[test]
@@ -275,6 +276,7 @@ mod tests {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         let entry_within = UsageEntry {
@@ -289,6 +291,7 @@ mod tests {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         let entry_after = UsageEntry {
@@ -303,6 +306,7 @@ mod tests {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         assert!(!filter.matches(&entry_before));
@@ -337,6 +341,7 @@ mod tests {
             total_cost: None,
             project: Some("my-project".to_string()),
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         let entry_different_project = UsageEntry {
@@ -351,6 +356,7 @@ mod tests {
             total_cost: None,
             project: Some("other-project".to_string()),
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         let entry_no_project = UsageEntry {
@@ -365,6 +371,7 @@ mod tests {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         assert!(filter.matches(&entry_with_project));
@@ -5,11 +5,14 @@
 //! by all other ccstat crates.
 
 pub mod aggregation_types;
+pub mod cost;
+pub mod currency;
 pub mod error;
 pub mod filters;
 pub mod memory_pool;
 pub mod model_formatter;
 pub mod provider;
+pub mod registry;
 pub mod string_pool;
 pub mod timezone;
 pub mod types;
@@ -18,5 +21,6 @@ pub mod types;
 pub mod test_utils;
 
 // Re-export commonly used types
+pub use cost::Cost;
 pub use error::{CcstatError, Result};
 pub use types::{CostMode, DailyDate, ISOTimestamp, ModelName, SessionId, TokenCounts};
@@ -140,5 +140,6 @@ mod tests {
         assert_eq!(entries.len(), 2);
         assert_eq!(entries[0].session_id.as_str(), "test1");
         assert_eq!(entries[1].session_id.as_str(), "test2");
+        assert_eq!(entries[0].provider.as_str(), "claude");
     }
 }
@@ -2,23 +2,104 @@
 //!
 //! This module defines the `ProviderDataLoader` trait that all provider crates
 //! must implement. It provides a uniform interface for constructing a data loader
-//! and streaming usage entries.
+//! and streaming usage entries. [`DynDataLoader`] is its object-safe
+//! counterpart, used where loaders are chosen at runtime.
 
 use crate::error::Result;
 use crate::types::UsageEntry;
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use futures::stream::Stream;
+use std::path::PathBuf;
 use std::pin::Pin;
 
 /// Trait for provider-specific data loaders.
 ///
-/// Each provider crate (Claude, Codex, OpenCode, Amp, Pi) implements this trait
-/// so that the main binary can dispatch to any provider using generic code.
+/// Each provider crate implements this trait so that the main binary can
+/// dispatch to any provider using generic code.
 #[async_trait]
 pub trait ProviderDataLoader: Send + Sync + Sized {
+    /// Default billing block length in hours, used when `--session-duration`
+    /// is not given.
+    const DEFAULT_BLOCK_HOURS: f64 = 5.0;
+
+    /// Data roots read when none are given explicitly.
+    ///
+    /// This is the directory named by the provider's environment variable
+    /// override if it is set, otherwise the provider's default location.
+    fn default_roots() -> Result<Vec<PathBuf>>;
+
+    /// Create a data loader reading from explicit data roots.
+    ///
+    /// A root is a directory of the kind the provider's environment variable
+    /// override points at (for example `CODEX_HOME`). Roots that do not exist
+    /// yield no entries.
+    fn from_roots(roots: Vec<PathBuf>) -> Self;
+
     /// Create a new data loader, discovering data directories.
-    async fn new() -> Result<Self>;
+    async fn new() -> Result<Self> {
+        Ok(Self::from_roots(Self::default_roots()?))
+    }
+
+    /// Stream all usage entries from the provider's data files.
+    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>>;
+
+    /// Directories holding the provider's data files.
+    ///
+    /// Live monitoring watches these for changes. Directories may not exist.
+    fn paths(&self) -> &[PathBuf];
+
+    /// Limit loading to data that may fall between `since` and `until`.
+    ///
+    /// Providers that partition data by date use this to skip whole
+    /// directories. It only narrows what is read; callers still filter the
+    /// loaded entries.
+    fn with_date_range(self, _since: Option<NaiveDate>, _until: Option<NaiveDate>) -> Self {
+        self
+    }
+}
 
+/// Object-safe form of [`ProviderDataLoader`]
+///
+/// Every `ProviderDataLoader` implements this trait, so loaders constructed
+/// through the [provider registry](crate::registry) can be handled as
+/// `Box<dyn DynDataLoader>`.
+pub trait DynDataLoader: Send + Sync {
     /// Stream all usage entries from the provider's data files.
     fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>>;
+
+    /// Directories holding the provider's data files.
+    fn paths(&self) -> &[PathBuf];
+
+    /// Default billing block length in hours.
+    fn default_block_hours(&self) -> f64;
+
+    /// Limit loading to data that may fall between `since` and `until`.
+    fn with_date_range(
+        self: Box<Self>,
+        since: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+    ) -> Box<dyn DynDataLoader>;
+}
+
+impl<T: ProviderDataLoader + 'static> DynDataLoader for T {
+    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+        ProviderDataLoader::load_entries(self)
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        ProviderDataLoader::paths(self)
+    }
+
+    fn default_block_hours(&self) -> f64 {
+        T::DEFAULT_BLOCK_HOURS
+    }
+
+    fn with_date_range(
+        self: Box<Self>,
+        since: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+    ) -> Box<dyn DynDataLoader> {
+        Box::new(ProviderDataLoader::with_date_range(*self, since, until))
+    }
 }
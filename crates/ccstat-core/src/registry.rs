@@ -0,0 +1,339 @@
+//! Provider registry
+//!
+//! Each provider crate describes itself with a [`ProviderInfo`]: its command
+//! name and aliases, the environment variables that override its data
+//! directory, the reports it supports, and how to construct its data loader.
+//! The binary collects these into a [`ProviderRegistry`], from which the CLI
+//! subcommands, the report support matrix, and report dispatch are derived.
+//!
+//! # Examples
+//!
+//! ```
+//! use ccstat_core::registry::{ProviderRegistry, ReportType};
+//! # use ccstat_core::provider::ProviderDataLoader;
+//! # use ccstat_core::registry::ProviderInfo;
+//! # struct Loader;
+//! # #[async_trait::async_trait]
+//! # impl ProviderDataLoader for Loader {
+//! #     fn default_roots() -> ccstat_core::Result<Vec<std::path::PathBuf>> { Ok(Vec::new()) }
+//! #     fn from_roots(_roots: Vec<std::path::PathBuf>) -> Self { Loader }
+//! #     fn load_entries(&self) -> std::pin::Pin<Box<dyn futures::Stream<Item = ccstat_core::Result<ccstat_core::types::UsageEntry>> + Send + '_>> {
+//! #         Box::pin(futures::stream::empty())
+//! #     }
+//! #     fn paths(&self) -> &[std::path::PathBuf] { &[] }
+//! # }
+//!
+//! let mut registry = ProviderRegistry::new();
+//! registry.register(
+//!     ProviderInfo::new::<Loader>("acme", "Acme usage data")
+//!         .with_aliases(&["acme-cli"])
+//!         .with_env_vars(&["ACME_HOME"]),
+//! );
+//!
+//! let info = registry.get("acme-cli").unwrap();
+//! assert_eq!(info.name, "acme");
+//! assert!(info.supports(ReportType::Daily));
+//! assert!(!info.supports(ReportType::Weekly));
+//! ```
+
+use crate::error::Result;
+use crate::provider::{DynDataLoader, ProviderDataLoader};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+/// Report types a provider can support
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportType {
+    Daily,
+    Monthly,
+    Weekly,
+    Session,
+    Blocks,
+    Statusline,
+    Limits,
+}
+
+impl ReportType {
+    /// Subcommand name of the report
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReportType::Daily => "daily",
+            ReportType::Monthly => "monthly",
+            ReportType::Weekly => "weekly",
+            ReportType::Session => "session",
+            ReportType::Blocks => "blocks",
+            ReportType::Statusline => "statusline",
+            ReportType::Limits => "limits",
+        }
+    }
+}
+
+impl std::fmt::Display for ReportType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Reports every provider supports unless it says otherwise
+pub const DEFAULT_REPORTS: &[ReportType] = &[
+    ReportType::Daily,
+    ReportType::Monthly,
+    ReportType::Session,
+    ReportType::Blocks,
+];
+
+/// Future resolving to a newly constructed data loader
+pub type LoaderFuture = Pin<Box<dyn Future<Output = Result<Box<dyn DynDataLoader>>> + Send>>;
+
+/// Registry entry describing a provider
+#[derive(Debug, Clone, Copy)]
+pub struct ProviderInfo {
+    /// Command name, also recorded on the provider's usage entries
+    pub name: &'static str,
+    /// One-line description shown in `--help`
+    pub description: &'static str,
+    /// Alternative command names
+    pub aliases: &'static [&'static str],
+    /// Environment variables that override the data directory
+    pub env_vars: &'static [&'static str],
+    /// Reports the provider supports
+    pub reports: &'static [ReportType],
+    /// Default billing block length in hours
+    pub default_block_hours: f64,
+    /// Whether usage is priced only at the costs the provider recorded
+    pub stored_costs: bool,
+    /// Caveat about the provider's data, shown in its help
+    pub note: Option<&'static str>,
+    /// Construct the provider's data loader, from explicit roots if given
+    pub loader: fn(Option<Vec<PathBuf>>) -> LoaderFuture,
+}
+
+impl ProviderInfo {
+    /// Describe a provider backed by the data loader `T`
+    ///
+    /// The provider supports [`DEFAULT_REPORTS`] until
+    /// [`with_reports`](Self::with_reports) says otherwise.
+    pub fn new<T: ProviderDataLoader + 'static>(
+        name: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            description,
+            aliases: &[],
+            env_vars: &[],
+            reports: DEFAULT_REPORTS,
+            default_block_hours: T::DEFAULT_BLOCK_HOURS,
+            stored_costs: false,
+            note: None,
+            loader: construct::<T>,
+        }
+    }
+
+    /// Set alternative command names
+    pub fn with_aliases(mut self, aliases: &'static [&'static str]) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Set the environment variables that override the data directory
+    pub fn with_env_vars(mut self, env_vars: &'static [&'static str]) -> Self {
+        self.env_vars = env_vars;
+        self
+    }
+
+    /// Set the supported reports
+    pub fn with_reports(mut self, reports: &'static [ReportType]) -> Self {
+        self.reports = reports;
+        self
+    }
+
+    /// Price usage only at the costs the provider recorded, in every cost
+    /// mode
+    ///
+    /// For providers whose entries aggregate several requests, where pricing
+    /// the summed tokens would be wrong.
+    pub fn with_stored_costs(mut self) -> Self {
+        self.stored_costs = true;
+        self
+    }
+
+    /// Set a caveat about the provider's data, shown in its help
+    pub fn with_note(mut self, note: &'static str) -> Self {
+        self.note = Some(note);
+        self
+    }
+
+    /// Whether the provider supports the given report
+    pub fn supports(&self, report: ReportType) -> bool {
+        self.reports.contains(&report)
+    }
+
+    /// Whether `name` is the provider's name or one of its aliases
+    pub fn matches(&self, name: &str) -> bool {
+        self.name == name || self.aliases.contains(&name)
+    }
+
+    /// Construct the provider's data loader from its default locations
+    pub async fn load(&self) -> Result<Box<dyn DynDataLoader>> {
+        (self.loader)(None).await
+    }
+
+    /// Construct the provider's data loader reading from explicit roots
+    pub async fn load_from(&self, roots: Vec<PathBuf>) -> Result<Box<dyn DynDataLoader>> {
+        (self.loader)(Some(roots)).await
+    }
+}
+
+fn construct<T: ProviderDataLoader + 'static>(roots: Option<Vec<PathBuf>>) -> LoaderFuture {
+    Box::pin(async move {
+        let loader = match roots {
+            Some(roots) => T::from_roots(roots),
+            None => T::new().await?,
+        };
+        Ok(Box::new(loader) as Box<dyn DynDataLoader>)
+    })
+}
+
+/// Ordered collection of registered providers
+#[derive(Debug, Clone, Default)]
+pub struct ProviderRegistry {
+    providers: Vec<ProviderInfo>,
+}
+
+impl ProviderRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a provider
+    ///
+    /// Providers are listed in registration order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provider's name or one of its aliases is already taken.
+    pub fn register(&mut self, info: ProviderInfo) {
+        for name in std::iter::once(&info.name).chain(info.aliases) {
+            assert!(
+                self.get(name).is_none(),
+                "provider name '{name}' is registered twice"
+            );
+        }
+        self.providers.push(info);
+    }
+
+    /// Look up a provider by name or alias
+    pub fn get(&self, name: &str) -> Option<&ProviderInfo> {
+        self.providers.iter().find(|info| info.matches(name))
+    }
+
+    /// Registered providers, in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &ProviderInfo> {
+        self.providers.iter()
+    }
+
+    /// Number of registered providers
+    pub fn len(&self) -> usize {
+        self.providers.len()
+    }
+
+    /// Whether no providers are registered
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::UsageEntry;
+    use async_trait::async_trait;
+    use futures::stream::Stream;
+
+    struct MockLoader {
+        paths: Vec<PathBuf>,
+    }
+
+    #[async_trait]
+    impl ProviderDataLoader for MockLoader {
+        const DEFAULT_BLOCK_HOURS: f64 = 24.0;
+
+        fn default_roots() -> Result<Vec<PathBuf>> {
+            Ok(vec![PathBuf::from("/tmp/mock")])
+        }
+
+        fn from_roots(roots: Vec<PathBuf>) -> Self {
+            MockLoader { paths: roots }
+        }
+
+        fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+            Box::pin(futures::stream::empty())
+        }
+
+        fn paths(&self) -> &[PathBuf] {
+            &self.paths
+        }
+    }
+
+    fn mock(name: &'static str, aliases: &'static [&'static str]) -> ProviderInfo {
+        ProviderInfo::new::<MockLoader>(name, "Mock usage data").with_aliases(aliases)
+    }
+
+    #[test]
+    fn test_lookup_by_name_and_alias() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(mock("first", &[]));
+        registry.register(mock("second", &["2nd"]));
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.get("second").unwrap().name, "second");
+        assert_eq!(registry.get("2nd").unwrap().name, "second");
+        assert!(registry.get("third").is_none());
+        let names: Vec<_> = registry.iter().map(|info| info.name).collect();
+        assert_eq!(names, ["first", "second"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice")]
+    fn test_duplicate_alias_panics() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(mock("first", &["one"]));
+        registry.register(mock("second", &["one"]));
+    }
+
+    #[test]
+    fn test_supported_reports() {
+        let info = mock("first", &[]);
+        assert!(info.supports(ReportType::Blocks));
+        assert!(!info.supports(ReportType::Weekly));
+
+        let info = info.with_reports(&[ReportType::Daily, ReportType::Weekly]);
+        assert!(info.supports(ReportType::Weekly));
+        assert!(!info.supports(ReportType::Blocks));
+    }
+
+    #[tokio::test]
+    async fn test_load_returns_trait_object() {
+        let info = mock("first", &[]);
+        assert_eq!(info.default_block_hours, 24.0);
+
+        let loader = info.load().await.unwrap();
+        assert_eq!(loader.paths(), [PathBuf::from("/tmp/mock")]);
+        assert_eq!(loader.default_block_hours(), 24.0);
+        let loader = loader.with_date_range(None, None);
+        assert!(
+            futures::StreamExt::next(&mut loader.load_entries())
+                .await
+                .is_none()
+        );
+
+        let loader = info
+            .load_from(vec![PathBuf::from("/backup")])
+            .await
+            .unwrap();
+        assert_eq!(loader.paths(), [PathBuf::from("/backup")]);
+    }
+}
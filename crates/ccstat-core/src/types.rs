@@ -83,6 +83,45 @@ impl AsRef<str> for SessionId {
     }
 }
 
+/// Strongly-typed provider identifier
+///
+/// Names the tool that produced a usage entry (e.g. "claude", "codex"), so
+/// entries from several providers can be merged and still told apart.
+///
+/// # Examples
+/// ```
+/// use ccstat_core::types::ProviderId;
+///
+/// let provider = ProviderId::new("codex");
+/// assert_eq!(provider.as_str(), "codex");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ProviderId(String);
+
+impl ProviderId {
+    /// Create a new ProviderId
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// Get the inner string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ProviderId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for ProviderId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
 /// ISO timestamp wrapper for UTC timestamps
 ///
 /// Provides a strongly-typed wrapper around chrono's `DateTime<Utc>` with
@@ -183,7 +222,9 @@ impl DailyDate {
 /// Token counts for usage tracking
 ///
 /// This struct tracks all types of tokens consumed during Claude API usage,
-/// including input, output, and cache-related tokens.
+/// including input, output, and cache-related tokens. Reasoning ("thinking")
+/// tokens are counted separately from output tokens, for providers that
+/// report them.
 ///
 /// # Examples
 /// ```
@@ -196,6 +237,18 @@ impl DailyDate {
 /// let tokens2 = TokenCounts::new(50, 25, 5, 2);
 /// let combined = tokens + tokens2;
 /// assert_eq!(combined.input_tokens, 150);
+///
+/// // Reasoning tokens count towards the total
+/// let tokens = TokenCounts::new(100, 50, 0, 0).with_reasoning_tokens(30);
+/// assert_eq!(tokens.total(), 180);
+///
+/// // The cache TTL breakdown is part of `cache_creation_tokens`
+/// let tokens = TokenCounts::new(0, 0, 100, 0).with_cache_creation_ttl(40, 60);
+/// assert_eq!(tokens.total(), 100);
+///
+/// // Server-side tool requests are counted, but are not tokens
+/// let tokens = TokenCounts::new(100, 50, 0, 0).with_server_tool_use(3, 1);
+/// assert_eq!(tokens.total(), 150);
 /// ```
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TokenCounts {
@@ -207,6 +260,21 @@ pub struct TokenCounts {
     pub cache_creation_tokens: u64,
     /// Cache read tokens
     pub cache_read_tokens: u64,
+    /// Reasoning tokens, not included in `output_tokens`
+    #[serde(default)]
+    pub reasoning_tokens: u64,
+    /// Part of `cache_creation_tokens` written with a 5-minute TTL
+    #[serde(default)]
+    pub cache_creation_5m_tokens: u64,
+    /// Part of `cache_creation_tokens` written with a 1-hour TTL
+    #[serde(default)]
+    pub cache_creation_1h_tokens: u64,
+    /// Server-side web search requests, billed per request
+    #[serde(default)]
+    pub web_search_requests: u64,
+    /// Server-side web fetch requests
+    #[serde(default)]
+    pub web_fetch_requests: u64,
 }
 
 impl TokenCounts {
@@ -222,12 +290,52 @@ impl TokenCounts {
             output_tokens,
             cache_creation_tokens,
             cache_read_tokens,
+            reasoning_tokens: 0,
+            cache_creation_5m_tokens: 0,
+            cache_creation_1h_tokens: 0,
+            web_search_requests: 0,
+            web_fetch_requests: 0,
         }
     }
 
+    /// Set the reasoning token count
+    pub fn with_reasoning_tokens(mut self, reasoning_tokens: u64) -> Self {
+        self.reasoning_tokens = reasoning_tokens;
+        self
+    }
+
+    /// Set how the cache creation tokens split between the 5-minute and
+    /// 1-hour TTLs
+    pub fn with_cache_creation_ttl(mut self, five_minute: u64, one_hour: u64) -> Self {
+        self.cache_creation_5m_tokens = five_minute;
+        self.cache_creation_1h_tokens = one_hour;
+        self
+    }
+
+    /// Set the number of server-side web search and web fetch requests
+    pub fn with_server_tool_use(
+        mut self,
+        web_search_requests: u64,
+        web_fetch_requests: u64,
+    ) -> Self {
+        self.web_search_requests = web_search_requests;
+        self.web_fetch_requests = web_fetch_requests;
+        self
+    }
+
+    /// Size of the request's context: input plus cache creation and cache
+    /// read tokens
+    pub fn context_tokens(&self) -> u64 {
+        self.input_tokens + self.cache_creation_tokens + self.cache_read_tokens
+    }
+
     /// Calculate total tokens
     pub fn total(&self) -> u64 {
-        self.input_tokens + self.output_tokens + self.cache_creation_tokens + self.cache_read_tokens
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_creation_tokens
+            + self.cache_read_tokens
+            + self.reasoning_tokens
     }
 }
 
@@ -240,6 +348,13 @@ impl Add for TokenCounts {
             output_tokens: self.output_tokens + other.output_tokens,
             cache_creation_tokens: self.cache_creation_tokens + other.cache_creation_tokens,
             cache_read_tokens: self.cache_read_tokens + other.cache_read_tokens,
+            reasoning_tokens: self.reasoning_tokens + other.reasoning_tokens,
+            cache_creation_5m_tokens: self.cache_creation_5m_tokens
+                + other.cache_creation_5m_tokens,
+            cache_creation_1h_tokens: self.cache_creation_1h_tokens
+                + other.cache_creation_1h_tokens,
+            web_search_requests: self.web_search_requests + other.web_search_requests,
+            web_fetch_requests: self.web_fetch_requests + other.web_fetch_requests,
         }
     }
 }
@@ -250,9 +365,41 @@ impl AddAssign for TokenCounts {
         self.output_tokens += other.output_tokens;
         self.cache_creation_tokens += other.cache_creation_tokens;
         self.cache_read_tokens += other.cache_read_tokens;
+        self.reasoning_tokens += other.reasoning_tokens;
+        self.cache_creation_5m_tokens += other.cache_creation_5m_tokens;
+        self.cache_creation_1h_tokens += other.cache_creation_1h_tokens;
+        self.web_search_requests += other.web_search_requests;
+        self.web_fetch_requests += other.web_fetch_requests;
     }
 }
 
+/// Usage of a single provider rate-limit window
+///
+/// Codex reports a short primary window and a longer secondary window, each
+/// with the share of its limit already used and when it resets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitWindow {
+    /// Share of the window's limit already used, in percent
+    pub used_percent: f64,
+    /// Length of the window in minutes, if reported
+    pub window_minutes: Option<u64>,
+    /// When the window resets, if reported
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+/// Rate-limit usage reported by a provider at a point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitSnapshot {
+    /// When the provider reported this snapshot
+    pub timestamp: DateTime<Utc>,
+    /// Session whose log carried the snapshot
+    pub session_id: SessionId,
+    /// Primary (short) window
+    pub primary: Option<RateLimitWindow>,
+    /// Secondary (long) window
+    pub secondary: Option<RateLimitWindow>,
+}
+
 /// Cost calculation mode
 ///
 /// Determines how costs are calculated when generating reports. This enum
@@ -327,19 +474,202 @@ impl std::str::FromStr for CostMode {
 ///     input_cost_per_token: Some(0.00001),  // $0.01 per 1K tokens
 ///     output_cost_per_token: Some(0.00003), // $0.03 per 1K tokens
 ///     cache_creation_input_token_cost: Some(0.0000125),
+///     cache_creation_input_token_cost_above_1hr: Some(0.00002),
 ///     cache_read_input_token_cost: Some(0.0000025),
+///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct ModelPricing {
     /// Cost per input token in USD
     pub input_cost_per_token: Option<f64>,
     /// Cost per output token in USD
     pub output_cost_per_token: Option<f64>,
-    /// Cost per cache creation token in USD
+    /// Cost per cache creation token in USD (the 5-minute TTL rate)
     pub cache_creation_input_token_cost: Option<f64>,
+    /// Cost per cache creation token written with a 1-hour TTL in USD
+    #[serde(default)]
+    pub cache_creation_input_token_cost_above_1hr: Option<f64>,
     /// Cost per cache read token in USD
     pub cache_read_input_token_cost: Option<f64>,
+    /// Cost per reasoning token in USD, if it differs from the output rate
+    #[serde(default)]
+    pub output_cost_per_reasoning_token: Option<f64>,
+    /// Cost per input token in USD for requests with a context above 200k tokens
+    #[serde(default)]
+    pub input_cost_per_token_above_200k_tokens: Option<f64>,
+    /// Cost per output token in USD for requests with a context above 200k tokens
+    #[serde(default)]
+    pub output_cost_per_token_above_200k_tokens: Option<f64>,
+    /// Cost per cache creation token in USD for requests with a context above
+    /// 200k tokens
+    #[serde(default)]
+    pub cache_creation_input_token_cost_above_200k_tokens: Option<f64>,
+    /// Cost per 1-hour cache creation token in USD for requests with a
+    /// context above 200k tokens
+    #[serde(default)]
+    pub cache_creation_input_token_cost_above_1hr_above_200k_tokens: Option<f64>,
+    /// Cost per cache read token in USD for requests with a context above
+    /// 200k tokens
+    #[serde(default)]
+    pub cache_read_input_token_cost_above_200k_tokens: Option<f64>,
+    /// Cost per server-side web search request in USD, by search context size
+    #[serde(default)]
+    pub search_context_cost_per_query: Option<SearchContextCost>,
+    /// Date, in UTC, from which these rates apply when earlier rates are known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_from: Option<NaiveDate>,
+    /// Rates in force before `effective_from`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rate_periods: Vec<RatePeriod>,
+}
+
+/// Rates a model was priced at from a given date
+///
+/// A period applies from its `effective_from` date until the next period
+/// starts, or until the model's current rates take effect. Rates it does not
+/// list were not charged.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RatePeriod {
+    /// Date, in UTC, from which the rates apply
+    pub effective_from: NaiveDate,
+    /// Rates in force during the period
+    #[serde(flatten)]
+    pub rates: ModelPricing,
+}
+
+impl ModelPricing {
+    /// Rates in force at `timestamp`
+    ///
+    /// These are the current rates unless the timestamp falls before their
+    /// `effective_from` date, in which case it is the latest rate period
+    /// started by then, or the earliest one for older timestamps.
+    ///
+    /// # Examples
+    /// ```
+    /// use ccstat_core::types::{ModelPricing, RatePeriod};
+    /// use chrono::{NaiveDate, TimeZone, Utc};
+    ///
+    /// let pricing = ModelPricing {
+    ///     input_cost_per_token: Some(0.000008),
+    ///     effective_from: NaiveDate::from_ymd_opt(2023, 11, 21),
+    ///     rate_periods: vec![RatePeriod {
+    ///         effective_from: NaiveDate::from_ymd_opt(2023, 7, 11).unwrap(),
+    ///         rates: ModelPricing {
+    ///             input_cost_per_token: Some(0.00001102),
+    ///             ..Default::default()
+    ///         },
+    ///     }],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let august = Utc.with_ymd_and_hms(2023, 8, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(pricing.at(august).input_cost_per_token, Some(0.00001102));
+    /// let december = Utc.with_ymd_and_hms(2023, 12, 1, 0, 0, 0).unwrap();
+    /// assert_eq!(pricing.at(december).input_cost_per_token, Some(0.000008));
+    /// ```
+    pub fn at(&self, timestamp: DateTime<Utc>) -> &ModelPricing {
+        let date = timestamp.date_naive();
+        match self.effective_from {
+            Some(from) if date < from => self
+                .rate_periods
+                .iter()
+                .filter(|period| period.effective_from <= date)
+                .max_by_key(|period| period.effective_from)
+                .or_else(|| {
+                    self.rate_periods
+                        .iter()
+                        .min_by_key(|period| period.effective_from)
+                })
+                .map_or(self, |period| &period.rates),
+            _ => self,
+        }
+    }
+
+    /// Pricing with every rate multiplied by `factor`
+    ///
+    /// # Examples
+    /// ```
+    /// use ccstat_core::types::ModelPricing;
+    ///
+    /// let pricing = ModelPricing {
+    ///     input_cost_per_token: Some(0.00001),
+    ///     ..Default::default()
+    /// };
+    /// let discounted = pricing.scaled(0.5);
+    /// assert_eq!(discounted.input_cost_per_token, Some(0.000005));
+    /// assert!(discounted.output_cost_per_token.is_none());
+    /// ```
+    pub fn scaled(&self, factor: f64) -> Self {
+        let scale = |rate: Option<f64>| rate.map(|rate| rate * factor);
+        Self {
+            input_cost_per_token: scale(self.input_cost_per_token),
+            output_cost_per_token: scale(self.output_cost_per_token),
+            cache_creation_input_token_cost: scale(self.cache_creation_input_token_cost),
+            cache_creation_input_token_cost_above_1hr: scale(
+                self.cache_creation_input_token_cost_above_1hr,
+            ),
+            cache_read_input_token_cost: scale(self.cache_read_input_token_cost),
+            output_cost_per_reasoning_token: scale(self.output_cost_per_reasoning_token),
+            input_cost_per_token_above_200k_tokens: scale(
+                self.input_cost_per_token_above_200k_tokens,
+            ),
+            output_cost_per_token_above_200k_tokens: scale(
+                self.output_cost_per_token_above_200k_tokens,
+            ),
+            cache_creation_input_token_cost_above_200k_tokens: scale(
+                self.cache_creation_input_token_cost_above_200k_tokens,
+            ),
+            cache_creation_input_token_cost_above_1hr_above_200k_tokens: scale(
+                self.cache_creation_input_token_cost_above_1hr_above_200k_tokens,
+            ),
+            cache_read_input_token_cost_above_200k_tokens: scale(
+                self.cache_read_input_token_cost_above_200k_tokens,
+            ),
+            search_context_cost_per_query: self.search_context_cost_per_query.as_ref().map(
+                |cost| SearchContextCost {
+                    search_context_size_low: scale(cost.search_context_size_low),
+                    search_context_size_medium: scale(cost.search_context_size_medium),
+                    search_context_size_high: scale(cost.search_context_size_high),
+                },
+            ),
+            effective_from: self.effective_from,
+            rate_periods: self
+                .rate_periods
+                .iter()
+                .map(|period| RatePeriod {
+                    effective_from: period.effective_from,
+                    rates: period.rates.scaled(factor),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Cost of one web search request in USD, by search context size
+///
+/// Mirrors LiteLLM's `search_context_cost_per_query`. Anthropic charges the
+/// same for every size.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SearchContextCost {
+    /// Cost with a low search context size
+    #[serde(default)]
+    pub search_context_size_low: Option<f64>,
+    /// Cost with a medium search context size
+    #[serde(default)]
+    pub search_context_size_medium: Option<f64>,
+    /// Cost with a high search context size
+    #[serde(default)]
+    pub search_context_size_high: Option<f64>,
+}
+
+impl SearchContextCost {
+    /// Cost of one request, at the default (medium) context size
+    pub fn per_request(&self) -> Option<f64> {
+        self.search_context_size_medium
+            .or(self.search_context_size_low)
+            .or(self.search_context_size_high)
+    }
 }
 
 /// Raw message usage data from the API
@@ -356,6 +686,66 @@ pub struct MessageUsage {
     /// Cache read tokens
     #[serde(default)]
     pub cache_read_input_tokens: u64,
+    /// Cache creation tokens broken down by TTL
+    #[serde(default)]
+    pub cache_creation: Option<CacheCreation>,
+    /// Server-side tool requests
+    #[serde(default)]
+    pub server_tool_use: Option<ServerToolUse>,
+}
+
+/// Server-side tool requests, as reported in `usage.server_tool_use`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerToolUse {
+    /// Web search requests
+    #[serde(default)]
+    pub web_search_requests: u64,
+    /// Web fetch requests
+    #[serde(default)]
+    pub web_fetch_requests: u64,
+}
+
+/// Cache creation tokens by TTL, as reported in `usage.cache_creation`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheCreation {
+    /// Tokens written with a 5-minute TTL
+    #[serde(default)]
+    pub ephemeral_5m_input_tokens: u64,
+    /// Tokens written with a 1-hour TTL
+    #[serde(default)]
+    pub ephemeral_1h_input_tokens: u64,
+}
+
+impl MessageUsage {
+    /// Convert to token counts, keeping the cache TTL breakdown and server
+    /// tool requests if present
+    pub fn token_counts(&self) -> TokenCounts {
+        let server_tool_use = self.server_tool_use.clone().unwrap_or_default();
+        let tokens = TokenCounts::new(
+            self.input_tokens,
+            self.output_tokens,
+            self.cache_creation_input_tokens,
+            self.cache_read_input_tokens,
+        )
+        .with_server_tool_use(
+            server_tool_use.web_search_requests,
+            server_tool_use.web_fetch_requests,
+        );
+        match &self.cache_creation {
+            Some(ttl) => {
+                let buckets = ttl.ephemeral_5m_input_tokens + ttl.ephemeral_1h_input_tokens;
+                TokenCounts {
+                    cache_creation_tokens: tokens.cache_creation_tokens.max(buckets),
+                    ..tokens
+                }
+                .with_cache_creation_ttl(
+                    ttl.ephemeral_5m_input_tokens,
+                    ttl.ephemeral_1h_input_tokens,
+                )
+            }
+            None => tokens,
+        }
+    }
 }
 
 /// Raw message data from the API
@@ -428,7 +818,7 @@ pub struct RawJsonlEntry {
 ///
 /// # Examples
 /// ```
-/// use ccstat_core::types::{UsageEntry, SessionId, ISOTimestamp, ModelName, TokenCounts};
+/// use ccstat_core::types::{UsageEntry, SessionId, ISOTimestamp, ModelName, ProviderId, TokenCounts};
 /// use chrono::Utc;
 ///
 /// let entry = UsageEntry {
@@ -439,6 +829,7 @@ pub struct RawJsonlEntry {
 ///     total_cost: Some(0.0255),
 ///     project: Some("my-project".to_string()),
 ///     instance_id: Some("instance-123".to_string()),
+///     provider: ProviderId::new("claude"),
 /// };
 ///
 /// // Entries can be serialized to JSON
@@ -466,6 +857,15 @@ pub struct UsageEntry {
     /// Instance identifier (UUID) for the API call (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instance_id: Option<String>,
+    /// Provider that produced this entry
+    ///
+    /// Entries serialized before providers were recorded are Claude entries.
+    #[serde(default = "default_provider")]
+    pub provider: ProviderId,
+}
+
+fn default_provider() -> ProviderId {
+    ProviderId::new("claude")
 }
 
 impl UsageEntry {
@@ -531,24 +931,26 @@ impl UsageEntry {
             session_id: SessionId::new(session_id),
             timestamp,
             model: ModelName::new(raw.message.model),
-            tokens: TokenCounts {
-                input_tokens: raw.message.usage.input_tokens,
-                output_tokens: raw.message.usage.output_tokens,
-                cache_creation_tokens: raw.message.usage.cache_creation_input_tokens,
-                cache_read_tokens: raw.message.usage.cache_read_input_tokens,
-            },
+            tokens: raw.message.usage.token_counts(),
             total_cost,
-            project: raw.cwd.as_ref().and_then(|cwd| {
-                // Extract project name from cwd path
-                std::path::Path::new(cwd)
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .map(|s| s.to_string())
-            }),
+            project: raw.cwd.as_deref().and_then(Self::project_from_cwd),
             instance_id,
+            provider: ProviderId::new("claude"),
         })
     }
 
+    /// Derive a project name from a working directory
+    ///
+    /// The project is the last path component, so `/home/user/my-app`
+    /// becomes `my-app`. All providers use this so `--project` matches
+    /// the same name regardless of which tool recorded the usage.
+    pub fn project_from_cwd(cwd: &str) -> Option<String> {
+        std::path::Path::new(cwd)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|s| s.to_string())
+    }
+
     /// Generate a deduplication key from message.id and requestId
     pub fn dedup_key(raw: &RawJsonlEntry) -> Option<String> {
         match (&raw.message.id, &raw.request_id) {
@@ -589,6 +991,50 @@ mod tests {
         assert_eq!(sum.cache_creation_tokens, 30);
         assert_eq!(sum.cache_read_tokens, 15);
         assert_eq!(sum.total(), 495);
+
+        let mut sum = tokens1.with_reasoning_tokens(40);
+        sum += tokens2.with_reasoning_tokens(2);
+        assert_eq!(sum.reasoning_tokens, 42);
+        assert_eq!(sum.total(), 537);
+    }
+
+    #[test]
+    fn test_cache_creation_ttl_parsing() {
+        let usage: MessageUsage = serde_json::from_str(
+            r#"{"input_tokens": 10, "output_tokens": 5, "cache_creation_input_tokens": 1500,
+                "cache_read_input_tokens": 0,
+                "cache_creation": {"ephemeral_5m_input_tokens": 500, "ephemeral_1h_input_tokens": 1000}}"#,
+        )
+        .unwrap();
+        let tokens = usage.token_counts();
+        assert_eq!(tokens.cache_creation_tokens, 1500);
+        assert_eq!(tokens.cache_creation_5m_tokens, 500);
+        assert_eq!(tokens.cache_creation_1h_tokens, 1000);
+        assert_eq!(tokens.total(), 1515);
+
+        // Older logs only carry the flat count
+        let usage: MessageUsage =
+            serde_json::from_str(r#"{"input_tokens": 10, "cache_creation_input_tokens": 1500}"#)
+                .unwrap();
+        let tokens = usage.token_counts();
+        assert_eq!(tokens.cache_creation_tokens, 1500);
+        assert_eq!(tokens.cache_creation_1h_tokens, 0);
+    }
+
+    #[test]
+    fn test_server_tool_use_parsing() {
+        let usage: MessageUsage = serde_json::from_str(
+            r#"{"input_tokens": 10, "output_tokens": 5,
+                "server_tool_use": {"web_search_requests": 3, "web_fetch_requests": 1}}"#,
+        )
+        .unwrap();
+        let tokens = usage.token_counts();
+        assert_eq!(tokens.web_search_requests, 3);
+        assert_eq!(tokens.web_fetch_requests, 1);
+        assert_eq!(tokens.total(), 15);
+
+        let sum = tokens + tokens;
+        assert_eq!(sum.web_search_requests, 6);
     }
 
     #[test]
@@ -611,6 +1057,19 @@ mod tests {
         assert_eq!(daily.format("%Y-%m-%d"), "2024-01-15");
     }
 
+    #[test]
+    fn test_project_from_cwd() {
+        assert_eq!(
+            UsageEntry::project_from_cwd("/home/user/my-app"),
+            Some("my-app".to_string())
+        );
+        assert_eq!(
+            UsageEntry::project_from_cwd("/home/user/my-app/"),
+            Some("my-app".to_string())
+        );
+        assert_eq!(UsageEntry::project_from_cwd("/"), None);
+    }
+
     #[test]
     fn test_dedup_key_generation() {
         // Test with both message.id and requestId
@@ -624,6 +1083,8 @@ mod tests {
                     output_tokens: 50,
                     cache_creation_input_tokens: 0,
                     cache_read_input_tokens: 0,
+                    cache_creation: None,
+                    server_tool_use: None,
                 },
                 id: Some("msg_123".to_string()),
                 content: None,
@@ -677,6 +1138,8 @@ mod tests {
                     output_tokens: 50,
                     cache_creation_input_tokens: 0,
                     cache_read_input_tokens: 0,
+                    cache_creation: None,
+                    server_tool_use: None,
                 },
                 id: None,
                 content: None,
@@ -697,6 +1160,7 @@ mod tests {
 
         let entry = UsageEntry::from_raw(raw1).unwrap();
         assert_eq!(entry.total_cost, Some(0.123));
+        assert_eq!(entry.provider.as_str(), "claude");
 
         // Test snake_case cost_usd field
         let raw2 = RawJsonlEntry {
@@ -709,6 +1173,8 @@ mod tests {
                     output_tokens: 50,
                     cache_creation_input_tokens: 0,
                     cache_read_input_tokens: 0,
+                    cache_creation: None,
+                    server_tool_use: None,
                 },
                 id: None,
                 content: None,
@@ -741,6 +1207,8 @@ mod tests {
                     output_tokens: 50,
                     cache_creation_input_tokens: 0,
                     cache_read_input_tokens: 0,
+                    cache_creation: None,
+                    server_tool_use: None,
                 },
                 id: None,
                 content: None,
@@ -775,6 +1243,8 @@ mod tests {
                     output_tokens: 50,
                     cache_creation_input_tokens: 0,
                     cache_read_input_tokens: 0,
+                    cache_creation: None,
+                    server_tool_use: None,
                 },
                 id: None,
                 content: None,
@@ -809,6 +1279,8 @@ mod tests {
                     output_tokens: 50,
                     cache_creation_input_tokens: 0,
                     cache_read_input_tokens: 0,
+                    cache_creation: None,
+                    server_tool_use: None,
                 },
                 id: Some("msg_123".to_string()),
                 content: None,
@@ -884,6 +1356,8 @@ mod tests {
                     output_tokens: 50,
                     cache_creation_input_tokens: 0,
                     cache_read_input_tokens: 0,
+                    cache_creation: None,
+                    server_tool_use: None,
                 },
                 id: Some("msg_123".to_string()),
                 content: None,
@@ -929,4 +1403,59 @@ mod tests {
         assert!(ts2 > ts1);
         assert_eq!(ts1, ts1);
     }
+
+    #[test]
+    fn test_model_pricing_rate_periods() {
+        let period = |year, month, input| RatePeriod {
+            effective_from: NaiveDate::from_ymd_opt(year, month, 1).unwrap(),
+            rates: ModelPricing {
+                input_cost_per_token: Some(input),
+                ..Default::default()
+            },
+        };
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(0.000001),
+            effective_from: NaiveDate::from_ymd_opt(2024, 6, 1),
+            // Periods need not be in order
+            rate_periods: vec![period(2024, 3, 0.000002), period(2024, 1, 0.000003)],
+            ..Default::default()
+        };
+        let at = |year, month, day| {
+            pricing
+                .at(Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap())
+                .input_cost_per_token
+        };
+
+        assert_eq!(at(2023, 6, 1), Some(0.000003));
+        assert_eq!(at(2024, 1, 1), Some(0.000003));
+        assert_eq!(at(2024, 2, 29), Some(0.000003));
+        assert_eq!(at(2024, 3, 1), Some(0.000002));
+        assert_eq!(at(2024, 5, 31), Some(0.000002));
+        assert_eq!(at(2024, 6, 1), Some(0.000001));
+
+        // Without an effective date the current rates always apply
+        let current = ModelPricing {
+            effective_from: None,
+            ..pricing.clone()
+        };
+        assert_eq!(current.at(Utc::now()), &current);
+
+        // Discounts apply to earlier rates too
+        let scaled = pricing.scaled(0.5);
+        assert_eq!(scaled.effective_from, pricing.effective_from);
+        assert_eq!(
+            scaled.rate_periods[0].rates.input_cost_per_token,
+            Some(0.000001)
+        );
+
+        // Periods serialize flat, and pricing without history as before
+        let json = serde_json::to_value(&pricing).unwrap();
+        assert_eq!(json["effective_from"], "2024-06-01");
+        assert_eq!(json["rate_periods"][0]["effective_from"], "2024-03-01");
+        assert_eq!(json["rate_periods"][0]["input_cost_per_token"], 0.000002);
+        let parsed: ModelPricing = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, pricing);
+        let json = serde_json::to_value(ModelPricing::default()).unwrap();
+        assert!(json.get("rate_periods").is_none());
+    }
 }
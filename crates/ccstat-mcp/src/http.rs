@@ -0,0 +1,1008 @@
+//! Streamable HTTP transport for the MCP server
+//!
+//! Implements the MCP streamable-HTTP transport on a single `/mcp` endpoint:
+//!
+//! - `POST` carries one JSON-RPC message. Requests are answered with a JSON
+//!   body (or a one-event SSE stream for clients that only accept
+//!   `text/event-stream`); notifications and responses get `202 Accepted`.
+//! - `GET` opens an SSE stream for server-initiated messages.
+//! - `DELETE` ends a session.
+//!
+//! A session is created by a successful `initialize` request and identified
+//! by the `Mcp-Session-Id` response header, which clients must echo on every
+//! later request. Sessions end on `DELETE`, after 30 minutes without requests
+//! or an open stream, or when the least recently used one is evicted to make
+//! room for a new session. Tool calls go through the same [`McpServer`] as
+//! the stdio transport.
+
+use crate::protocol::{
+    INVALID_REQUEST, JsonRpcError, JsonRpcResponse, PARSE_ERROR, SUPPORTED_PROTOCOL_VERSIONS,
+};
+use crate::resources::resource_updated_notification;
+use crate::server::McpServer;
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use ccstat_core::error::Result;
+use futures::Stream;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+/// Path of the MCP endpoint
+pub const MCP_ENDPOINT: &str = "/mcp";
+
+/// Header carrying the session id
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Header carrying the negotiated protocol version
+pub const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+/// Capacity of each session's server-to-client message buffer
+const SESSION_CHANNEL_CAPACITY: usize = 64;
+
+/// How long a session lasts without requests or an open stream
+pub const DEFAULT_SESSION_IDLE_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Most sessions kept at once
+pub const DEFAULT_MAX_SESSIONS: usize = 256;
+
+/// Settings for the HTTP transport
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    /// Address to listen on
+    pub bind: SocketAddr,
+    /// Require `Authorization: Bearer <token>` on every request when set
+    pub bearer_token: Option<String>,
+    /// Host names accepted besides loopback and the bind address, for both
+    /// the `Host` header and browser `Origin`s
+    pub allowed_hosts: Vec<String>,
+}
+
+impl HttpConfig {
+    /// Create a configuration listening on `bind` without authentication
+    pub fn new(bind: SocketAddr) -> Self {
+        Self {
+            bind,
+            bearer_token: None,
+            allowed_hosts: Vec::new(),
+        }
+    }
+
+    /// Require the given bearer token
+    pub fn with_bearer_token(mut self, token: Option<String>) -> Self {
+        self.bearer_token = token.filter(|t| !t.is_empty());
+        self
+    }
+
+    /// Also accept requests addressed to, and browser requests from, these
+    /// host names (without ports)
+    pub fn with_allowed_hosts(mut self, hosts: Vec<String>) -> Self {
+        self.allowed_hosts = hosts;
+        self
+    }
+}
+
+/// Live HTTP sessions, keyed by session id
+///
+/// Each session owns a broadcast channel feeding its `GET` SSE streams.
+/// Sessions idle for longer than the idle TTL are dropped, and creating a
+/// session beyond the cap evicts the least recently used one, so clients
+/// that never send `DELETE` cannot grow the registry without bound.
+#[derive(Clone)]
+pub struct SessionRegistry {
+    sessions: Arc<Mutex<HashMap<String, Session>>>,
+    idle_ttl: Duration,
+    max_sessions: usize,
+}
+
+struct Session {
+    sender: broadcast::Sender<String>,
+    /// Time of the last request naming the session
+    last_active: Instant,
+}
+
+impl Session {
+    /// Whether the session has had no requests for `ttl` and no open stream
+    fn is_idle(&self, now: Instant, ttl: Duration) -> bool {
+        self.sender.receiver_count() == 0 && now.duration_since(self.last_active) >= ttl
+    }
+}
+
+impl Default for SessionRegistry {
+    fn default() -> Self {
+        Self {
+            sessions: Arc::default(),
+            idle_ttl: DEFAULT_SESSION_IDLE_TTL,
+            max_sessions: DEFAULT_MAX_SESSIONS,
+        }
+    }
+}
+
+impl SessionRegistry {
+    /// Drop sessions after `ttl` without requests or an open stream
+    pub fn with_idle_ttl(mut self, ttl: Duration) -> Self {
+        self.idle_ttl = ttl;
+        self
+    }
+
+    /// Keep at most `max` sessions, evicting the least recently used
+    pub fn with_max_sessions(mut self, max: usize) -> Self {
+        self.max_sessions = max.max(1);
+        self
+    }
+
+    fn create(&self) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let (sender, _) = broadcast::channel(SESSION_CHANNEL_CAPACITY);
+        let now = Instant::now();
+        let mut sessions = self.lock();
+        self.remove_idle(&mut sessions, now);
+        while sessions.len() >= self.max_sessions {
+            let Some(oldest) = sessions
+                .iter()
+                .min_by_key(|(_, session)| session.last_active)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            debug!("Evicting least recently used MCP HTTP session {}", oldest);
+            sessions.remove(&oldest);
+        }
+        sessions.insert(
+            id.clone(),
+            Session {
+                sender,
+                last_active: now,
+            },
+        );
+        id
+    }
+
+    /// Whether the session is live, marking it as active if so
+    fn touch(&self, id: &str) -> bool {
+        let now = Instant::now();
+        let mut sessions = self.lock();
+        self.remove_idle(&mut sessions, now);
+        match sessions.get_mut(id) {
+            Some(session) => {
+                session.last_active = now;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn subscribe(&self, id: &str) -> Option<broadcast::Receiver<String>> {
+        self.lock()
+            .get(id)
+            .map(|session| session.sender.subscribe())
+    }
+
+    fn remove(&self, id: &str) -> bool {
+        self.lock().remove(id).is_some()
+    }
+
+    /// Send a serialized JSON-RPC message to every open stream of a session
+    ///
+    /// Returns `false` if the session does not exist. Messages sent while no
+    /// stream is open are dropped, as the transport allows.
+    pub fn send(&self, id: &str, message: String) -> bool {
+        match self.lock().get(id) {
+            Some(session) => {
+                let _ = session.sender.send(message);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Number of live sessions
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether there are no live sessions
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn remove_idle(&self, sessions: &mut HashMap<String, Session>, now: Instant) {
+        sessions.retain(|id, session| {
+            let idle = session.is_idle(now, self.idle_ttl);
+            if idle {
+                debug!("Expired idle MCP HTTP session {}", id);
+            }
+            !idle
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Session>> {
+        // A poisoned lock only means another request panicked mid-insert;
+        // the map itself is still usable.
+        self.sessions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[derive(Clone)]
+struct HttpState {
+    server: McpServer,
+    sessions: SessionRegistry,
+    bearer_token: Option<Arc<str>>,
+    /// Host names the `Host` header may name: the allowed hosts and the
+    /// bind address
+    local_hosts: Arc<[String]>,
+    /// Host names browser `Origin`s may name besides loopback
+    allowed_hosts: Arc<[String]>,
+}
+
+/// Build the axum router serving the MCP endpoint
+pub fn router(server: McpServer, config: &HttpConfig) -> Router {
+    router_with_sessions(server, config, SessionRegistry::default())
+}
+
+/// Build the router with an existing session registry
+///
+/// Useful when other components need to push messages to HTTP sessions.
+pub fn router_with_sessions(
+    server: McpServer,
+    config: &HttpConfig,
+    sessions: SessionRegistry,
+) -> Router {
+    let state = HttpState {
+        server,
+        sessions,
+        bearer_token: config.bearer_token.as_deref().map(Arc::from),
+        local_hosts: config
+            .allowed_hosts
+            .iter()
+            .cloned()
+            .chain(bind_host(config.bind))
+            .collect(),
+        allowed_hosts: config.allowed_hosts.iter().cloned().collect(),
+    };
+    Router::new()
+        .route(
+            MCP_ENDPOINT,
+            post(handle_post).get(handle_get).delete(handle_delete),
+        )
+        .with_state(state)
+}
+
+impl McpServer {
+    /// Serve MCP over streamable HTTP until the process is interrupted
+    pub async fn serve_http(&self, config: HttpConfig) -> Result<()> {
+        if config.bearer_token.is_none() && !config.bind.ip().is_loopback() {
+            warn!(
+                "MCP HTTP server is listening on non-loopback address {} without a bearer token",
+                config.bind
+            );
+        }
+        if config.bind.ip().is_unspecified() && config.allowed_hosts.is_empty() {
+            warn!(
+                "MCP HTTP server is listening on {} but only accepts loopback hosts; \
+                 list the host names clients use with --allow-host",
+                config.bind
+            );
+        }
+
+        let listener = tokio::net::TcpListener::bind(config.bind).await?;
+        info!(
+            "MCP server listening on http://{}{}",
+            listener.local_addr()?,
+            MCP_ENDPOINT
+        );
+
+        let sessions = SessionRegistry::default();
+        tokio::spawn(forward_resource_updates(self.clone(), sessions.clone()));
+
+        let app = router_with_sessions(self.clone(), &config, sessions);
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async {
+                let _ = tokio::signal::ctrl_c().await;
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+/// Push resource updates to the SSE streams of subscribed sessions
+///
+/// Subscribes to the server's updates immediately; the returned future runs
+/// until the update channel is closed.
+pub fn forward_resource_updates(
+    server: McpServer,
+    sessions: SessionRegistry,
+) -> impl Future<Output = ()> + Send + 'static {
+    let mut updates = server.resource_updates();
+    async move {
+        loop {
+            match updates.recv().await {
+                Ok(uri) => {
+                    for client in server.subscribers(&uri) {
+                        if !sessions.send(&client, resource_updated_notification(&uri)) {
+                            // Session is gone; drop its stale subscriptions
+                            server.remove_client(&client);
+                        }
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Dropped {} resource updates", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Request validation
+// ---------------------------------------------------------------------------
+
+/// A request refused before it reaches the MCP server
+struct Rejection {
+    status: StatusCode,
+    message: String,
+}
+
+impl Rejection {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> Response {
+        let mut response = (self.status, self.message).into_response();
+        if self.status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
+
+/// Reject the request unless it carries the configured bearer token
+fn check_auth(state: &HttpState, headers: &HeaderMap) -> std::result::Result<(), Rejection> {
+    let Some(expected) = state.bearer_token.as_deref() else {
+        return Ok(());
+    };
+    let provided = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+        _ => Err(Rejection::new(StatusCode::UNAUTHORIZED, "Unauthorized")),
+    }
+}
+
+/// Compare two byte strings without short-circuiting on the first mismatch
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Reject requests for foreign hosts and browser requests from foreign
+/// origins (DNS rebinding protection)
+///
+/// After a DNS rebinding, a browser names the attacker's host in both
+/// `Host` and `Origin`, so neither may be trusted by comparing the two.
+/// `Host` must be loopback, the bind address, or an allowed host; `Origin`,
+/// which non-browser clients do not send, must be loopback or an allowed
+/// host.
+fn check_origin(state: &HttpState, headers: &HeaderMap) -> std::result::Result<(), Rejection> {
+    if let Some(host) = headers.get(header::HOST).and_then(|v| v.to_str().ok())
+        && !is_allowed_host(host_name(host), &state.local_hosts)
+    {
+        warn!("Rejected MCP request for host {}", host);
+        return Err(Rejection::new(StatusCode::FORBIDDEN, "Forbidden host"));
+    }
+
+    let Some(origin) = headers.get(header::ORIGIN).and_then(|v| v.to_str().ok()) else {
+        return Ok(());
+    };
+    let origin_host = origin
+        .split_once("://")
+        .map_or(origin, |(_, rest)| rest)
+        .trim_end_matches('/');
+    if is_allowed_host(host_name(origin_host), &state.allowed_hosts) {
+        Ok(())
+    } else {
+        warn!("Rejected MCP request from origin {}", origin);
+        Err(Rejection::new(StatusCode::FORBIDDEN, "Forbidden origin"))
+    }
+}
+
+/// Whether a host name is loopback or one of `allowed`
+fn is_allowed_host(name: &str, allowed: &[String]) -> bool {
+    matches!(name, "localhost" | "127.0.0.1" | "[::1]")
+        || allowed.iter().any(|host| host.eq_ignore_ascii_case(name))
+}
+
+/// Host name of the bind address, as it appears in a `Host` header
+///
+/// Unspecified addresses (`0.0.0.0`, `::`) are never named by clients.
+fn bind_host(bind: SocketAddr) -> Option<String> {
+    match bind.ip() {
+        ip if ip.is_unspecified() => None,
+        std::net::IpAddr::V4(ip) => Some(ip.to_string()),
+        std::net::IpAddr::V6(ip) => Some(format!("[{ip}]")),
+    }
+}
+
+/// Strip the port from a `host[:port]` authority
+fn host_name(authority: &str) -> &str {
+    if authority.starts_with('[') {
+        authority
+            .find(']')
+            .map_or(authority, |end| &authority[..=end])
+    } else {
+        authority.split(':').next().unwrap_or(authority)
+    }
+}
+
+fn check_protocol_version(headers: &HeaderMap) -> std::result::Result<(), Rejection> {
+    match headers
+        .get(PROTOCOL_VERSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+    {
+        Some(version) if !SUPPORTED_PROTOCOL_VERSIONS.contains(&version) => Err(Rejection::new(
+            StatusCode::BAD_REQUEST,
+            format!("Unsupported MCP protocol version: {version}"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Look up the session named by the request headers
+fn require_session(
+    state: &HttpState,
+    headers: &HeaderMap,
+) -> std::result::Result<String, Rejection> {
+    let Some(id) = headers.get(SESSION_HEADER).and_then(|v| v.to_str().ok()) else {
+        return Err(Rejection::new(
+            StatusCode::BAD_REQUEST,
+            "Missing Mcp-Session-Id header",
+        ));
+    };
+    if state.sessions.touch(id) {
+        Ok(id.to_string())
+    } else {
+        Err(Rejection::new(
+            StatusCode::NOT_FOUND,
+            "Unknown or expired session",
+        ))
+    }
+}
+
+fn accepts(headers: &HeaderMap, mime: &str) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|v| v.split(';').next().unwrap_or("").trim())
+        .any(|v| v == mime || v == "*/*")
+}
+
+// ---------------------------------------------------------------------------
+// Handlers
+// ---------------------------------------------------------------------------
+
+async fn handle_post(State(state): State<HttpState>, headers: HeaderMap, body: Bytes) -> Response {
+    if let Err(rejection) = check_auth(&state, &headers)
+        .and_then(|_| check_origin(&state, &headers))
+        .and_then(|_| check_protocol_version(&headers))
+    {
+        return rejection.into_response();
+    }
+
+    let message: Value = match serde_json::from_slice(&body) {
+        Ok(message) => message,
+        Err(e) => {
+            let error = JsonRpcResponse::error(
+                Value::Null,
+                JsonRpcError::new(PARSE_ERROR, format!("Parse error: {e}")),
+            );
+            return (StatusCode::BAD_REQUEST, axum::Json(error)).into_response();
+        }
+    };
+    if !message.is_object() {
+        let error = JsonRpcResponse::error(
+            Value::Null,
+            JsonRpcError::new(INVALID_REQUEST, "Expected a single JSON-RPC message"),
+        );
+        return (StatusCode::BAD_REQUEST, axum::Json(error)).into_response();
+    }
+
+    let is_initialize = message.get("method").and_then(Value::as_str) == Some("initialize");
+    let session_id = if is_initialize {
+        None
+    } else {
+        match require_session(&state, &headers) {
+            Ok(id) => Some(id),
+            Err(rejection) => return rejection.into_response(),
+        }
+    };
+
+    // Initialize runs before a session exists; it never touches client state
+    let client = session_id.as_deref().unwrap_or_default();
+
+    // Notifications and client responses need no reply
+    if message.get("method").is_none() || message.get("id").is_none() {
+        if let Some(response) = state.server.handle_value(client, message).await {
+            debug!("Dropping reply to client message: {:?}", response.error);
+        }
+        return StatusCode::ACCEPTED.into_response();
+    }
+
+    let Some(reply) = state.server.handle_value(client, message).await else {
+        return StatusCode::ACCEPTED.into_response();
+    };
+
+    // Only a successful initialize opens a session
+    let session_id = match session_id {
+        Some(id) => id,
+        None if reply.error.is_none() => {
+            let id = state.sessions.create();
+            info!("Opened MCP HTTP session {}", id);
+            id
+        }
+        None => return axum::Json(reply).into_response(),
+    };
+
+    let mut response = if accepts(&headers, "application/json") {
+        axum::Json(reply).into_response()
+    } else if accepts(&headers, "text/event-stream") {
+        let data = serde_json::to_string(&reply).unwrap_or_default();
+        let stream = futures::stream::once(async move {
+            Ok::<_, Infallible>(Event::default().event("message").data(data))
+        });
+        Sse::new(stream).into_response()
+    } else {
+        return (
+            StatusCode::NOT_ACCEPTABLE,
+            "Accept must include application/json or text/event-stream",
+        )
+            .into_response();
+    };
+
+    if let Ok(value) = HeaderValue::from_str(&session_id) {
+        response.headers_mut().insert(SESSION_HEADER, value);
+    }
+    response
+}
+
+async fn handle_get(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    if let Err(rejection) =
+        check_auth(&state, &headers).and_then(|_| check_origin(&state, &headers))
+    {
+        return rejection.into_response();
+    }
+    if !accepts(&headers, "text/event-stream") {
+        return (
+            StatusCode::METHOD_NOT_ALLOWED,
+            "GET requires Accept: text/event-stream",
+        )
+            .into_response();
+    }
+    let session_id = match require_session(&state, &headers) {
+        Ok(id) => id,
+        Err(rejection) => return rejection.into_response(),
+    };
+    let Some(receiver) = state.sessions.subscribe(&session_id) else {
+        return (StatusCode::NOT_FOUND, "Unknown or expired session").into_response();
+    };
+
+    debug!("Opened SSE stream for session {}", session_id);
+    Sse::new(session_events(receiver))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+async fn handle_delete(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    if let Err(rejection) =
+        check_auth(&state, &headers).and_then(|_| check_origin(&state, &headers))
+    {
+        return rejection.into_response();
+    }
+    let session_id = match require_session(&state, &headers) {
+        Ok(id) => id,
+        Err(rejection) => return rejection.into_response(),
+    };
+    state.sessions.remove(&session_id);
+    state.server.remove_client(&session_id);
+    info!("Closed MCP HTTP session {}", session_id);
+    StatusCode::NO_CONTENT.into_response()
+}
+
+/// Turn a session's broadcast channel into SSE events
+///
+/// The stream ends when the session is deleted (all senders dropped).
+fn session_events(
+    mut receiver: broadcast::Receiver<String>,
+) -> impl Stream<Item = std::result::Result<Event, Infallible>> {
+    async_stream::stream! {
+        loop {
+            match receiver.recv().await {
+                Ok(message) => yield Ok(Event::default().event("message").data(message)),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("SSE stream lagged, dropped {} messages", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::Resource;
+    use crate::tools::{ReportHandler, ReportRequest};
+    use async_trait::async_trait;
+    use axum::body::{Body, to_bytes};
+    use axum::http::Request;
+    use futures::StreamExt;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    struct StaticHandler;
+
+    #[async_trait]
+    impl ReportHandler for StaticHandler {
+        fn providers(&self) -> Vec<String> {
+            vec!["claude".into()]
+        }
+
+        async fn run_report(&self, request: ReportRequest) -> Result<String> {
+            Ok(json!({ "report": request.report.tool_name() }).to_string())
+        }
+
+        async fn read_resource(&self, resource: Resource) -> Result<String> {
+            Ok(json!({ "uri": resource.uri() }).to_string())
+        }
+    }
+
+    fn app(token: Option<&str>) -> (Router, SessionRegistry) {
+        let server = McpServer::new(Arc::new(StaticHandler));
+        let config = HttpConfig::new("127.0.0.1:0".parse().unwrap())
+            .with_bearer_token(token.map(str::to_string));
+        let sessions = SessionRegistry::default();
+        (
+            router_with_sessions(server, &config, sessions.clone()),
+            sessions,
+        )
+    }
+
+    fn post_request(body: Value, session: Option<&str>, token: Option<&str>) -> Request<Body> {
+        let mut builder = Request::post(MCP_ENDPOINT)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::ACCEPT, "application/json, text/event-stream");
+        if let Some(session) = session {
+            builder = builder.header(SESSION_HEADER, session);
+        }
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    fn initialize_message() -> Value {
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize",
+               "params": {"protocolVersion": "2025-06-18", "capabilities": {}}})
+    }
+
+    async fn body_json(response: Response) -> Value {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    async fn initialize(app: &Router, token: Option<&str>) -> String {
+        let response = app
+            .clone()
+            .oneshot(post_request(initialize_message(), None, token))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.headers()[SESSION_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_initialize_creates_session() {
+        let (app, sessions) = app(None);
+        let response = app
+            .oneshot(post_request(initialize_message(), None, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let session = response.headers()[SESSION_HEADER].to_str().unwrap();
+        assert!(sessions.touch(session));
+
+        let body = body_json(response).await;
+        assert_eq!(body["result"]["serverInfo"]["name"], "ccstat");
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_requires_session() {
+        let (app, _) = app(None);
+        let call = json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call",
+                          "params": {"name": "daily"}});
+
+        let response = app
+            .clone()
+            .oneshot(post_request(call.clone(), None, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = app
+            .clone()
+            .oneshot(post_request(call.clone(), Some("nope"), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let session = initialize(&app, None).await;
+        let response = app
+            .oneshot(post_request(call, Some(&session), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_json(response).await;
+        assert_eq!(body["result"]["structuredContent"]["report"], "daily");
+    }
+
+    #[tokio::test]
+    async fn test_notification_is_accepted() {
+        let (app, _) = app(None);
+        let session = initialize(&app, None).await;
+        let response = app
+            .oneshot(post_request(
+                json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+                Some(&session),
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+    }
+
+    #[tokio::test]
+    async fn test_bearer_token() {
+        let (app, _) = app(Some("secret"));
+
+        let response = app
+            .clone()
+            .oneshot(post_request(initialize_message(), None, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+
+        let response = app
+            .clone()
+            .oneshot(post_request(initialize_message(), None, Some("wrong")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        initialize(&app, Some("secret")).await;
+    }
+
+    #[tokio::test]
+    async fn test_foreign_origin_rejected() {
+        let (app, _) = app(None);
+        let mut request = post_request(initialize_message(), None, None);
+        request.headers_mut().insert(
+            header::ORIGIN,
+            HeaderValue::from_static("https://evil.example"),
+        );
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let mut request = post_request(initialize_message(), None, None);
+        request.headers_mut().insert(
+            header::ORIGIN,
+            HeaderValue::from_static("http://localhost:3000"),
+        );
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_rebound_host_rejected() {
+        let request = |host: &'static str, origin: &'static str| {
+            let mut request = post_request(initialize_message(), None, None);
+            let headers = request.headers_mut();
+            headers.insert(header::HOST, HeaderValue::from_static(host));
+            headers.insert(header::ORIGIN, HeaderValue::from_static(origin));
+            request
+        };
+
+        // A DNS rebinding attack names the attacker's host in both headers
+        let (app, _) = app(None);
+        let response = app
+            .clone()
+            .oneshot(request("evil.example:8080", "http://evil.example:8080"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app
+            .oneshot(request("127.0.0.1:8080", "http://127.0.0.1:8080"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Hosts must be allowed explicitly
+        let server = McpServer::new(Arc::new(StaticHandler));
+        let config = HttpConfig::new("0.0.0.0:8080".parse().unwrap())
+            .with_allowed_hosts(vec!["mcp.internal".to_string()]);
+        let app = router(server, &config);
+        let response = app
+            .clone()
+            .oneshot(request("mcp.internal:8080", "https://mcp.internal"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app
+            .oneshot(request("0.0.0.0:8080", "http://localhost:3000"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_delete_ends_session() {
+        let (app, sessions) = app(None);
+        let session = initialize(&app, None).await;
+
+        let request = Request::delete(MCP_ENDPOINT)
+            .header(SESSION_HEADER, &session)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(sessions.is_empty());
+
+        let response = app
+            .oneshot(post_request(
+                json!({"jsonrpc": "2.0", "id": 3, "method": "ping"}),
+                Some(&session),
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_idle_sessions_expire() {
+        let sessions = SessionRegistry::default().with_idle_ttl(Duration::from_millis(50));
+        let idle = sessions.create();
+        let streaming = sessions.create();
+        let _stream = sessions.subscribe(&streaming).unwrap();
+
+        std::thread::sleep(Duration::from_millis(80));
+        assert!(!sessions.touch(&idle));
+        // An open stream keeps its session alive
+        assert!(sessions.touch(&streaming));
+        assert_eq!(sessions.len(), 1);
+    }
+
+    #[test]
+    fn test_session_cap_evicts_least_recently_used() {
+        let pause = || std::thread::sleep(Duration::from_millis(5));
+        let sessions = SessionRegistry::default().with_max_sessions(2);
+        let first = sessions.create();
+        pause();
+        let second = sessions.create();
+        pause();
+        assert!(sessions.touch(&first));
+        pause();
+
+        let third = sessions.create();
+        assert_eq!(sessions.len(), 2);
+        assert!(!sessions.touch(&second));
+        assert!(sessions.touch(&first));
+        assert!(sessions.touch(&third));
+    }
+
+    #[tokio::test]
+    async fn test_get_requires_event_stream() {
+        let (app, _) = app(None);
+        let session = initialize(&app, None).await;
+
+        let request = Request::get(MCP_ENDPOINT)
+            .header(SESSION_HEADER, &session)
+            .header(header::ACCEPT, "application/json")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+
+        let request = Request::get(MCP_ENDPOINT)
+            .header(SESSION_HEADER, &session)
+            .header(header::ACCEPT, "text/event-stream")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/event-stream"
+        );
+    }
+
+    #[test]
+    fn test_host_name() {
+        assert_eq!(host_name("localhost:8080"), "localhost");
+        assert_eq!(host_name("127.0.0.1"), "127.0.0.1");
+        assert_eq!(host_name("[::1]:3000"), "[::1]");
+    }
+
+    #[tokio::test]
+    async fn test_subscribed_session_receives_updates() {
+        let server = McpServer::new(Arc::new(StaticHandler));
+        let config = HttpConfig::new("127.0.0.1:0".parse().unwrap());
+        let sessions = SessionRegistry::default();
+        let app = router_with_sessions(server.clone(), &config, sessions.clone());
+        tokio::spawn(forward_resource_updates(server.clone(), sessions));
+
+        let session = initialize(&app, None).await;
+        let response = app
+            .clone()
+            .oneshot(post_request(
+                json!({"jsonrpc": "2.0", "id": 2, "method": "resources/subscribe",
+                       "params": {"uri": "ccstat://blocks/active"}}),
+                Some(&session),
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::get(MCP_ENDPOINT)
+            .header(SESSION_HEADER, &session)
+            .header(header::ACCEPT, "text/event-stream")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let mut body = response.into_body().into_data_stream();
+
+        server.notify_resource_updated("ccstat://daily/today");
+        server.notify_resource_updated("ccstat://blocks/active");
+
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let text = String::from_utf8(chunk.to_vec()).unwrap();
+        assert!(text.contains("notifications/resources/updated"));
+        assert!(text.contains("ccstat://blocks/active"));
+        assert!(!text.contains("ccstat://daily/today"));
+    }
+}
@@ -1,6 +1,21 @@
 //! MCP server for ccstat
 //!
-//! This crate provides MCP (Model Context Protocol) server functionality
-//! with stdio and HTTP transports.
+//! This crate provides MCP (Model Context Protocol) server functionality,
+//! exposing ccstat's usage reports as tools that MCP clients can call and as
+//! resources they can read and subscribe to, over either stdio or streamable
+//! HTTP.
+//!
+//! The protocol handling lives here, while report execution is supplied by
+//! the caller through the [`ReportHandler`] trait so that this crate does not
+//! depend on the aggregation pipeline in the main binary.
+
+pub mod http;
+pub mod protocol;
+pub mod resources;
+pub mod server;
+pub mod tools;
 
-// Stub: will be implemented in a later phase
+pub use http::HttpConfig;
+pub use resources::Resource;
+pub use server::McpServer;
+pub use tools::{ReportHandler, ReportKind, ReportRequest};
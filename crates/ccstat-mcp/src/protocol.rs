@@ -0,0 +1,125 @@
+//! JSON-RPC 2.0 message types used by the MCP server
+//!
+//! MCP messages are plain JSON-RPC 2.0 requests, notifications, and
+//! responses. Only the subset needed by ccstat is modelled here; request
+//! parameters are kept as raw JSON and interpreted per method.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// JSON-RPC protocol version string
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// MCP protocol version advertised when the client does not request one we know
+pub const LATEST_PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// MCP protocol versions this server can speak
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+/// Invalid JSON was received
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON sent is not a valid request object
+pub const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist or is not available
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Invalid method parameters
+pub const INVALID_PARAMS: i64 = -32602;
+/// Internal JSON-RPC error
+pub const INTERNAL_ERROR: i64 = -32603;
+/// MCP: the requested resource does not exist
+pub const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// An incoming JSON-RPC request or notification
+///
+/// Requests carry an `id` and expect a response; notifications omit it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonRpcRequest {
+    /// Must be "2.0"
+    pub jsonrpc: String,
+    /// Request id (absent for notifications)
+    #[serde(default)]
+    pub id: Option<Value>,
+    /// Method name
+    pub method: String,
+    /// Method parameters
+    #[serde(default)]
+    pub params: Option<Value>,
+}
+
+impl JsonRpcRequest {
+    /// Whether this message is a notification (no response expected)
+    pub fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+}
+
+/// A JSON-RPC error object
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JsonRpcError {
+    /// Error code
+    pub code: i64,
+    /// Human-readable message
+    pub message: String,
+    /// Additional error data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl JsonRpcError {
+    /// Create an error with the given code and message
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+/// An outgoing JSON-RPC response
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JsonRpcResponse {
+    /// Always "2.0"
+    pub jsonrpc: String,
+    /// Id of the request being answered (null if it could not be determined)
+    pub id: Value,
+    /// Successful result
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// Error result
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+impl JsonRpcResponse {
+    /// Build a successful response
+    pub fn success(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    /// Build an error response
+    pub fn error(id: Value, error: JsonRpcError) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Pick the protocol version to answer an `initialize` request with
+///
+/// Echoes the client's requested version when supported, otherwise falls
+/// back to the latest version this server implements.
+pub fn negotiate_protocol_version(requested: Option<&str>) -> &'static str {
+    requested
+        .and_then(|v| SUPPORTED_PROTOCOL_VERSIONS.iter().find(|s| **s == v))
+        .copied()
+        .unwrap_or(LATEST_PROTOCOL_VERSION)
+}
@@ -0,0 +1,141 @@
+//! Resources exposed over MCP
+//!
+//! Resources are read-only views of Claude usage data addressed by
+//! `ccstat://` URIs. Clients can read them on demand or subscribe to be
+//! notified when the underlying JSONL files change.
+
+use serde_json::{Value, json};
+
+/// Currently active billing blocks
+pub const ACTIVE_BLOCKS_URI: &str = "ccstat://blocks/active";
+
+/// Today's daily usage, in the server's local timezone
+pub const DAILY_TODAY_URI: &str = "ccstat://daily/today";
+
+/// Prefix of per-session resource URIs
+pub const SESSION_URI_PREFIX: &str = "ccstat://session/";
+
+/// URI template for per-session resources
+pub const SESSION_URI_TEMPLATE: &str = "ccstat://session/{id}";
+
+/// MIME type of every resource body
+pub const RESOURCE_MIME_TYPE: &str = "application/json";
+
+/// A resource addressed by a `ccstat://` URI
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Resource {
+    /// `ccstat://blocks/active`
+    ActiveBlocks,
+    /// `ccstat://daily/today`
+    DailyToday,
+    /// `ccstat://session/{id}`
+    Session(String),
+}
+
+impl Resource {
+    /// Parse a resource URI, returning `None` for unknown URIs
+    pub fn parse(uri: &str) -> Option<Self> {
+        match uri {
+            ACTIVE_BLOCKS_URI => Some(Resource::ActiveBlocks),
+            DAILY_TODAY_URI => Some(Resource::DailyToday),
+            _ => uri
+                .strip_prefix(SESSION_URI_PREFIX)
+                .filter(|id| !id.is_empty() && !id.contains('/'))
+                .map(|id| Resource::Session(id.to_string())),
+        }
+    }
+
+    /// The URI identifying this resource
+    pub fn uri(&self) -> String {
+        match self {
+            Resource::ActiveBlocks => ACTIVE_BLOCKS_URI.to_string(),
+            Resource::DailyToday => DAILY_TODAY_URI.to_string(),
+            Resource::Session(id) => format!("{SESSION_URI_PREFIX}{id}"),
+        }
+    }
+}
+
+/// Build the `resources/list` result
+pub fn resource_definitions() -> Vec<Value> {
+    vec![
+        json!({
+            "uri": ACTIVE_BLOCKS_URI,
+            "name": "active-blocks",
+            "title": "Active billing blocks",
+            "description": "Claude billing blocks that are currently active, with tokens, cost, and projections",
+            "mimeType": RESOURCE_MIME_TYPE
+        }),
+        json!({
+            "uri": DAILY_TODAY_URI,
+            "name": "daily-today",
+            "title": "Today's usage",
+            "description": "Claude token usage and cost for today",
+            "mimeType": RESOURCE_MIME_TYPE
+        }),
+    ]
+}
+
+/// Build the `resources/templates/list` result
+pub fn resource_template_definitions() -> Vec<Value> {
+    vec![json!({
+        "uriTemplate": SESSION_URI_TEMPLATE,
+        "name": "session",
+        "title": "Session usage",
+        "description": "Token usage and cost for a single Claude session",
+        "mimeType": RESOURCE_MIME_TYPE
+    })]
+}
+
+/// Build a `notifications/resources/updated` message for `uri`
+pub fn resource_updated_notification(uri: &str) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/resources/updated",
+        "params": { "uri": uri }
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resource_uris() {
+        assert_eq!(
+            Resource::parse("ccstat://blocks/active"),
+            Some(Resource::ActiveBlocks)
+        );
+        assert_eq!(
+            Resource::parse("ccstat://daily/today"),
+            Some(Resource::DailyToday)
+        );
+        assert_eq!(
+            Resource::parse("ccstat://session/abc-123"),
+            Some(Resource::Session("abc-123".into()))
+        );
+        assert_eq!(Resource::parse("ccstat://session/"), None);
+        assert_eq!(Resource::parse("ccstat://session/a/b"), None);
+        assert_eq!(Resource::parse("ccstat://monthly/current"), None);
+    }
+
+    #[test]
+    fn test_resource_uri_round_trip() {
+        for resource in [
+            Resource::ActiveBlocks,
+            Resource::DailyToday,
+            Resource::Session("s1".into()),
+        ] {
+            assert_eq!(Resource::parse(&resource.uri()), Some(resource));
+        }
+    }
+
+    #[test]
+    fn test_resource_updated_notification() {
+        let message: Value =
+            serde_json::from_str(&resource_updated_notification(ACTIVE_BLOCKS_URI)).unwrap();
+        assert_eq!(message["method"], "notifications/resources/updated");
+        assert_eq!(message["params"]["uri"], ACTIVE_BLOCKS_URI);
+        assert!(message.get("id").is_none());
+    }
+}
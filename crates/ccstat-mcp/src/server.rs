@@ -0,0 +1,611 @@
+//! MCP request dispatch and the stdio transport
+//!
+//! [`McpServer`] maps JSON-RPC methods onto the report tools and resources
+//! and is independent of the transport. [`McpServer::serve_stdio`] runs it
+//! over newline-delimited JSON on stdin/stdout, which is how MCP clients
+//! launch local servers.
+//!
+//! Resource subscriptions are tracked per client. Transports identify their
+//! client with a string id (the stdio transport uses [`STDIO_CLIENT`], the
+//! HTTP transport uses the session id) and forward the server's resource
+//! updates to subscribed clients.
+
+use crate::protocol::{
+    INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST, JSONRPC_VERSION, JsonRpcError, JsonRpcRequest,
+    JsonRpcResponse, METHOD_NOT_FOUND, PARSE_ERROR, RESOURCE_NOT_FOUND, negotiate_protocol_version,
+};
+use crate::resources::{
+    RESOURCE_MIME_TYPE, Resource, resource_definitions, resource_template_definitions,
+    resource_updated_notification,
+};
+use crate::tools::{ReportHandler, ReportKind, ReportRequest, tool_definitions};
+use ccstat_core::error::Result;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+/// Name reported to clients in `serverInfo`
+pub const SERVER_NAME: &str = "ccstat";
+
+/// Client id used by the stdio transport
+pub const STDIO_CLIENT: &str = "stdio";
+
+/// Capacity of the resource update channel
+const UPDATE_CHANNEL_CAPACITY: usize = 64;
+
+/// Resource URIs each client is subscribed to
+type Subscriptions = HashMap<String, HashSet<String>>;
+
+/// Transport-independent MCP server
+#[derive(Clone)]
+pub struct McpServer {
+    handler: Arc<dyn ReportHandler>,
+    subscriptions: Arc<Mutex<Subscriptions>>,
+    updates: broadcast::Sender<String>,
+}
+
+impl McpServer {
+    /// Create a server that runs reports through the given handler
+    pub fn new(handler: Arc<dyn ReportHandler>) -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+        Self {
+            handler,
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            updates,
+        }
+    }
+
+    /// Handle one raw JSON-RPC message from `client`
+    ///
+    /// Returns the serialized response, or `None` for notifications.
+    pub async fn handle_message(&self, client: &str, message: &str) -> Option<String> {
+        let response = match serde_json::from_str::<Value>(message) {
+            Ok(value) => self.handle_value(client, value).await?,
+            Err(e) => JsonRpcResponse::error(
+                Value::Null,
+                JsonRpcError::new(PARSE_ERROR, format!("Parse error: {e}")),
+            ),
+        };
+        // Serializing our own response types cannot fail
+        Some(serde_json::to_string(&response).unwrap_or_default())
+    }
+
+    /// Handle one already-parsed JSON-RPC message from `client`
+    pub async fn handle_value(&self, client: &str, value: Value) -> Option<JsonRpcResponse> {
+        let id = value.get("id").cloned().unwrap_or(Value::Null);
+        let request: JsonRpcRequest = match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(e) => {
+                return Some(JsonRpcResponse::error(
+                    id,
+                    JsonRpcError::new(INVALID_REQUEST, format!("Invalid request: {e}")),
+                ));
+            }
+        };
+        if request.jsonrpc != JSONRPC_VERSION {
+            return Some(JsonRpcResponse::error(
+                id,
+                JsonRpcError::new(INVALID_REQUEST, "Invalid request: jsonrpc must be \"2.0\""),
+            ));
+        }
+
+        if request.is_notification() {
+            debug!("Received notification: {}", request.method);
+            return None;
+        }
+
+        let result = self.dispatch(client, &request).await;
+        Some(match result {
+            Ok(result) => JsonRpcResponse::success(id, result),
+            Err(error) => JsonRpcResponse::error(id, error),
+        })
+    }
+
+    async fn dispatch(
+        &self,
+        client: &str,
+        request: &JsonRpcRequest,
+    ) -> std::result::Result<Value, JsonRpcError> {
+        let params = request.params.as_ref();
+        match request.method.as_str() {
+            "initialize" => Ok(self.initialize(params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions(&self.handler.providers()) })),
+            "tools/call" => self.call_tool(params).await,
+            "resources/list" => Ok(json!({ "resources": resource_definitions() })),
+            "resources/templates/list" => {
+                Ok(json!({ "resourceTemplates": resource_template_definitions() }))
+            }
+            "resources/read" => self.read_resource(params).await,
+            "resources/subscribe" => {
+                let resource = resource_param(params)?;
+                self.lock_subscriptions()
+                    .entry(client.to_string())
+                    .or_default()
+                    .insert(resource.uri());
+                debug!("Client {} subscribed to {}", client, resource.uri());
+                Ok(json!({}))
+            }
+            "resources/unsubscribe" => {
+                let resource = resource_param(params)?;
+                if let Some(uris) = self.lock_subscriptions().get_mut(client) {
+                    uris.remove(&resource.uri());
+                }
+                Ok(json!({}))
+            }
+            method => Err(JsonRpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {method}"),
+            )),
+        }
+    }
+
+    fn initialize(&self, params: Option<&Value>) -> Value {
+        let requested = params
+            .and_then(|p| p.get("protocolVersion"))
+            .and_then(Value::as_str);
+        json!({
+            "protocolVersion": negotiate_protocol_version(requested),
+            "capabilities": {
+                "tools": { "listChanged": false },
+                "resources": { "subscribe": true, "listChanged": false }
+            },
+            "serverInfo": {
+                "name": SERVER_NAME,
+                "version": env!("CARGO_PKG_VERSION")
+            }
+        })
+    }
+
+    async fn call_tool(&self, params: Option<&Value>) -> std::result::Result<Value, JsonRpcError> {
+        let params = params
+            .ok_or_else(|| JsonRpcError::new(INVALID_PARAMS, "Missing params for tools/call"))?;
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| JsonRpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+        let report = ReportKind::from_tool_name(name)
+            .ok_or_else(|| JsonRpcError::new(INVALID_PARAMS, format!("Unknown tool: {name}")))?;
+
+        // Argument and execution failures are reported as tool errors so the
+        // model can see and correct them, rather than as protocol errors.
+        let outcome = match ReportRequest::from_arguments(
+            report,
+            params.get("arguments"),
+            &self.handler.providers(),
+        ) {
+            Ok(request) => self.handler.run_report(request).await,
+            Err(e) => Err(e),
+        };
+
+        match outcome {
+            Ok(output) => {
+                let structured: Value = serde_json::from_str(&output).map_err(|e| {
+                    JsonRpcError::new(INTERNAL_ERROR, format!("Report produced invalid JSON: {e}"))
+                })?;
+                Ok(json!({
+                    "content": [{ "type": "text", "text": output }],
+                    "structuredContent": structured,
+                    "isError": false
+                }))
+            }
+            Err(e) => {
+                warn!("Tool '{}' failed: {}", name, e);
+                Ok(json!({
+                    "content": [{ "type": "text", "text": e.to_string() }],
+                    "isError": true
+                }))
+            }
+        }
+    }
+
+    async fn read_resource(
+        &self,
+        params: Option<&Value>,
+    ) -> std::result::Result<Value, JsonRpcError> {
+        let resource = resource_param(params)?;
+        let uri = resource.uri();
+        let text = self.handler.read_resource(resource).await.map_err(|e| {
+            warn!("Reading resource '{}' failed: {}", uri, e);
+            JsonRpcError::new(INTERNAL_ERROR, e.to_string())
+        })?;
+        Ok(json!({
+            "contents": [{ "uri": uri, "mimeType": RESOURCE_MIME_TYPE, "text": text }]
+        }))
+    }
+
+    // -- Subscriptions -------------------------------------------------------
+
+    /// Announce that the resource at `uri` has changed
+    ///
+    /// Transports forward the update to every client subscribed to `uri`.
+    pub fn notify_resource_updated(&self, uri: &str) {
+        // No receivers just means no transport is listening yet
+        let _ = self.updates.send(uri.to_string());
+    }
+
+    /// Receive the URIs passed to [`McpServer::notify_resource_updated`]
+    pub fn resource_updates(&self) -> broadcast::Receiver<String> {
+        self.updates.subscribe()
+    }
+
+    /// Whether `client` is subscribed to `uri`
+    pub fn is_subscribed(&self, client: &str, uri: &str) -> bool {
+        self.lock_subscriptions()
+            .get(client)
+            .is_some_and(|uris| uris.contains(uri))
+    }
+
+    /// Clients subscribed to `uri`
+    pub fn subscribers(&self, uri: &str) -> Vec<String> {
+        self.lock_subscriptions()
+            .iter()
+            .filter(|(_, uris)| uris.contains(uri))
+            .map(|(client, _)| client.clone())
+            .collect()
+    }
+
+    /// Forget every subscription held by `client`
+    pub fn remove_client(&self, client: &str) {
+        self.lock_subscriptions().remove(client);
+    }
+
+    fn lock_subscriptions(&self) -> std::sync::MutexGuard<'_, Subscriptions> {
+        // The map stays consistent even if a holder panicked
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // -- stdio transport -----------------------------------------------------
+
+    /// Serve MCP over stdin/stdout until stdin is closed
+    ///
+    /// Each line on stdin is one JSON-RPC message and each response or
+    /// notification is written as one line on stdout. Logging must go to
+    /// stderr while this runs, since stdout is reserved for protocol messages.
+    pub async fn serve_stdio(&self) -> Result<()> {
+        let stdin = BufReader::new(tokio::io::stdin());
+        let mut stdout = tokio::io::stdout();
+        let mut lines = stdin.lines();
+        let mut updates = self.resource_updates();
+
+        loop {
+            let outgoing = tokio::select! {
+                line = lines.next_line() => {
+                    let Some(line) = line? else { break };
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    self.handle_message(STDIO_CLIENT, line).await
+                }
+                update = updates.recv() => match update {
+                    Ok(uri) if self.is_subscribed(STDIO_CLIENT, &uri) => {
+                        Some(resource_updated_notification(&uri))
+                    }
+                    Ok(_) => None,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Dropped {} resource updates", skipped);
+                        None
+                    }
+                    Err(broadcast::error::RecvError::Closed) => None,
+                },
+            };
+
+            if let Some(message) = outgoing {
+                stdout.write_all(message.as_bytes()).await?;
+                stdout.write_all(b"\n").await?;
+                stdout.flush().await?;
+            }
+        }
+
+        debug!("stdin closed, shutting down MCP server");
+        Ok(())
+    }
+}
+
+/// Extract and parse the `uri` parameter of a resources request
+fn resource_param(params: Option<&Value>) -> std::result::Result<Resource, JsonRpcError> {
+    let uri = params
+        .and_then(|p| p.get("uri"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| JsonRpcError::new(INVALID_PARAMS, "Missing resource uri"))?;
+    Resource::parse(uri).ok_or_else(|| {
+        let mut error = JsonRpcError::new(RESOURCE_NOT_FOUND, "Resource not found");
+        error.data = Some(json!({ "uri": uri }));
+        error
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::LATEST_PROTOCOL_VERSION;
+    use async_trait::async_trait;
+    use ccstat_core::error::CcstatError;
+    use std::sync::Mutex;
+
+    /// Handler that records requests and echoes them back as JSON
+    #[derive(Default)]
+    struct EchoHandler {
+        requests: Mutex<Vec<ReportRequest>>,
+    }
+
+    #[async_trait]
+    impl ReportHandler for EchoHandler {
+        fn providers(&self) -> Vec<String> {
+            vec!["claude".into(), "amp".into(), "all".into()]
+        }
+
+        async fn run_report(&self, request: ReportRequest) -> Result<String> {
+            if request.project.as_deref() == Some("missing") {
+                return Err(CcstatError::Config("no data".into()));
+            }
+            let output = json!({
+                "report": request.report.tool_name(),
+                "provider": request.provider,
+            })
+            .to_string();
+            self.requests.lock().unwrap().push(request);
+            Ok(output)
+        }
+
+        async fn read_resource(&self, resource: Resource) -> Result<String> {
+            match resource {
+                Resource::Session(id) if id == "missing" => Err(CcstatError::InvalidArgument(
+                    "Session 'missing' not found".into(),
+                )),
+                resource => Ok(json!({ "uri": resource.uri() }).to_string()),
+            }
+        }
+    }
+
+    fn server() -> (McpServer, Arc<EchoHandler>) {
+        let handler = Arc::new(EchoHandler::default());
+        (McpServer::new(handler.clone()), handler)
+    }
+
+    async fn call(server: &McpServer, message: Value) -> Value {
+        let response = server
+            .handle_message(STDIO_CLIENT, &message.to_string())
+            .await
+            .unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_initialize() {
+        let (server, _) = server();
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize",
+                   "params": {"protocolVersion": "2025-03-26", "capabilities": {}}}),
+        )
+        .await;
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(response["result"]["serverInfo"]["name"], "ccstat");
+        assert!(response["result"]["capabilities"]["tools"].is_object());
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "initialize",
+                   "params": {"protocolVersion": "1999-01-01"}}),
+        )
+        .await;
+        assert_eq!(
+            response["result"]["protocolVersion"],
+            LATEST_PROTOCOL_VERSION
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notifications_get_no_response() {
+        let (server, _) = server();
+        let message = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(
+            server
+                .handle_message(STDIO_CLIENT, &message.to_string())
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tools_list() {
+        let (server, _) = server();
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": "a", "method": "tools/list"}),
+        )
+        .await;
+        let names: Vec<&str> = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["daily", "monthly", "weekly", "session", "blocks"]);
+    }
+
+    #[tokio::test]
+    async fn test_tools_call() {
+        let (server, handler) = server();
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call",
+                   "params": {"name": "monthly", "arguments": {"provider": "amp", "since": "2025-01"}}}),
+        )
+        .await;
+        assert_eq!(response["result"]["isError"], false);
+        assert_eq!(response["result"]["structuredContent"]["report"], "monthly");
+        assert_eq!(response["result"]["structuredContent"]["provider"], "amp");
+
+        let requests = handler.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].since.as_deref(), Some("2025-01"));
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_errors() {
+        let (server, _) = server();
+
+        // Unknown tool is a protocol error
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 4, "method": "tools/call",
+                   "params": {"name": "statusline"}}),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        // Bad arguments and handler failures are tool errors
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 5, "method": "tools/call",
+                   "params": {"name": "daily", "arguments": {"mode": "cheap"}}}),
+        )
+        .await;
+        assert_eq!(response["result"]["isError"], true);
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 6, "method": "tools/call",
+                   "params": {"name": "daily", "arguments": {"project": "missing"}}}),
+        )
+        .await;
+        assert_eq!(response["result"]["isError"], true);
+        assert!(
+            response["result"]["content"][0]["text"]
+                .as_str()
+                .unwrap()
+                .contains("no data")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_protocol_errors() {
+        let (server, _) = server();
+
+        let response: Value = serde_json::from_str(
+            &server
+                .handle_message(STDIO_CLIENT, "{not json")
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+        assert!(response["id"].is_null());
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 7, "method": "prompts/list"}),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "1.0", "id": 8, "method": "ping"}),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 9, "method": "ping"}),
+        )
+        .await;
+        assert_eq!(response["result"], json!({}));
+    }
+
+    #[tokio::test]
+    async fn test_resources_list_and_read() {
+        let (server, _) = server();
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "resources/list"}),
+        )
+        .await;
+        let uris: Vec<&str> = response["result"]["resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["uri"].as_str().unwrap())
+            .collect();
+        assert_eq!(uris, ["ccstat://blocks/active", "ccstat://daily/today"]);
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "resources/templates/list"}),
+        )
+        .await;
+        assert_eq!(
+            response["result"]["resourceTemplates"][0]["uriTemplate"],
+            "ccstat://session/{id}"
+        );
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "resources/read",
+                   "params": {"uri": "ccstat://session/abc"}}),
+        )
+        .await;
+        let contents = &response["result"]["contents"][0];
+        assert_eq!(contents["uri"], "ccstat://session/abc");
+        assert_eq!(contents["mimeType"], "application/json");
+        assert!(contents["text"].as_str().unwrap().contains("session/abc"));
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 4, "method": "resources/read",
+                   "params": {"uri": "ccstat://nope"}}),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], RESOURCE_NOT_FOUND);
+
+        let response = call(
+            &server,
+            json!({"jsonrpc": "2.0", "id": 5, "method": "resources/read",
+                   "params": {"uri": "ccstat://session/missing"}}),
+        )
+        .await;
+        assert_eq!(response["error"]["code"], INTERNAL_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_resource_subscriptions() {
+        let (server, _) = server();
+        let subscribe = |id: i64, method: &str, uri: &str| json!({"jsonrpc": "2.0", "id": id, "method": method, "params": {"uri": uri}});
+
+        call(
+            &server,
+            subscribe(1, "resources/subscribe", "ccstat://blocks/active"),
+        )
+        .await;
+        assert!(server.is_subscribed(STDIO_CLIENT, "ccstat://blocks/active"));
+        assert!(!server.is_subscribed(STDIO_CLIENT, "ccstat://daily/today"));
+        assert_eq!(
+            server.subscribers("ccstat://blocks/active"),
+            vec![STDIO_CLIENT.to_string()]
+        );
+
+        let mut updates = server.resource_updates();
+        server.notify_resource_updated("ccstat://blocks/active");
+        assert_eq!(updates.recv().await.unwrap(), "ccstat://blocks/active");
+
+        call(
+            &server,
+            subscribe(2, "resources/unsubscribe", "ccstat://blocks/active"),
+        )
+        .await;
+        assert!(server.subscribers("ccstat://blocks/active").is_empty());
+
+        let response = call(&server, subscribe(3, "resources/subscribe", "bogus")).await;
+        assert_eq!(response["error"]["code"], RESOURCE_NOT_FOUND);
+    }
+}
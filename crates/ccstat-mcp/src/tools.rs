@@ -0,0 +1,382 @@
+//! Report tools exposed over MCP
+//!
+//! Each ccstat report (daily, monthly, weekly, session, blocks) is exposed as
+//! an MCP tool. This module owns the tool definitions and their input
+//! schemas, and turns raw `tools/call` arguments into a typed
+//! [`ReportRequest`]. Actually running a report is delegated to a
+//! [`ReportHandler`] supplied by the binary, which owns the aggregation
+//! pipeline.
+
+use crate::resources::Resource;
+use async_trait::async_trait;
+use ccstat_core::error::{CcstatError, Result};
+use ccstat_core::types::CostMode;
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::str::FromStr;
+
+/// Report types exposed as MCP tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportKind {
+    Daily,
+    Monthly,
+    Weekly,
+    Session,
+    Blocks,
+}
+
+impl ReportKind {
+    /// All report kinds, in the order they are listed by `tools/list`
+    pub const ALL: [ReportKind; 5] = [
+        ReportKind::Daily,
+        ReportKind::Monthly,
+        ReportKind::Weekly,
+        ReportKind::Session,
+        ReportKind::Blocks,
+    ];
+
+    /// MCP tool name for this report
+    pub fn tool_name(self) -> &'static str {
+        match self {
+            ReportKind::Daily => "daily",
+            ReportKind::Monthly => "monthly",
+            ReportKind::Weekly => "weekly",
+            ReportKind::Session => "session",
+            ReportKind::Blocks => "blocks",
+        }
+    }
+
+    /// Look up a report by its MCP tool name
+    pub fn from_tool_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.tool_name() == name)
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            ReportKind::Daily => "Daily token usage and cost, grouped by date",
+            ReportKind::Monthly => "Monthly token usage and cost, grouped by calendar month",
+            ReportKind::Weekly => "Weekly token usage and cost, grouped by week",
+            ReportKind::Session => "Token usage and cost per coding session",
+            ReportKind::Blocks => "5-hour billing blocks with token usage and cost",
+        }
+    }
+}
+
+/// A parsed report tool invocation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportRequest {
+    /// Which report to produce
+    pub report: ReportKind,
+    /// Provider name (one of [`ReportHandler::providers`])
+    pub provider: String,
+    /// Start date filter (YYYY-MM-DD or YYYY-MM)
+    pub since: Option<String>,
+    /// End date filter (YYYY-MM-DD or YYYY-MM)
+    pub until: Option<String>,
+    /// Project name filter
+    pub project: Option<String>,
+    /// Timezone for date grouping
+    pub timezone: Option<String>,
+    /// Use UTC for date grouping (overrides `timezone`)
+    pub utc: bool,
+    /// Cost calculation mode
+    pub mode: CostMode,
+    /// Day the week starts on (weekly report only)
+    pub start_of_week: Option<String>,
+    /// Show only active blocks (blocks report only)
+    pub active: bool,
+    /// Show only recent blocks (blocks report only)
+    pub recent: bool,
+    /// Billing block duration in hours (blocks report only)
+    pub session_duration: Option<f64>,
+}
+
+impl ReportRequest {
+    /// Build a request from `tools/call` arguments
+    ///
+    /// Unknown arguments and arguments that do not apply to the selected
+    /// report are rejected so that typos do not silently change results.
+    /// The provider must be one of `providers`.
+    pub fn from_arguments(
+        report: ReportKind,
+        arguments: Option<&Value>,
+        providers: &[String],
+    ) -> Result<Self> {
+        let args: ToolArguments = match arguments {
+            None | Some(Value::Null) => ToolArguments::default(),
+            Some(value) => serde_json::from_value(value.clone()).map_err(|e| {
+                CcstatError::InvalidArgument(format!(
+                    "Invalid arguments for '{}': {e}",
+                    report.tool_name()
+                ))
+            })?,
+        };
+
+        let provider = args.provider.unwrap_or_else(|| "claude".to_string());
+        if !providers.contains(&provider) {
+            return Err(CcstatError::InvalidArgument(format!(
+                "Unknown provider '{provider}'. Expected one of: {}",
+                providers.join(", ")
+            )));
+        }
+
+        let mode = match args.mode.as_deref() {
+            Some(mode) => CostMode::from_str(mode).map_err(CcstatError::InvalidArgument)?,
+            None => CostMode::Auto,
+        };
+
+        if report != ReportKind::Weekly && args.start_of_week.is_some() {
+            return Err(not_applicable("start_of_week", report));
+        }
+        if report != ReportKind::Blocks {
+            if args.active.is_some() {
+                return Err(not_applicable("active", report));
+            }
+            if args.recent.is_some() {
+                return Err(not_applicable("recent", report));
+            }
+            if args.session_duration.is_some() {
+                return Err(not_applicable("session_duration", report));
+            }
+        }
+
+        Ok(Self {
+            report,
+            provider,
+            since: args.since,
+            until: args.until,
+            project: args.project,
+            timezone: args.timezone,
+            utc: args.utc.unwrap_or(false),
+            mode,
+            start_of_week: args.start_of_week,
+            active: args.active.unwrap_or(false),
+            recent: args.recent.unwrap_or(false),
+            session_duration: args.session_duration,
+        })
+    }
+}
+
+fn not_applicable(argument: &str, report: ReportKind) -> CcstatError {
+    CcstatError::InvalidArgument(format!(
+        "Argument '{argument}' does not apply to the '{}' tool",
+        report.tool_name()
+    ))
+}
+
+/// Raw tool arguments as sent by the client
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ToolArguments {
+    provider: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+    project: Option<String>,
+    timezone: Option<String>,
+    utc: Option<bool>,
+    mode: Option<String>,
+    start_of_week: Option<String>,
+    active: Option<bool>,
+    recent: Option<bool>,
+    session_duration: Option<f64>,
+}
+
+/// Executes report requests on behalf of the MCP server
+///
+/// Implementations return the report rendered as a JSON document, in the
+/// same shape as `ccstat <report> --json`.
+#[async_trait]
+pub trait ReportHandler: Send + Sync {
+    /// Provider names accepted by the `provider` argument
+    ///
+    /// `all` combines every provider into one report with per-provider
+    /// subtotals.
+    fn providers(&self) -> Vec<String>;
+
+    /// Run a report and return its JSON output
+    async fn run_report(&self, request: ReportRequest) -> Result<String>;
+
+    /// Read a resource and return its JSON body
+    async fn read_resource(&self, resource: Resource) -> Result<String>;
+}
+
+/// Build the `tools/list` entry for a report
+pub fn tool_definition(report: ReportKind, providers: &[String]) -> Value {
+    let mut properties = Map::new();
+    properties.insert(
+        "provider".into(),
+        json!({
+            "type": "string",
+            "enum": providers,
+            "description": "Usage data provider (default: claude)"
+        }),
+    );
+    properties.insert(
+        "since".into(),
+        json!({"type": "string", "description": "Start date filter (YYYY-MM-DD or YYYY-MM)"}),
+    );
+    properties.insert(
+        "until".into(),
+        json!({"type": "string", "description": "End date filter (YYYY-MM-DD or YYYY-MM)"}),
+    );
+    properties.insert(
+        "project".into(),
+        json!({"type": "string", "description": "Filter by project name"}),
+    );
+    properties.insert(
+        "timezone".into(),
+        json!({
+            "type": "string",
+            "description": "Timezone for date grouping (e.g. \"America/New_York\"); defaults to the system timezone"
+        }),
+    );
+    properties.insert(
+        "utc".into(),
+        json!({"type": "boolean", "description": "Use UTC for date grouping (overrides timezone)"}),
+    );
+    properties.insert(
+        "mode".into(),
+        json!({
+            "type": "string",
+            "enum": ["auto", "calculate", "display"],
+            "description": "Cost calculation mode (default: auto)"
+        }),
+    );
+
+    match report {
+        ReportKind::Weekly => {
+            properties.insert(
+                "start_of_week".into(),
+                json!({"type": "string", "description": "Day to start the week (default: sunday)"}),
+            );
+        }
+        ReportKind::Blocks => {
+            properties.insert(
+                "active".into(),
+                json!({"type": "boolean", "description": "Show only active blocks"}),
+            );
+            properties.insert(
+                "recent".into(),
+                json!({"type": "boolean", "description": "Show only recent blocks (last 24h)"}),
+            );
+            properties.insert(
+                "session_duration".into(),
+                json!({"type": "number", "description": "Billing block duration in hours (default: 5)"}),
+            );
+        }
+        _ => {}
+    }
+
+    json!({
+        "name": report.tool_name(),
+        "description": report.description(),
+        "inputSchema": {
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": false
+        }
+    })
+}
+
+/// Build the full `tools/list` result
+pub fn tool_definitions(providers: &[String]) -> Vec<Value> {
+    ReportKind::ALL
+        .into_iter()
+        .map(|report| tool_definition(report, providers))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn providers() -> Vec<String> {
+        vec!["claude".into(), "codex".into(), "all".into()]
+    }
+
+    #[test]
+    fn test_tool_names_round_trip() {
+        for kind in ReportKind::ALL {
+            assert_eq!(ReportKind::from_tool_name(kind.tool_name()), Some(kind));
+        }
+        assert_eq!(ReportKind::from_tool_name("statusline"), None);
+    }
+
+    #[test]
+    fn test_tool_definitions_schema() {
+        let tools = tool_definitions(&providers());
+        assert_eq!(tools.len(), 5);
+
+        let weekly = &tools[2];
+        assert_eq!(weekly["name"], "weekly");
+        assert!(weekly["inputSchema"]["properties"]["start_of_week"].is_object());
+        assert!(weekly["inputSchema"]["properties"]["active"].is_null());
+
+        let blocks = &tools[4];
+        assert!(blocks["inputSchema"]["properties"]["session_duration"].is_object());
+        assert_eq!(
+            blocks["inputSchema"]["properties"]["provider"]["enum"][1],
+            "codex"
+        );
+    }
+
+    #[test]
+    fn test_request_defaults() {
+        let request = ReportRequest::from_arguments(ReportKind::Daily, None, &providers()).unwrap();
+        assert_eq!(request.provider, "claude");
+        assert_eq!(request.mode, CostMode::Auto);
+        assert!(request.since.is_none());
+        assert!(!request.utc);
+    }
+
+    #[test]
+    fn test_request_from_arguments() {
+        let args = json!({
+            "provider": "codex",
+            "since": "2025-01-01",
+            "until": "2025-01-31",
+            "project": "ccstat",
+            "timezone": "Asia/Tokyo",
+            "mode": "calculate"
+        });
+        let request =
+            ReportRequest::from_arguments(ReportKind::Monthly, Some(&args), &providers()).unwrap();
+        assert_eq!(request.provider, "codex");
+        assert_eq!(request.since.as_deref(), Some("2025-01-01"));
+        assert_eq!(request.until.as_deref(), Some("2025-01-31"));
+        assert_eq!(request.project.as_deref(), Some("ccstat"));
+        assert_eq!(request.timezone.as_deref(), Some("Asia/Tokyo"));
+        assert_eq!(request.mode, CostMode::Calculate);
+    }
+
+    #[test]
+    fn test_request_rejects_invalid_arguments() {
+        let unknown_provider = json!({"provider": "cursor"});
+        assert!(
+            ReportRequest::from_arguments(ReportKind::Daily, Some(&unknown_provider), &providers())
+                .is_err()
+        );
+
+        let bad_mode = json!({"mode": "cheap"});
+        assert!(
+            ReportRequest::from_arguments(ReportKind::Daily, Some(&bad_mode), &providers())
+                .is_err()
+        );
+
+        let unknown_field = json!({"sinse": "2025-01-01"});
+        assert!(
+            ReportRequest::from_arguments(ReportKind::Daily, Some(&unknown_field), &providers())
+                .is_err()
+        );
+
+        let wrong_report = json!({"active": true});
+        assert!(
+            ReportRequest::from_arguments(ReportKind::Daily, Some(&wrong_report), &providers())
+                .is_err()
+        );
+        assert!(
+            ReportRequest::from_arguments(ReportKind::Blocks, Some(&wrong_report), &providers())
+                .is_ok()
+        );
+    }
+}
@@ -11,6 +11,7 @@
 //!     pricing_fetcher::PricingFetcher,
 //! };
 //! use ccstat_core::types::{CostMode, ModelName, TokenCounts};
+//! use chrono::Utc;
 //! use std::sync::Arc;
 //!
 //! # async fn example() -> ccstat_core::Result<()> {
@@ -23,21 +24,28 @@
 //! // Calculate cost directly
 //! let cost = calculator.calculate_cost(&tokens, &model).await?;
 //!
-//! // Calculate with mode consideration
+//! // Calculate with mode consideration, at the rates in force at a time
 //! let cost_with_mode = calculator
-//!     .calculate_with_mode(&tokens, &model, Some(0.05), CostMode::Auto)
+//!     .calculate_with_mode(&tokens, &model, Utc::now(), Some(0.05), CostMode::Auto)
 //!     .await?;
 //! # Ok(())
 //! # }
 //! ```
 
 use crate::pricing_fetcher::PricingFetcher;
+use ccstat_core::cost::Cost;
 use ccstat_core::error::{CcstatError, Result};
-use ccstat_core::types::{CostMode, ModelName, ModelPricing, TokenCounts};
+use ccstat_core::types::{CostMode, ModelName, ModelPricing, SearchContextCost, TokenCounts};
+use chrono::{DateTime, Utc};
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 use tracing::{debug, warn};
 
+/// Context size, in tokens, above which long-context rates apply
+///
+/// A request's context is its input, cache creation, and cache read tokens.
+pub const LONG_CONTEXT_THRESHOLD: u64 = 200_000;
+
 /// Calculates costs based on token usage and pricing
 ///
 /// The CostCalculator integrates with the PricingFetcher to provide accurate
@@ -80,14 +88,31 @@ impl CostCalculator {
         &self,
         tokens: &TokenCounts,
         model_name: &ModelName,
-    ) -> Result<f64> {
+    ) -> Result<Cost> {
+        self.calculate_cost_at(tokens, model_name, Utc::now()).await
+    }
+
+    /// Calculate cost for token usage at the rates in force at `timestamp`
+    ///
+    /// Usage from before a price change is priced at the rates of the time,
+    /// when the model's pricing lists them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the model is unknown or pricing data is unavailable
+    pub async fn calculate_cost_at(
+        &self,
+        tokens: &TokenCounts,
+        model_name: &ModelName,
+        timestamp: DateTime<Utc>,
+    ) -> Result<Cost> {
         let pricing = self
             .pricing_fetcher
             .get_model_pricing(model_name.as_str())
             .await?
             .ok_or_else(|| CcstatError::UnknownModel(model_name.clone()))?;
 
-        Ok(Self::calculate_from_pricing(tokens, &pricing))
+        Ok(Self::calculate_from_pricing(tokens, pricing.at(timestamp)))
     }
 
     /// Calculate cost from pricing data without fetching
@@ -95,6 +120,10 @@ impl CostCalculator {
     /// This is a pure function that calculates cost given token counts and pricing.
     /// Useful when you already have pricing data and don't need to fetch it.
     ///
+    /// `tokens` should describe a single request: when its context exceeds
+    /// [`LONG_CONTEXT_THRESHOLD`], the model's long-context rates apply to
+    /// the whole request.
+    ///
     /// # Arguments
     ///
     /// * `tokens` - Token counts to calculate cost for
@@ -102,33 +131,84 @@ impl CostCalculator {
     ///
     /// # Returns
     ///
-    /// Total cost in dollars
-    pub fn calculate_from_pricing(tokens: &TokenCounts, pricing: &ModelPricing) -> f64 {
+    /// Total cost, rounded to the micro-dollar
+    pub fn calculate_from_pricing(tokens: &TokenCounts, pricing: &ModelPricing) -> Cost {
+        let long_context = tokens.context_tokens() > LONG_CONTEXT_THRESHOLD;
+        let tiered = |flat: Option<f64>, above_200k: Option<f64>| {
+            if long_context {
+                above_200k.or(flat)
+            } else {
+                flat
+            }
+        };
+        let input_rate = tiered(
+            pricing.input_cost_per_token,
+            pricing.input_cost_per_token_above_200k_tokens,
+        );
+        let output_rate = tiered(
+            pricing.output_cost_per_token,
+            pricing.output_cost_per_token_above_200k_tokens,
+        );
+        let cache_creation_rate = tiered(
+            pricing.cache_creation_input_token_cost,
+            pricing.cache_creation_input_token_cost_above_200k_tokens,
+        );
+        let cache_creation_1h_rate = tiered(
+            pricing.cache_creation_input_token_cost_above_1hr,
+            pricing.cache_creation_input_token_cost_above_1hr_above_200k_tokens,
+        );
+        let cache_read_rate = tiered(
+            pricing.cache_read_input_token_cost,
+            pricing.cache_read_input_token_cost_above_200k_tokens,
+        );
+
         let mut cost = 0.0;
 
-        if let Some(rate) = pricing.input_cost_per_token {
+        if let Some(rate) = input_rate {
             cost += tokens.input_tokens as f64 * rate;
         }
 
-        if let Some(rate) = pricing.output_cost_per_token {
+        if let Some(rate) = output_rate {
             cost += tokens.output_tokens as f64 * rate;
         }
 
-        if let Some(rate) = pricing.cache_creation_input_token_cost {
-            cost += tokens.cache_creation_tokens as f64 * rate;
+        // Reasoning is billed as output unless the model prices it separately
+        if let Some(rate) = pricing.output_cost_per_reasoning_token.or(output_rate) {
+            cost += tokens.reasoning_tokens as f64 * rate;
+        }
+
+        // 1-hour cache writes have their own rate; everything else, including
+        // cache writes without a TTL breakdown, is billed at the flat rate
+        let one_hour = tokens
+            .cache_creation_1h_tokens
+            .min(tokens.cache_creation_tokens);
+        if let Some(rate) = cache_creation_rate {
+            cost += (tokens.cache_creation_tokens - one_hour) as f64 * rate;
+        }
+        if let Some(rate) = cache_creation_1h_rate.or(cache_creation_rate) {
+            cost += one_hour as f64 * rate;
         }
 
-        if let Some(rate) = pricing.cache_read_input_token_cost {
+        if let Some(rate) = cache_read_rate {
             cost += tokens.cache_read_tokens as f64 * rate;
         }
 
+        // Web search is billed per request, not per token
+        if let Some(rate) = pricing
+            .search_context_cost_per_query
+            .as_ref()
+            .and_then(SearchContextCost::per_request)
+        {
+            cost += tokens.web_search_requests as f64 * rate;
+        }
+
         debug!(
             "Calculated cost: ${:.6} for {} total tokens",
             cost,
             tokens.total()
         );
 
-        cost
+        Cost::from_usd(cost)
     }
 
     /// Calculate cost with mode consideration
@@ -142,39 +222,46 @@ impl CostCalculator {
     ///
     /// * `tokens` - Token counts to calculate cost for
     /// * `model_name` - Name of the model to get pricing for
-    /// * `pre_calculated` - Optional pre-calculated cost from usage data
+    /// * `timestamp` - When the usage happened, selecting the rates in force
+    /// * `pre_calculated` - Optional pre-calculated cost in USD from usage data
     /// * `mode` - Cost calculation mode to use
     ///
     /// # Errors
     ///
     /// Returns an error if:
-    /// - Model is unknown (in Calculate/Auto modes)
+    /// - Model is unknown (in Calculate mode, or in Auto mode with strict
+    ///   pricing)
+    /// - Model matched pricing only by prefix, with strict pricing
     /// - No pre-calculated cost is available (in Display mode)
     pub async fn calculate_with_mode(
         &self,
         tokens: &TokenCounts,
         model_name: &ModelName,
+        timestamp: DateTime<Utc>,
         pre_calculated: Option<f64>,
         mode: CostMode,
-    ) -> Result<f64> {
+    ) -> Result<Cost> {
+        let pre_calculated = pre_calculated.map(Cost::from_usd);
         match mode {
             CostMode::Auto => {
                 if let Some(cost) = pre_calculated {
                     Ok(cost)
                 } else {
-                    match self.calculate_cost(tokens, model_name).await {
+                    match self.calculate_cost_at(tokens, model_name, timestamp).await {
                         Ok(cost) => Ok(cost),
-                        Err(CcstatError::UnknownModel(ref name)) => {
+                        Err(CcstatError::UnknownModel(ref name))
+                            if !self.pricing_fetcher.is_strict() =>
+                        {
                             if self.warned_models.lock().unwrap().insert(name.to_string()) {
                                 warn!(model = %name, "Unknown model encountered, cost will be reported as $0.00");
                             }
-                            Ok(0.0)
+                            Ok(Cost::ZERO)
                         }
                         Err(e) => Err(e),
                     }
                 }
             }
-            CostMode::Calculate => self.calculate_cost(tokens, model_name).await,
+            CostMode::Calculate => self.calculate_cost_at(tokens, model_name, timestamp).await,
             CostMode::Display => pre_calculated.ok_or_else(|| {
                 CcstatError::InvalidArgument(
                     "No pre-calculated cost available in display mode".to_string(),
@@ -196,13 +283,14 @@ mod tests {
             output_cost_per_token: Some(0.00002),
             cache_creation_input_token_cost: Some(0.000015),
             cache_read_input_token_cost: Some(0.000001),
+            ..Default::default()
         };
 
         let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
 
         // Expected: (1000 * 0.00001) + (500 * 0.00002) + (100 * 0.000015) + (50 * 0.000001)
         // = 0.01 + 0.01 + 0.0015 + 0.00005 = 0.02155
-        assert!((cost - 0.02155).abs() < 0.000001);
+        assert_eq!(cost, Cost::from_usd(0.02155));
     }
 
     #[test]
@@ -213,12 +301,155 @@ mod tests {
             output_cost_per_token: Some(0.00002),
             cache_creation_input_token_cost: None,
             cache_read_input_token_cost: None,
+            ..Default::default()
         };
 
         let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
 
         // Expected: (1000 * 0.00001) + (500 * 0.00002) = 0.01 + 0.01 = 0.02
-        assert!((cost - 0.02).abs() < 0.000001);
+        assert_eq!(cost, Cost::from_usd(0.02));
+    }
+
+    #[test]
+    fn test_reasoning_tokens_cost() {
+        let tokens = TokenCounts::new(0, 500, 0, 0).with_reasoning_tokens(1000);
+        let mut pricing = ModelPricing {
+            input_cost_per_token: Some(0.00001),
+            output_cost_per_token: Some(0.00002),
+            cache_creation_input_token_cost: None,
+            cache_read_input_token_cost: None,
+            ..Default::default()
+        };
+
+        // Reasoning falls back to the output rate: (500 + 1000) * 0.00002 = 0.03
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(cost, Cost::from_usd(0.03));
+
+        // A dedicated rate wins: 500 * 0.00002 + 1000 * 0.00001 = 0.02
+        pricing.output_cost_per_reasoning_token = Some(0.00001);
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(cost, Cost::from_usd(0.02));
+    }
+
+    #[test]
+    fn test_cache_creation_ttl_cost() {
+        let mut pricing = ModelPricing {
+            cache_creation_input_token_cost: Some(0.00000375),
+            cache_creation_input_token_cost_above_1hr: Some(0.000006),
+            ..Default::default()
+        };
+
+        // 400 tokens at the 5-minute rate, 600 at the 1-hour rate
+        let tokens = TokenCounts::new(0, 0, 1000, 0).with_cache_creation_ttl(400, 600);
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(cost, Cost::from_usd(400.0 * 0.00000375 + 600.0 * 0.000006));
+
+        // Without a breakdown everything is billed at the flat rate
+        let flat = TokenCounts::new(0, 0, 1000, 0);
+        let cost = CostCalculator::calculate_from_pricing(&flat, &pricing);
+        assert_eq!(cost, Cost::from_usd(1000.0 * 0.00000375));
+
+        // Without a 1-hour rate the flat rate applies to both buckets
+        pricing.cache_creation_input_token_cost_above_1hr = None;
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(cost, Cost::from_usd(1000.0 * 0.00000375));
+    }
+
+    fn long_context_pricing() -> ModelPricing {
+        ModelPricing {
+            input_cost_per_token: Some(0.000003),
+            output_cost_per_token: Some(0.000015),
+            cache_creation_input_token_cost: Some(0.00000375),
+            cache_read_input_token_cost: Some(0.0000003),
+            input_cost_per_token_above_200k_tokens: Some(0.000006),
+            output_cost_per_token_above_200k_tokens: Some(0.0000225),
+            cache_creation_input_token_cost_above_200k_tokens: Some(0.0000075),
+            cache_read_input_token_cost_above_200k_tokens: Some(0.0000006),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_long_context_threshold() {
+        let pricing = long_context_pricing();
+
+        // Exactly at the threshold: flat rates
+        let tokens = TokenCounts::new(LONG_CONTEXT_THRESHOLD, 1000, 0, 0);
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(
+            cost,
+            Cost::from_usd(200_000.0 * 0.000003 + 1000.0 * 0.000015)
+        );
+
+        // One token above: every bucket uses the long-context rate
+        let tokens = TokenCounts::new(LONG_CONTEXT_THRESHOLD + 1, 1000, 0, 0);
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(
+            cost,
+            Cost::from_usd(200_001.0 * 0.000006 + 1000.0 * 0.0000225)
+        );
+    }
+
+    #[test]
+    fn test_long_context_counts_cache_tokens() {
+        let pricing = long_context_pricing();
+
+        // 10k input + 50k cache creation + 150k cache read = 210k context
+        let tokens = TokenCounts::new(10_000, 500, 50_000, 150_000);
+        assert_eq!(tokens.context_tokens(), 210_000);
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        let expected =
+            10_000.0 * 0.000006 + 500.0 * 0.0000225 + 50_000.0 * 0.0000075 + 150_000.0 * 0.0000006;
+        assert_eq!(cost, Cost::from_usd(expected));
+
+        // Output and reasoning tokens don't count towards the context
+        let tokens = TokenCounts::new(100_000, 150_000, 0, 0).with_reasoning_tokens(50_000);
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(
+            cost,
+            Cost::from_usd(100_000.0 * 0.000003 + 200_000.0 * 0.000015)
+        );
+    }
+
+    #[test]
+    fn test_long_context_without_tiered_rates() {
+        // Models without long-context rates keep their flat rates
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(0.000003),
+            output_cost_per_token: Some(0.000015),
+            ..Default::default()
+        };
+        let tokens = TokenCounts::new(300_000, 1000, 0, 0);
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(
+            cost,
+            Cost::from_usd(300_000.0 * 0.000003 + 1000.0 * 0.000015)
+        );
+    }
+
+    #[test]
+    fn test_web_search_cost() {
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(0.000003),
+            search_context_cost_per_query: Some(SearchContextCost {
+                search_context_size_medium: Some(0.01),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // 1000 * 0.000003 + 3 searches * $0.01; web fetches are free
+        let tokens = TokenCounts::new(1000, 0, 0, 0).with_server_tool_use(3, 2);
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(cost, Cost::from_usd(0.033));
+
+        // No search pricing: requests are not charged
+        let pricing = ModelPricing {
+            input_cost_per_token: Some(0.000003),
+            ..Default::default()
+        };
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(cost, Cost::from_usd(0.003));
     }
 
     #[test]
@@ -229,10 +460,11 @@ mod tests {
             output_cost_per_token: Some(0.00002),
             cache_creation_input_token_cost: Some(0.000015),
             cache_read_input_token_cost: Some(0.000001),
+            ..Default::default()
         };
 
         let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
-        assert_eq!(cost, 0.0);
+        assert_eq!(cost, Cost::ZERO);
     }
 
     #[test]
@@ -244,12 +476,13 @@ mod tests {
             output_cost_per_token: Some(0.00002),
             cache_creation_input_token_cost: Some(0.000015),
             cache_read_input_token_cost: Some(0.000001),
+            ..Default::default()
         };
 
         let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
         // Expected: (10M * 0.00001) + (5M * 0.00002) + (1M * 0.000015) + (500k * 0.000001)
         // = 100 + 100 + 15 + 0.5 = 215.5
-        assert!((cost - 215.5).abs() < 0.01);
+        assert_eq!(cost, Cost::from_usd(215.5));
     }
 
     #[test]
@@ -260,10 +493,11 @@ mod tests {
             output_cost_per_token: None,
             cache_creation_input_token_cost: None,
             cache_read_input_token_cost: None,
+            ..Default::default()
         };
 
         let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
-        assert_eq!(cost, 0.0);
+        assert_eq!(cost, Cost::ZERO);
     }
 
     #[test]
@@ -276,11 +510,11 @@ mod tests {
             output_cost_per_token: Some(0.0),
             cache_creation_input_token_cost: Some(0.0),
             cache_read_input_token_cost: Some(0.0),
+            ..Default::default()
         };
 
         let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
-        assert!(cost >= 0.0);
-        assert_eq!(cost, 0.0);
+        assert_eq!(cost, Cost::ZERO);
     }
 
     #[test]
@@ -292,12 +526,16 @@ mod tests {
             output_cost_per_token: Some(0.000000002),            // 2e-9
             cache_creation_input_token_cost: Some(0.0000000015), // 1.5e-9
             cache_read_input_token_cost: Some(0.0000000001),     // 1e-10
+            ..Default::default()
         };
 
+        // Costs are rounded to the micro-dollar, so a fraction of one is lost
+        let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
+        assert_eq!(cost, Cost::ZERO);
+
+        let tokens = TokenCounts::new(1000, 1000, 1000, 1000);
         let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
-        // Should handle very small numbers without precision issues
-        assert!(cost > 0.0);
-        assert!(cost < 0.00001);
+        assert_eq!(cost, Cost::from_micros(5));
     }
 
     #[tokio::test]
@@ -308,11 +546,61 @@ mod tests {
         let model = ModelName::new("totally-unknown-model-xyz");
 
         let result = calculator
-            .calculate_with_mode(&tokens, &model, None, CostMode::Auto)
+            .calculate_with_mode(&tokens, &model, Utc::now(), None, CostMode::Auto)
             .await;
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 0.0);
+        assert_eq!(result.unwrap(), Cost::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_auto_mode_unknown_model_errors_with_strict_pricing() {
+        let fetcher = Arc::new(PricingFetcher::new(true).await.with_strict_pricing(true));
+        let calculator = CostCalculator::new(fetcher);
+        let tokens = TokenCounts::new(1000, 500, 0, 0);
+
+        let model = ModelName::new("totally-unknown-model-xyz");
+        let result = calculator
+            .calculate_with_mode(&tokens, &model, Utc::now(), None, CostMode::Auto)
+            .await;
+        assert!(matches!(result.unwrap_err(), CcstatError::UnknownModel(_)));
+
+        let model = ModelName::new("claude-3-opus-20240229");
+        let result = calculator
+            .calculate_with_mode(&tokens, &model, Utc::now(), None, CostMode::Auto)
+            .await;
+        assert!(matches!(
+            result.unwrap_err(),
+            CcstatError::InexactPricing { .. }
+        ));
+
+        // Pre-calculated costs need no pricing
+        let result = calculator
+            .calculate_with_mode(&tokens, &model, Utc::now(), Some(1.23), CostMode::Auto)
+            .await;
+        assert_eq!(result.unwrap(), Cost::from_usd(1.23));
+    }
+
+    #[tokio::test]
+    async fn test_usage_priced_at_rates_of_the_time() {
+        let fetcher = Arc::new(PricingFetcher::new(true).await);
+        let calculator = CostCalculator::new(fetcher);
+        let tokens = TokenCounts::new(1_000_000, 1_000_000, 0, 0);
+        let model = ModelName::new("claude-2");
+
+        let before = "2023-09-01T12:00:00Z".parse().unwrap();
+        let cost = calculator
+            .calculate_with_mode(&tokens, &model, before, None, CostMode::Calculate)
+            .await
+            .unwrap();
+        assert_eq!(cost, Cost::from_usd(11.02 + 32.68));
+
+        let after = "2023-11-21T00:00:00Z".parse().unwrap();
+        let cost = calculator
+            .calculate_with_mode(&tokens, &model, after, None, CostMode::Auto)
+            .await
+            .unwrap();
+        assert_eq!(cost, Cost::from_usd(8.0 + 24.0));
     }
 
     #[tokio::test]
@@ -323,7 +611,7 @@ mod tests {
         let model = ModelName::new("totally-unknown-model-xyz");
 
         let result = calculator
-            .calculate_with_mode(&tokens, &model, None, CostMode::Calculate)
+            .calculate_with_mode(&tokens, &model, Utc::now(), None, CostMode::Calculate)
             .await;
 
         assert!(result.is_err());
@@ -338,10 +626,10 @@ mod tests {
         let model = ModelName::new("totally-unknown-model-xyz");
 
         let result = calculator
-            .calculate_with_mode(&tokens, &model, Some(1.23), CostMode::Auto)
+            .calculate_with_mode(&tokens, &model, Utc::now(), Some(1.23), CostMode::Auto)
             .await;
 
         assert!(result.is_ok());
-        assert!((result.unwrap() - 1.23).abs() < f64::EPSILON);
+        assert_eq!(result.unwrap(), Cost::from_usd(1.23));
     }
 }
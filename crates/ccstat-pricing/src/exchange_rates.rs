@@ -0,0 +1,178 @@
+//! Offline exchange rate tables
+//!
+//! Costs can be displayed in another currency at a fixed rate, without any
+//! network call. Rates come from the `[exchange_rates]` table of the user
+//! pricing file, or from a snapshot file given with `--exchange-rates`, in
+//! TOML or, with a `.json` extension, JSON:
+//!
+//! ```toml
+//! # Date the rates were taken, shown with converted reports
+//! as_of = "2025-06-02"
+//!
+//! # Units of each currency per USD
+//! [rates]
+//! EUR = 0.88
+//! JPY = 143.5
+//! TWD = 29.9
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use ccstat_pricing::exchange_rates::ExchangeRates;
+//!
+//! let rates = ExchangeRates::parse("as_of = \"2025-06-02\"\n[rates]\nEUR = 0.88\n").unwrap();
+//! let eur = rates.currency("eur").unwrap();
+//! assert_eq!(eur.code(), "EUR");
+//! assert_eq!(eur.rate(), 0.88);
+//! assert!(rates.currency("GBP").is_err());
+//! ```
+
+use ccstat_core::currency::Currency;
+use ccstat_core::error::{CcstatError, Result};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Exchange rates against USD, as of a date
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExchangeRates {
+    /// Date the rates were taken
+    #[serde(default)]
+    as_of: Option<NaiveDate>,
+    /// Units of each currency per USD, by ISO 4217 code
+    rates: HashMap<String, f64>,
+}
+
+impl ExchangeRates {
+    /// Read a snapshot file, as JSON if its extension is `.json` and as TOML
+    /// otherwise
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CcstatError::Config(format!(
+                "Cannot read exchange rates {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let rates = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content)
+                .map_err(|e| CcstatError::Config(e.to_string()))
+                .and_then(Self::validated)
+        } else {
+            Self::parse(&content)
+        };
+        rates.map_err(|e| {
+            CcstatError::Config(format!("Invalid exchange rates {}: {}", path.display(), e))
+        })
+    }
+
+    /// Parse a TOML rate table
+    pub fn parse(content: &str) -> Result<Self> {
+        let rates: Self =
+            toml::from_str(content).map_err(|e| CcstatError::Config(e.to_string()))?;
+        rates.validated()
+    }
+
+    /// Reject rates that are not positive numbers
+    pub(crate) fn validated(self) -> Result<Self> {
+        if let Some((code, rate)) = self
+            .rates
+            .iter()
+            .find(|(_, rate)| !(rate.is_finite() && **rate > 0.0))
+        {
+            return Err(CcstatError::Config(format!(
+                "exchange rate for {code} must be a positive number, got {rate}"
+            )));
+        }
+        Ok(self)
+    }
+
+    /// Date the rates were taken
+    pub fn as_of(&self) -> Option<NaiveDate> {
+        self.as_of
+    }
+
+    /// Units of `code` per USD, with the code in any case
+    pub fn rate(&self, code: &str) -> Option<f64> {
+        if code.eq_ignore_ascii_case("USD") {
+            return Some(1.0);
+        }
+        self.rates
+            .iter()
+            .find(|(listed, _)| listed.eq_ignore_ascii_case(code))
+            .map(|(_, rate)| *rate)
+    }
+
+    /// Display currency for `code` at the table's rate
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CcstatError::Config`] if the table has no rate for `code`.
+    pub fn currency(&self, code: &str) -> Result<Currency> {
+        let rate = self.rate(code).ok_or_else(|| {
+            CcstatError::Config(format!(
+                "No exchange rate for {}",
+                code.to_ascii_uppercase()
+            ))
+        })?;
+        Ok(Currency::new(code, rate).with_as_of(self.as_of))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_lookup() {
+        let rates = ExchangeRates::parse(
+            r#"
+            as_of = "2025-06-02"
+
+            [rates]
+            EUR = 0.88
+            jpy = 143.5
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rates.as_of(), NaiveDate::from_ymd_opt(2025, 6, 2));
+        assert_eq!(rates.rate("eur"), Some(0.88));
+        assert_eq!(rates.rate("JPY"), Some(143.5));
+        assert_eq!(rates.rate("usd"), Some(1.0));
+        assert_eq!(rates.rate("TWD"), None);
+
+        let currency = rates.currency("jpy").unwrap();
+        assert_eq!(currency.code(), "JPY");
+        assert_eq!(currency.as_of(), rates.as_of());
+        let err = rates.currency("twd").unwrap_err();
+        assert!(err.to_string().contains("No exchange rate for TWD"));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_tables() {
+        assert!(ExchangeRates::parse("[rates]\nEUR = 0").is_err());
+        assert!(ExchangeRates::parse("[rates]\nEUR = -1.0").is_err());
+        assert!(ExchangeRates::parse("[rates]\nEUR = \"0.9\"").is_err());
+        assert!(ExchangeRates::parse("date = \"2025-06-02\"\n[rates]").is_err());
+        assert!(ExchangeRates::parse("as_of = \"2025-06-02\"").is_err());
+    }
+
+    #[test]
+    fn test_load_json_and_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = dir.path().join("rates.json");
+        std::fs::write(&json, r#"{"as_of": "2025-06-02", "rates": {"TWD": 29.9}}"#).unwrap();
+        assert_eq!(ExchangeRates::load(&json).unwrap().rate("TWD"), Some(29.9));
+
+        let toml = dir.path().join("rates.toml");
+        std::fs::write(&toml, "[rates]\nTWD = 29.9\n").unwrap();
+        assert_eq!(ExchangeRates::load(&toml).unwrap().rate("TWD"), Some(29.9));
+
+        std::fs::write(&json, r#"{"rates": {"TWD": 0}}"#).unwrap();
+        let err = ExchangeRates::load(&json).unwrap_err();
+        assert!(err.to_string().contains("rates.json"));
+    }
+}
@@ -1,10 +1,19 @@
 //! Pricing fetcher and cost calculator for ccstat
 //!
-//! This crate handles fetching model pricing data from LiteLLM
-//! and calculating costs from token usage.
+//! This crate handles fetching model pricing data from LiteLLM, caching
+//! it on disk, calculating costs from token usage, and converting them to
+//! other currencies with offline exchange rates.
 
 pub mod cost_calculator;
+pub mod exchange_rates;
+pub mod model_resolver;
+pub mod pricing_cache;
 pub mod pricing_fetcher;
+pub mod user_pricing;
 
 pub use cost_calculator::CostCalculator;
-pub use pricing_fetcher::PricingFetcher;
+pub use exchange_rates::ExchangeRates;
+pub use model_resolver::MatchKind;
+pub use pricing_cache::PricingCache;
+pub use pricing_fetcher::{PricingFetcher, PricingLookup, PricingResolution, PricingSource};
+pub use user_pricing::UserPricing;
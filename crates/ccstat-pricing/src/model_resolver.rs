@@ -0,0 +1,404 @@
+//! Model name to pricing key resolution
+//!
+//! Model names in usage data do not always match a pricing key: they may be
+//! written with a provider prefix, a Bedrock version suffix, a Vertex `@`
+//! date, or a date the pricing data does not list. [`resolve`] tries, in
+//! order:
+//!
+//! 1. the name itself
+//! 2. the normalization rules, each applied on top of the previous ones
+//! 3. the ranked alias rules, applied to the normalized name
+//! 4. the longest pricing key that the normalized name or an alias starts
+//!    with, ending at a word boundary
+//!
+//! Every step depends only on which pricing keys exist, never on map
+//! iteration order, so a name always resolves to the same key. Steps 1 to 3
+//! are exact; a prefix match is a guess, which strict pricing rejects.
+//!
+//! # Examples
+//!
+//! ```
+//! use ccstat_pricing::model_resolver::{MatchKind, resolve};
+//! use std::collections::HashMap;
+//!
+//! let pricing = HashMap::from([
+//!     ("claude-3-opus".to_string(), ()),
+//!     ("claude-sonnet-4-5-20250929".to_string(), ()),
+//! ]);
+//!
+//! let resolution = resolve("claude-sonnet-4-5@20250929", &pricing);
+//! let matched = resolution.matched.unwrap();
+//! assert_eq!(matched.key, "claude-sonnet-4-5-20250929");
+//! assert_eq!(matched.kind, MatchKind::Normalized);
+//!
+//! let matched = resolve("claude-3-opus-20240229", &pricing).matched.unwrap();
+//! assert_eq!(matched.key, "claude-3-opus");
+//! assert_eq!(matched.kind, MatchKind::Prefix);
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// How a model name matched a pricing key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    /// The name is a pricing key
+    Exact,
+    /// The name is a pricing key once normalized
+    Normalized,
+    /// An alias rule turned the normalized name into a pricing key
+    Alias,
+    /// The longest pricing key the name starts with
+    Prefix,
+}
+
+impl MatchKind {
+    /// Whether the match is certain rather than a guess
+    pub fn is_exact(self) -> bool {
+        !matches!(self, MatchKind::Prefix)
+    }
+}
+
+impl fmt::Display for MatchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MatchKind::Exact => "exact",
+            MatchKind::Normalized => "normalized",
+            MatchKind::Alias => "alias",
+            MatchKind::Prefix => "prefix",
+        })
+    }
+}
+
+/// A candidate name tried while resolving
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveStep {
+    /// Rule that produced the candidate
+    pub rule: &'static str,
+    /// Candidate name
+    pub name: String,
+    /// Pricing key the candidate matched, if any
+    pub matched: Option<String>,
+}
+
+/// Pricing key a model name resolved to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyMatch {
+    /// Matched pricing key
+    pub key: String,
+    /// How it matched
+    pub kind: MatchKind,
+}
+
+/// Outcome of resolving a model name, with every candidate tried
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    /// Candidates tried, in order
+    pub steps: Vec<ResolveStep>,
+    /// Matched pricing key
+    pub matched: Option<KeyMatch>,
+}
+
+/// Rewrite rule producing a candidate name, or `None` when it does not apply
+type Rule = (&'static str, fn(&str) -> Option<String>);
+
+/// Normalization rules, applied in order on top of each other
+const NORMALIZATION_RULES: &[Rule] = &[
+    ("lowercase", lowercase),
+    ("strip provider path", strip_provider_path),
+    ("strip Bedrock prefix", strip_bedrock_prefix),
+    ("strip Bedrock version", strip_bedrock_version),
+    ("Vertex date", vertex_date),
+];
+
+/// Alias rules, each applied to the normalized name, in rank order
+const ALIAS_RULES: &[Rule] = &[
+    ("anthropic/ prefix", |name| {
+        Some(format!("anthropic/{name}"))
+    }),
+    ("claude- prefix", |name| {
+        (!name.starts_with("claude-")).then(|| format!("claude-{name}"))
+    }),
+    ("dotted version", dotted_version),
+    ("dashed version", dashed_version),
+];
+
+/// Resolve a model name to one of the keys of `pricing`
+pub fn resolve<V>(model: &str, pricing: &HashMap<String, V>) -> Resolution {
+    let mut steps = Vec::new();
+    let mut try_exact = |rule, name: &str, kind| {
+        let matched = pricing.contains_key(name).then(|| name.to_string());
+        steps.push(ResolveStep {
+            rule,
+            name: name.to_string(),
+            matched: matched.clone(),
+        });
+        matched.map(|key| KeyMatch { key, kind })
+    };
+
+    if let Some(matched) = try_exact("exact", model, MatchKind::Exact) {
+        return Resolution::found(steps, matched);
+    }
+
+    let mut normalized = model.to_string();
+    for (rule, apply) in NORMALIZATION_RULES {
+        let Some(name) = apply(&normalized).filter(|name| *name != normalized) else {
+            continue;
+        };
+        normalized = name;
+        if let Some(matched) = try_exact(rule, &normalized, MatchKind::Normalized) {
+            return Resolution::found(steps, matched);
+        }
+    }
+
+    let mut candidates = vec![("longest prefix", normalized.clone())];
+    for (rule, apply) in ALIAS_RULES {
+        let Some(name) = apply(&normalized).filter(|name| *name != normalized) else {
+            continue;
+        };
+        if let Some(matched) = try_exact(rule, &name, MatchKind::Alias) {
+            return Resolution::found(steps, matched);
+        }
+        candidates.push(("longest prefix of alias", name));
+    }
+
+    // The longest prefix of any candidate wins, the earliest on a tie
+    let mut best: Option<&str> = None;
+    for (rule, name) in candidates {
+        let matched = longest_prefix_key(&name, pricing);
+        if let Some(key) = matched
+            && best.is_none_or(|best| key.len() > best.len())
+        {
+            best = Some(key);
+        }
+        steps.push(ResolveStep {
+            rule,
+            name,
+            matched: matched.map(str::to_string),
+        });
+    }
+
+    Resolution {
+        steps,
+        matched: best.map(|key| KeyMatch {
+            key: key.to_string(),
+            kind: MatchKind::Prefix,
+        }),
+    }
+}
+
+impl Resolution {
+    fn found(steps: Vec<ResolveStep>, matched: KeyMatch) -> Self {
+        Self {
+            steps,
+            matched: Some(matched),
+        }
+    }
+}
+
+/// Longest key that `name` starts with, followed by a non-alphanumeric
+/// character
+fn longest_prefix_key<'a, V>(name: &str, pricing: &'a HashMap<String, V>) -> Option<&'a str> {
+    pricing
+        .keys()
+        .filter(|key| {
+            name.strip_prefix(key.as_str())
+                .and_then(|rest| rest.chars().next())
+                .is_some_and(|next| !next.is_ascii_alphanumeric())
+        })
+        .max_by_key(|key| key.len())
+        .map(String::as_str)
+}
+
+fn lowercase(name: &str) -> Option<String> {
+    Some(name.trim().to_ascii_lowercase())
+}
+
+/// `vertex_ai/claude-3-opus` → `claude-3-opus`
+fn strip_provider_path(name: &str) -> Option<String> {
+    name.rsplit_once('/').map(|(_, model)| model.to_string())
+}
+
+/// `us.anthropic.claude-3-opus` → `claude-3-opus`
+fn strip_bedrock_prefix(name: &str) -> Option<String> {
+    let name = ["us.", "eu.", "apac.", "global."]
+        .iter()
+        .find_map(|region| name.strip_prefix(region))
+        .unwrap_or(name);
+    name.strip_prefix("anthropic.").map(str::to_string)
+}
+
+/// `claude-3-opus-20240229-v1:0` → `claude-3-opus-20240229`
+///
+/// The `:N` revision is required, so that `claude-v2` is left alone.
+fn strip_bedrock_version(name: &str) -> Option<String> {
+    let (model, version) = name.rsplit_once("-v")?;
+    let (major, minor) = version.split_once(':')?;
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    (is_number(major) && is_number(minor)).then(|| model.to_string())
+}
+
+/// `claude-3-opus@20240229` → `claude-3-opus-20240229`
+fn vertex_date(name: &str) -> Option<String> {
+    name.contains('@').then(|| name.replace('@', "-"))
+}
+
+/// `claude-3-5-sonnet` → `claude-3.5-sonnet`
+fn dotted_version(name: &str) -> Option<String> {
+    let parts: Vec<&str> = name.split('-').collect();
+    let is_digit = |part: &str| part.len() == 1 && part.chars().all(|c| c.is_ascii_digit());
+    let index = parts
+        .windows(2)
+        .position(|pair| is_digit(pair[0]) && is_digit(pair[1]))?;
+    let mut name = parts[..index].join("-");
+    if !name.is_empty() {
+        name.push('-');
+    }
+    name.push_str(&format!("{}.{}", parts[index], parts[index + 1]));
+    for part in &parts[index + 2..] {
+        name.push('-');
+        name.push_str(part);
+    }
+    Some(name)
+}
+
+/// `claude-3.5-sonnet` → `claude-3-5-sonnet`
+fn dashed_version(name: &str) -> Option<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let index = (1..chars.len().saturating_sub(1)).find(|&i| {
+        chars[i] == '.' && chars[i - 1].is_ascii_digit() && chars[i + 1].is_ascii_digit()
+    })?;
+    let mut name: String = chars[..index].iter().collect();
+    name.push('-');
+    name.extend(&chars[index + 1..]);
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pricing(keys: &[&str]) -> HashMap<String, ()> {
+        keys.iter().map(|key| (key.to_string(), ())).collect()
+    }
+
+    fn resolved(model: &str, pricing: &HashMap<String, ()>) -> Option<(String, MatchKind)> {
+        resolve(model, pricing)
+            .matched
+            .map(|matched| (matched.key, matched.kind))
+    }
+
+    #[test]
+    fn test_normalization_rules() {
+        let pricing = pricing(&["claude-3-opus-20240229", "claude-sonnet-4-5-20250929"]);
+        for model in [
+            "Claude-3-Opus-20240229",
+            "vertex_ai/claude-3-opus-20240229",
+            "anthropic.claude-3-opus-20240229-v1:0",
+            "us.anthropic.claude-3-opus-20240229-v1:0",
+            "claude-3-opus@20240229",
+        ] {
+            assert_eq!(
+                resolved(model, &pricing),
+                Some(("claude-3-opus-20240229".to_string(), MatchKind::Normalized)),
+                "{model}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_alias_rules_in_rank_order() {
+        let pricing = pricing(&[
+            "anthropic/claude-3-opus",
+            "claude-3-opus",
+            "claude-3.5-sonnet",
+            "claude-sonnet-4-5",
+        ]);
+        assert_eq!(
+            resolved("claude-3-5-sonnet", &pricing),
+            Some(("claude-3.5-sonnet".to_string(), MatchKind::Alias))
+        );
+        assert_eq!(
+            resolved("claude-sonnet-4.5", &pricing),
+            Some(("claude-sonnet-4-5".to_string(), MatchKind::Alias))
+        );
+        assert_eq!(
+            resolved("3-opus", &pricing),
+            Some(("claude-3-opus".to_string(), MatchKind::Alias))
+        );
+
+        // The exact key beats the anthropic/ alias
+        assert_eq!(
+            resolved("claude-3-opus", &pricing),
+            Some(("claude-3-opus".to_string(), MatchKind::Exact))
+        );
+    }
+
+    #[test]
+    fn test_longest_prefix() {
+        let pricing = pricing(&["claude-3", "claude-3-opus", "claude-3.5-sonnet", "gpt-4"]);
+        assert_eq!(
+            resolved("claude-3-opus-20240229", &pricing),
+            Some(("claude-3-opus".to_string(), MatchKind::Prefix))
+        );
+        assert_eq!(
+            resolved("claude-3-5-sonnet-20241022", &pricing),
+            Some(("claude-3.5-sonnet".to_string(), MatchKind::Prefix))
+        );
+        // Prefixes must end at a word boundary
+        assert_eq!(resolved("gpt-4o-mini", &pricing), None);
+        // A key containing the name is not a match
+        assert_eq!(resolved("opus", &pricing), None);
+    }
+
+    #[test]
+    fn test_resolution_is_deterministic() {
+        let keys: Vec<String> = (0..50).map(|i| format!("claude-3-opus-{i}")).collect();
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        let first = resolve("claude-3-opus-7-beta", &pricing(&keys));
+        for _ in 0..20 {
+            // A fresh map has a fresh iteration order
+            assert_eq!(resolve("claude-3-opus-7-beta", &pricing(&keys)), first);
+        }
+        assert_eq!(first.matched.unwrap().key, "claude-3-opus-7");
+    }
+
+    #[test]
+    fn test_steps_record_the_chain() {
+        let pricing = pricing(&["claude-sonnet-4-5-20250929"]);
+        let resolution = resolve("anthropic.claude-sonnet-4-5-20250929-v1:0", &pricing);
+        let rules: Vec<_> = resolution.steps.iter().map(|step| step.rule).collect();
+        assert_eq!(
+            rules,
+            ["exact", "strip Bedrock prefix", "strip Bedrock version"]
+        );
+        assert!(resolution.steps[0].matched.is_none());
+        assert_eq!(
+            resolution.steps[2].matched.as_deref(),
+            Some("claude-sonnet-4-5-20250929")
+        );
+
+        let resolution = resolve("unknown-model", &pricing);
+        assert!(resolution.matched.is_none());
+        assert!(resolution.steps.iter().all(|step| step.matched.is_none()));
+    }
+
+    #[test]
+    fn test_version_rewrites() {
+        assert_eq!(
+            dotted_version("claude-sonnet-4-5-20250929").as_deref(),
+            Some("claude-sonnet-4.5-20250929")
+        );
+        assert_eq!(dotted_version("claude-3-opus-20240229"), None);
+        assert_eq!(
+            dashed_version("claude-3.5-sonnet").as_deref(),
+            Some("claude-3-5-sonnet")
+        );
+        assert_eq!(dashed_version("claude-2"), None);
+        assert_eq!(strip_bedrock_version("claude-v2"), None);
+        assert_eq!(
+            strip_bedrock_version("claude-3-haiku-20240307-v1:0").as_deref(),
+            Some("claude-3-haiku-20240307")
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! On-disk cache of fetched pricing data
+//!
+//! Pricing fetched from LiteLLM is written to `pricing.json` under the user
+//! cache directory together with the time it was fetched, so later runs can
+//! reuse it instead of downloading it again. Cached data older than the TTL
+//! is refetched; offline runs use it regardless of age.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use ccstat_pricing::{pricing_cache::PricingCache, pricing_fetcher::PricingFetcher};
+//! use std::time::Duration;
+//!
+//! # async fn example() {
+//! let mut fetcher = PricingFetcher::new(false).await;
+//! if let Some(path) = PricingCache::default_path() {
+//!     let cache = PricingCache::new(path).with_ttl(Duration::from_secs(6 * 60 * 60));
+//!     fetcher = fetcher.with_disk_cache(cache);
+//! }
+//! # }
+//! ```
+
+use ccstat_core::error::Result;
+use ccstat_core::types::ModelPricing;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long cached pricing is used before it is fetched again
+pub const DEFAULT_PRICING_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Pricing data as stored in the cache file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedPricing {
+    /// When the pricing was fetched
+    pub fetched_at: DateTime<Utc>,
+    /// Pricing per model, as fetched
+    pub models: HashMap<String, ModelPricing>,
+}
+
+impl CachedPricing {
+    /// Whether the pricing is younger than `ttl` at `now`
+    pub fn is_fresh(&self, ttl: Duration, now: DateTime<Utc>) -> bool {
+        let Ok(ttl) = chrono::Duration::from_std(ttl) else {
+            return true;
+        };
+        now.signed_duration_since(self.fetched_at) < ttl
+    }
+}
+
+/// Location and lifetime of the pricing cache file
+#[derive(Debug, Clone)]
+pub struct PricingCache {
+    path: PathBuf,
+    ttl: Duration,
+}
+
+impl PricingCache {
+    /// Cache stored at `path`, with the default TTL
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ttl: DEFAULT_PRICING_CACHE_TTL,
+        }
+    }
+
+    /// Default cache file, `ccstat/pricing.json` under the user cache directory
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("ccstat").join("pricing.json"))
+    }
+
+    /// Set how long cached pricing stays fresh
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Path of the cache file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// How long cached pricing stays fresh
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Read the cached pricing, if the file exists
+    pub fn load(&self) -> Result<Option<CachedPricing>> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Write pricing fetched at `fetched_at` to the cache file
+    ///
+    /// The file is written next to its final location and renamed into
+    /// place, so concurrent readers never see a partial file.
+    pub fn store(
+        &self,
+        models: &HashMap<String, ModelPricing>,
+        fetched_at: DateTime<Utc>,
+    ) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let cached = CachedPricing {
+            fetched_at,
+            models: models.clone(),
+        };
+        let tmp_path = self
+            .path
+            .with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&tmp_path, serde_json::to_vec(&cached)?)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn models() -> HashMap<String, ModelPricing> {
+        HashMap::from([(
+            "claude-3-opus".to_string(),
+            ModelPricing {
+                input_cost_per_token: Some(0.000015),
+                output_cost_per_token: Some(0.000075),
+                ..Default::default()
+            },
+        )])
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = PricingCache::new(dir.path().join("nested").join("pricing.json"));
+        assert!(cache.load().unwrap().is_none());
+
+        let fetched_at = Utc::now();
+        cache.store(&models(), fetched_at).unwrap();
+
+        let cached = cache.load().unwrap().unwrap();
+        assert_eq!(cached.fetched_at, fetched_at);
+        assert_eq!(cached.models, models());
+    }
+
+    #[test]
+    fn test_freshness() {
+        let now = Utc::now();
+        let cached = CachedPricing {
+            fetched_at: now - chrono::Duration::hours(2),
+            models: models(),
+        };
+        assert!(cached.is_fresh(Duration::from_secs(3 * 60 * 60), now));
+        assert!(!cached.is_fresh(Duration::from_secs(60 * 60), now));
+        assert!(!cached.is_fresh(Duration::ZERO, now));
+    }
+
+    #[test]
+    fn test_corrupt_cache_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = PricingCache::new(dir.path().join("pricing.json"));
+        std::fs::write(cache.path(), "not json").unwrap();
+        assert!(cache.load().is_err());
+    }
+}
@@ -1,8 +1,13 @@
 //! Pricing fetcher module for LiteLLM model pricing data
 
-use ccstat_core::error::Result;
-use ccstat_core::types::ModelPricing;
+use crate::model_resolver::{self, KeyMatch, MatchKind, Resolution, ResolveStep};
+use crate::pricing_cache::{CachedPricing, PricingCache};
+use crate::user_pricing::UserPricing;
+use ccstat_core::error::{CcstatError, Result};
+use ccstat_core::types::{ModelName, ModelPricing};
+use chrono::Utc;
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
@@ -14,12 +19,100 @@ const LITELLM_PRICING_URL: &str =
 /// Embedded pricing data for offline mode
 const EMBEDDED_PRICING: &str = include_str!("../embedded/pricing.json");
 
+/// Where a model's pricing came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PricingSource {
+    /// The user pricing file
+    UserFile,
+    /// Pricing fetched from LiteLLM by this run
+    LiteLlm,
+    /// Pricing fetched from LiteLLM by an earlier run, read from the disk cache
+    DiskCache,
+    /// Pricing embedded in ccstat
+    Embedded,
+}
+
+impl fmt::Display for PricingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PricingSource::UserFile => "user pricing file",
+            PricingSource::LiteLlm => "LiteLLM",
+            PricingSource::DiskCache => "LiteLLM (cached)",
+            PricingSource::Embedded => "embedded",
+        })
+    }
+}
+
+/// Pricing found for a model, and how it was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingLookup {
+    /// Pricing key the model matched
+    pub key: String,
+    /// How the model name matched the key
+    pub match_kind: MatchKind,
+    /// Model the user pricing file maps the model name to, if any
+    pub alias: Option<String>,
+    /// Where the matched pricing came from
+    pub source: PricingSource,
+    /// Discount from the user pricing file, in percent
+    pub discount_percent: Option<f64>,
+    /// Rates after any discount
+    pub pricing: ModelPricing,
+}
+
+/// How a model name resolved to a pricing key, step by step
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingResolution {
+    /// Model the user pricing file maps the model name to, if any
+    pub alias: Option<String>,
+    /// Candidates tried, in order
+    pub steps: Vec<ResolveStep>,
+    /// Matched pricing key
+    pub matched: Option<KeyMatch>,
+    /// Where the matched pricing came from
+    pub source: Option<PricingSource>,
+}
+
+/// Loaded pricing data with the source of each model's rates
+#[derive(Debug, Default)]
+struct PricingTable {
+    models: HashMap<String, ModelPricing>,
+    sources: HashMap<String, PricingSource>,
+}
+
+impl PricingTable {
+    fn from_source(models: HashMap<String, ModelPricing>, source: PricingSource) -> Self {
+        let sources = models.keys().map(|key| (key.clone(), source)).collect();
+        Self { models, sources }
+    }
+
+    /// Resolve a model name, with the matched rates and their source
+    fn resolve(&self, model_name: &str) -> (Resolution, Option<(ModelPricing, PricingSource)>) {
+        let resolution = model_resolver::resolve(model_name, &self.models);
+        let found = resolution.matched.as_ref().map(|matched| {
+            let source = self
+                .sources
+                .get(&matched.key)
+                .copied()
+                .unwrap_or(PricingSource::Embedded);
+            (self.models[&matched.key].clone(), source)
+        });
+        (resolution, found)
+    }
+}
+
 /// Fetches and caches model pricing data
 pub struct PricingFetcher {
     /// Cached pricing data
-    cache: Arc<RwLock<Option<HashMap<String, ModelPricing>>>>,
+    cache: Arc<RwLock<Option<PricingTable>>>,
     /// Whether to operate in offline mode
     offline_mode: bool,
+    /// On-disk cache of fetched pricing, shared across runs
+    disk_cache: Option<PricingCache>,
+    /// User pricing overrides
+    user_pricing: Option<UserPricing>,
+    /// Whether to reject guessed model matches
+    strict: bool,
     /// HTTP client
     client: reqwest::Client,
 }
@@ -30,31 +123,189 @@ impl PricingFetcher {
         Self {
             cache: Arc::new(RwLock::new(None)),
             offline_mode: offline,
+            disk_cache: None,
+            user_pricing: None,
+            strict: false,
             client: reqwest::Client::new(),
         }
     }
 
+    /// Reuse fetched pricing across runs through an on-disk cache
+    ///
+    /// Cached pricing younger than the cache's TTL is used instead of
+    /// fetching. Older pricing is refetched, and still used if the fetch
+    /// fails. In offline mode cached pricing is used regardless of its age,
+    /// falling back to the embedded data only when there is none.
+    pub fn with_disk_cache(mut self, disk_cache: PricingCache) -> Self {
+        self.disk_cache = Some(disk_cache);
+        self
+    }
+
+    /// Apply a user pricing file
+    ///
+    /// Its aliases are resolved first, its model rates take precedence over
+    /// fetched and embedded pricing, and its discounts apply to pricing from
+    /// any source.
+    pub fn with_user_pricing(mut self, user_pricing: UserPricing) -> Self {
+        self.user_pricing = Some(user_pricing);
+        self
+    }
+
+    /// User pricing overrides, if any
+    pub fn user_pricing(&self) -> Option<&UserPricing> {
+        self.user_pricing.as_ref()
+    }
+
+    /// Reject model names that match pricing only by prefix
+    ///
+    /// In strict mode, looking up such a model fails with
+    /// [`CcstatError::InexactPricing`] instead of pricing it as the closest
+    /// match, and the cost calculator reports unknown models as errors
+    /// instead of costing them at $0.00.
+    pub fn with_strict_pricing(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Whether guessed model matches are rejected
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
     /// Get pricing for a specific model
     pub async fn get_model_pricing(&self, model_name: &str) -> Result<Option<ModelPricing>> {
+        Ok(self.lookup(model_name).await?.map(|lookup| lookup.pricing))
+    }
+
+    /// Get pricing for a specific model along with where it came from
+    ///
+    /// # Errors
+    ///
+    /// In strict mode, returns [`CcstatError::InexactPricing`] if the model
+    /// matched only by prefix.
+    pub async fn lookup(&self, model_name: &str) -> Result<Option<PricingLookup>> {
+        let (resolution, found) = self.resolve_pricing(model_name).await?;
+        let (Some(matched), Some((pricing, source))) = (resolution.matched, found) else {
+            return Ok(None);
+        };
+        if self.strict && !matched.kind.is_exact() {
+            return Err(CcstatError::InexactPricing {
+                model: ModelName::new(model_name),
+                candidate: matched.key,
+            });
+        }
+
+        let alias = resolution.alias;
+        let mut pricing = pricing;
+        if let Some(history) = self.user_pricing.as_ref().and_then(|user| {
+            user.history(model_name)
+                .or_else(|| alias.as_ref().and_then(|alias| user.history(alias)))
+                .or_else(|| user.history(&matched.key))
+        }) {
+            history.apply(&mut pricing);
+        }
+
+        let discount_percent = self.user_pricing.as_ref().and_then(|user| {
+            user.discount(model_name)
+                .or_else(|| alias.as_ref().and_then(|alias| user.discount(alias)))
+        });
+        let pricing = match discount_percent {
+            Some(percent) => pricing.scaled(1.0 - percent / 100.0),
+            None => pricing,
+        };
+
+        Ok(Some(PricingLookup {
+            key: matched.key,
+            match_kind: matched.kind,
+            alias,
+            source,
+            discount_percent,
+            pricing,
+        }))
+    }
+
+    /// Explain how a model name resolves to a pricing key
+    ///
+    /// Lists the user pricing file's alias and rates, then every candidate
+    /// name tried against the fetched or embedded pricing, in order.
+    pub async fn resolve(&self, model_name: &str) -> Result<PricingResolution> {
+        Ok(self.resolve_pricing(model_name).await?.0)
+    }
+
+    /// Resolve a model name, with the matched rates and their source
+    async fn resolve_pricing(
+        &self,
+        model_name: &str,
+    ) -> Result<(PricingResolution, Option<(ModelPricing, PricingSource)>)> {
+        let user_pricing = self.user_pricing.as_ref();
+        let alias = user_pricing
+            .and_then(|user| user.resolve_alias(model_name))
+            .map(str::to_string);
+        let name = alias.clone().unwrap_or_else(|| model_name.to_string());
+
+        let mut steps = Vec::new();
+        if let Some(user) = user_pricing {
+            let pricing = user.model(&name);
+            steps.push(ResolveStep {
+                rule: "user pricing file",
+                name: name.to_string(),
+                matched: pricing.map(|_| name.to_string()),
+            });
+            if let Some(pricing) = pricing {
+                let resolution = PricingResolution {
+                    alias,
+                    steps,
+                    matched: Some(KeyMatch {
+                        key: name,
+                        kind: MatchKind::Exact,
+                    }),
+                    source: Some(PricingSource::UserFile),
+                };
+                return Ok((resolution, Some((pricing.clone(), PricingSource::UserFile))));
+            }
+        }
+
+        let (resolution, found) = self.resolve_loaded(&name).await?;
+        steps.extend(resolution.steps);
+        let resolution = PricingResolution {
+            alias,
+            steps,
+            matched: resolution.matched,
+            source: found.as_ref().map(|(_, source)| *source),
+        };
+        Ok((resolution, found))
+    }
+
+    /// Resolve a model against the fetched or embedded pricing, loading it
+    /// if needed
+    async fn resolve_loaded(
+        &self,
+        model_name: &str,
+    ) -> Result<(Resolution, Option<(ModelPricing, PricingSource)>)> {
         // Check cache first
         {
             let cache = self.cache.read().await;
-            if let Some(ref pricing_map) = *cache
-                && let Some(pricing) = Self::find_model_pricing(pricing_map, model_name)
-            {
-                return Ok(Some(pricing.clone()));
+            if let Some(table) = cache.as_ref() {
+                return Ok(table.resolve(model_name));
             }
         }
 
         // Load pricing if not cached
         self.ensure_pricing_loaded().await?;
 
-        // Check again after loading
         let cache = self.cache.read().await;
         Ok(cache
             .as_ref()
-            .and_then(|map| Self::find_model_pricing(map, model_name))
-            .cloned())
+            .map(|table| table.resolve(model_name))
+            .unwrap_or_else(|| {
+                (
+                    Resolution {
+                        steps: Vec::new(),
+                        matched: None,
+                    },
+                    None,
+                )
+            }))
     }
 
     /// Ensure pricing data is loaded
@@ -69,32 +320,115 @@ impl PricingFetcher {
         Ok(())
     }
 
-    /// Fetch pricing data from LiteLLM or embedded data
-    async fn fetch_pricing_data(&self) -> Result<HashMap<String, ModelPricing>> {
+    /// Fetch pricing data from the disk cache, LiteLLM, or embedded data
+    async fn fetch_pricing_data(&self) -> Result<PricingTable> {
+        let cached = self.load_disk_cache();
+
         if self.offline_mode {
+            if let Some(cached) = cached {
+                info!(
+                    "Using cached pricing data fetched at {} (offline mode)",
+                    cached.fetched_at
+                );
+                return Self::merge_embedded_pricing(cached.models, PricingSource::DiskCache);
+            }
             info!("Using embedded pricing data (offline mode)");
-            return Self::parse_embedded_pricing();
+            return Self::embedded_table();
+        }
+
+        if let (Some(cached), Some(disk_cache)) = (&cached, &self.disk_cache)
+            && cached.is_fresh(disk_cache.ttl(), Utc::now())
+        {
+            info!("Using cached pricing data fetched at {}", cached.fetched_at);
+            return Self::merge_embedded_pricing(cached.models.clone(), PricingSource::DiskCache);
         }
 
-        match self.fetch_litellm_pricing().await {
-            Ok(mut online_data) => {
+        match self.fetch_and_store_pricing().await {
+            Ok(online_data) => {
                 info!("Successfully fetched pricing data from LiteLLM");
-                // Merge with embedded data as fallback for missing models
-                let embedded_data = Self::parse_embedded_pricing()?;
-                for (model, pricing) in embedded_data {
-                    online_data.entry(model).or_insert(pricing);
+                Self::merge_embedded_pricing(online_data, PricingSource::LiteLlm)
+            }
+            Err(e) => match cached {
+                Some(cached) => {
+                    warn!(
+                        "Failed to fetch pricing data: {}, using cached data fetched at {}",
+                        e, cached.fetched_at
+                    );
+                    Self::merge_embedded_pricing(cached.models, PricingSource::DiskCache)
                 }
-                debug!(
-                    "Merged online pricing ({} models) with embedded pricing",
-                    online_data.len()
+                None => {
+                    warn!("Failed to fetch pricing data: {}, using embedded data", e);
+                    Self::embedded_table()
+                }
+            },
+        }
+    }
+
+    /// Read the disk cache, treating an unreadable cache as missing
+    fn load_disk_cache(&self) -> Option<CachedPricing> {
+        let disk_cache = self.disk_cache.as_ref()?;
+        match disk_cache.load() {
+            Ok(cached) => cached,
+            Err(e) => {
+                warn!(
+                    "Ignoring unreadable pricing cache {}: {}",
+                    disk_cache.path().display(),
+                    e
                 );
-                Ok(online_data)
+                None
             }
-            Err(e) => {
-                warn!("Failed to fetch pricing data: {}, using embedded data", e);
-                Self::parse_embedded_pricing()
+        }
+    }
+
+    /// Fetch pricing from LiteLLM and write it to the disk cache
+    async fn fetch_and_store_pricing(&self) -> Result<HashMap<String, ModelPricing>> {
+        let online_data = self.fetch_litellm_pricing().await?;
+        if let Some(disk_cache) = &self.disk_cache {
+            match disk_cache.store(&online_data, Utc::now()) {
+                Ok(()) => debug!("Cached pricing data at {}", disk_cache.path().display()),
+                Err(e) => warn!(
+                    "Failed to write pricing cache {}: {}",
+                    disk_cache.path().display(),
+                    e
+                ),
             }
         }
+        Ok(online_data)
+    }
+
+    /// Fill in models, web search rates, and earlier rates missing from
+    /// fetched pricing with the embedded data
+    fn merge_embedded_pricing(
+        online_data: HashMap<String, ModelPricing>,
+        source: PricingSource,
+    ) -> Result<PricingTable> {
+        let mut table = PricingTable::from_source(online_data, source);
+        for (model, pricing) in Self::parse_embedded_pricing()? {
+            let online = table.models.entry(model.clone()).or_insert_with(|| {
+                table.sources.insert(model, PricingSource::Embedded);
+                pricing.clone()
+            });
+            if online.search_context_cost_per_query.is_none() {
+                online.search_context_cost_per_query = pricing.search_context_cost_per_query;
+            }
+            if online.effective_from.is_none() && online.rate_periods.is_empty() {
+                online.effective_from = pricing.effective_from;
+                online.rate_periods = pricing.rate_periods;
+            }
+        }
+        debug!(
+            "Merged online pricing ({} models) with embedded pricing",
+            table.models.len()
+        );
+        Ok(table)
+    }
+
+    /// Embedded pricing data on its own
+    fn embedded_table() -> Result<PricingTable> {
+        Ok(PricingTable::from_source(
+            Self::parse_embedded_pricing()?,
+            PricingSource::Embedded,
+        ))
     }
 
     /// Fetch pricing from LiteLLM API
@@ -126,53 +460,37 @@ impl PricingFetcher {
         Ok(Self::parse_pricing_data(data))
     }
 
-    /// Find pricing for a model, with fuzzy matching
+    /// Find pricing for a model by name resolution
+    #[cfg(test)]
     fn find_model_pricing<'a>(
         pricing_map: &'a HashMap<String, ModelPricing>,
-        model_name: &'a str,
+        model_name: &str,
     ) -> Option<&'a ModelPricing> {
-        // Exact match
-        if let Some(pricing) = pricing_map.get(model_name) {
-            return Some(pricing);
-        }
-
-        // Try common variations
-        let variations = [
-            model_name.to_string(),
-            format!("anthropic/{model_name}"),
-            format!("claude-{model_name}"),
-            model_name.replace("claude-3-", "claude-3."),
-            model_name.replace("claude-3.", "claude-3-"),
-        ];
-
-        for variant in &variations {
-            if let Some(pricing) = pricing_map.get(variant) {
-                debug!("Found pricing for {} using variant {}", model_name, variant);
-                return Some(pricing);
-            }
-        }
-
-        // Partial match (contains model name)
-        for (key, pricing) in pricing_map {
-            if key.contains(model_name) || model_name.contains(key) {
-                debug!(
-                    "Found pricing for {} using partial match {}",
-                    model_name, key
-                );
-                return Some(pricing);
-            }
-        }
-
-        None
+        model_resolver::resolve(model_name, pricing_map)
+            .matched
+            .map(|matched| &pricing_map[&matched.key])
     }
 
     /// Force refresh pricing data
-    pub async fn refresh(&self) -> Result<()> {
+    ///
+    /// Outside offline mode this always fetches from LiteLLM, ignoring the
+    /// disk cache's TTL, and fails if the fetch does. The fetched pricing is
+    /// written to the disk cache. Returns the number of models with pricing.
+    pub async fn refresh(&self) -> Result<usize> {
         let mut cache = self.cache.write().await;
         *cache = None;
-        drop(cache);
 
-        self.ensure_pricing_loaded().await
+        let pricing_data = if self.offline_mode {
+            self.fetch_pricing_data().await?
+        } else {
+            Self::merge_embedded_pricing(
+                self.fetch_and_store_pricing().await?,
+                PricingSource::LiteLlm,
+            )?
+        };
+        let count = pricing_data.models.len();
+        *cache = Some(pricing_data);
+        Ok(count)
     }
 }
 
@@ -189,6 +507,274 @@ mod tests {
         assert!(!online_fetcher.offline_mode);
     }
 
+    fn cached_pricing(input_cost: f64) -> HashMap<String, ModelPricing> {
+        HashMap::from([(
+            "claude-3-opus".to_string(),
+            ModelPricing {
+                input_cost_per_token: Some(input_cost),
+                output_cost_per_token: Some(0.000075),
+                ..Default::default()
+            },
+        )])
+    }
+
+    #[tokio::test]
+    async fn test_offline_uses_disk_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk_cache = PricingCache::new(dir.path().join("pricing.json"));
+        // Stale cached pricing is still used offline
+        disk_cache
+            .store(
+                &cached_pricing(0.123),
+                Utc::now() - chrono::Duration::days(30),
+            )
+            .unwrap();
+
+        let fetcher = PricingFetcher::new(true).await.with_disk_cache(disk_cache);
+        let pricing = fetcher
+            .get_model_pricing("claude-3-opus")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pricing.input_cost_per_token, Some(0.123));
+
+        // Models missing from the cache come from the embedded data
+        assert!(
+            fetcher
+                .get_model_pricing("claude-3-haiku")
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fresh_disk_cache_skips_fetch() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk_cache = PricingCache::new(dir.path().join("pricing.json"));
+        disk_cache
+            .store(&cached_pricing(0.456), Utc::now())
+            .unwrap();
+
+        let fetcher = PricingFetcher::new(false).await.with_disk_cache(disk_cache);
+        let pricing = fetcher
+            .get_model_pricing("claude-3-opus")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(pricing.input_cost_per_token, Some(0.456));
+    }
+
+    #[tokio::test]
+    async fn test_offline_ignores_unreadable_disk_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pricing.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let fetcher = PricingFetcher::new(true)
+            .await
+            .with_disk_cache(PricingCache::new(path));
+        assert!(
+            fetcher
+                .get_model_pricing("claude-3-opus-20240229")
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lookup_reports_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk_cache = PricingCache::new(dir.path().join("pricing.json"));
+        disk_cache
+            .store(&cached_pricing(0.123), Utc::now())
+            .unwrap();
+        let fetcher = PricingFetcher::new(true).await.with_disk_cache(disk_cache);
+
+        let lookup = fetcher.lookup("claude-3-opus").await.unwrap().unwrap();
+        assert_eq!(lookup.source, PricingSource::DiskCache);
+        assert_eq!(lookup.key, "claude-3-opus");
+        assert_eq!(lookup.match_kind, MatchKind::Exact);
+        assert!(lookup.alias.is_none());
+        assert!(lookup.discount_percent.is_none());
+
+        let lookup = fetcher.lookup("claude-3-haiku").await.unwrap().unwrap();
+        assert_eq!(lookup.source, PricingSource::Embedded);
+    }
+
+    #[tokio::test]
+    async fn test_user_pricing_takes_precedence() {
+        let user_pricing = UserPricing::parse(
+            r#"
+            [models."claude-3-opus"]
+            input_cost_per_token = 0.00001
+            output_cost_per_token = 0.00004
+
+            [models."gateway/sonnet"]
+            input_cost_per_token = 0.000002
+
+            [aliases]
+            "anthropic.claude-3-haiku-20240307-v1:0" = "claude-3-haiku"
+            "my-opus" = "claude-3-opus"
+
+            [[discounts]]
+            models = "anthropic.*"
+            percent = 50
+            "#,
+        )
+        .unwrap();
+        let fetcher = PricingFetcher::new(true)
+            .await
+            .with_user_pricing(user_pricing);
+
+        // User rates win over the embedded ones
+        let lookup = fetcher.lookup("claude-3-opus").await.unwrap().unwrap();
+        assert_eq!(lookup.source, PricingSource::UserFile);
+        assert_eq!(lookup.pricing.input_cost_per_token, Some(0.00001));
+
+        // Models only the user file knows
+        let lookup = fetcher.lookup("gateway/sonnet").await.unwrap().unwrap();
+        assert_eq!(lookup.source, PricingSource::UserFile);
+
+        // Aliases resolve into user rates too
+        let lookup = fetcher.lookup("my-opus").await.unwrap().unwrap();
+        assert_eq!(lookup.alias.as_deref(), Some("claude-3-opus"));
+        assert_eq!(lookup.source, PricingSource::UserFile);
+
+        // An alias onto embedded pricing, discounted by the alias name
+        let embedded = fetcher
+            .lookup("claude-3-haiku")
+            .await
+            .unwrap()
+            .unwrap()
+            .pricing;
+        let lookup = fetcher
+            .lookup("anthropic.claude-3-haiku-20240307-v1:0")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(lookup.alias.as_deref(), Some("claude-3-haiku"));
+        assert_eq!(lookup.source, PricingSource::Embedded);
+        assert_eq!(lookup.discount_percent, Some(50.0));
+        assert_eq!(lookup.pricing, embedded.scaled(0.5));
+    }
+
+    #[tokio::test]
+    async fn test_rate_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let disk_cache = PricingCache::new(dir.path().join("pricing.json"));
+        let mut models = cached_pricing(0.123);
+        models.insert(
+            "claude-2".to_string(),
+            ModelPricing {
+                input_cost_per_token: Some(0.000008),
+                ..Default::default()
+            },
+        );
+        disk_cache.store(&models, Utc::now()).unwrap();
+        let user_pricing = UserPricing::parse(
+            r#"
+            [history."claude-3-opus"]
+            effective_from = "2024-06-01"
+
+            [[history."claude-3-opus".rate_periods]]
+            effective_from = "2024-03-04"
+            input_cost_per_token = 0.2
+            "#,
+        )
+        .unwrap();
+        let fetcher = PricingFetcher::new(true)
+            .await
+            .with_disk_cache(disk_cache)
+            .with_user_pricing(user_pricing);
+        let august_2023 = "2023-08-01T00:00:00Z".parse().unwrap();
+
+        // Fetched pricing without history gets the embedded history
+        let lookup = fetcher.lookup("claude-2").await.unwrap().unwrap();
+        assert_eq!(lookup.source, PricingSource::DiskCache);
+        assert_eq!(
+            lookup.pricing.at(Utc::now()).input_cost_per_token,
+            Some(0.000008)
+        );
+        assert_eq!(
+            lookup.pricing.at(august_2023).input_cost_per_token,
+            Some(0.00001102)
+        );
+
+        // The user pricing file adds history to any model
+        let lookup = fetcher.lookup("claude-3-opus").await.unwrap().unwrap();
+        assert_eq!(
+            lookup.pricing.at(Utc::now()).input_cost_per_token,
+            Some(0.123)
+        );
+        assert_eq!(
+            lookup.pricing.at(august_2023).input_cost_per_token,
+            Some(0.2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strict_pricing_rejects_prefix_matches() {
+        let fetcher = PricingFetcher::new(true).await;
+        let lookup = fetcher
+            .lookup("claude-3-opus-20240229")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(lookup.key, "claude-3-opus");
+        assert_eq!(lookup.match_kind, MatchKind::Prefix);
+
+        let fetcher = PricingFetcher::new(true).await.with_strict_pricing(true);
+        let err = fetcher.lookup("claude-3-opus-20240229").await.unwrap_err();
+        assert!(matches!(
+            err,
+            CcstatError::InexactPricing { ref candidate, .. } if candidate == "claude-3-opus"
+        ));
+
+        // Exact, normalized, and alias matches are still accepted
+        let lookup = fetcher.lookup("claude-3-5-sonnet").await.unwrap().unwrap();
+        assert_eq!(lookup.key, "claude-3.5-sonnet");
+        assert_eq!(lookup.match_kind, MatchKind::Alias);
+        assert!(fetcher.lookup("unknown-model-xyz").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_lists_steps() {
+        let user_pricing = UserPricing::parse(
+            r#"
+            [aliases]
+            "my-sonnet" = "Claude-3-5-Sonnet"
+            "#,
+        )
+        .unwrap();
+        let fetcher = PricingFetcher::new(true)
+            .await
+            .with_user_pricing(user_pricing);
+
+        let resolution = fetcher.resolve("my-sonnet").await.unwrap();
+        assert_eq!(resolution.alias.as_deref(), Some("Claude-3-5-Sonnet"));
+        assert_eq!(resolution.source, Some(PricingSource::Embedded));
+        let rules: Vec<_> = resolution.steps.iter().map(|step| step.rule).collect();
+        assert_eq!(
+            rules,
+            [
+                "user pricing file",
+                "exact",
+                "lowercase",
+                "anthropic/ prefix",
+                "dotted version"
+            ]
+        );
+        let matched = resolution.matched.unwrap();
+        assert_eq!(matched.key, "claude-3.5-sonnet");
+        assert_eq!(matched.kind, MatchKind::Alias);
+
+        let resolution = fetcher.resolve("unknown-model-xyz").await.unwrap();
+        assert!(resolution.matched.is_none());
+        assert!(resolution.source.is_none());
+    }
+
     #[test]
     fn test_model_name_variations() {
         let mut pricing_map = HashMap::new();
@@ -199,14 +785,20 @@ mod tests {
                 output_cost_per_token: Some(0.00002),
                 cache_creation_input_token_cost: None,
                 cache_read_input_token_cost: None,
+                ..Default::default()
             },
         );
 
         // Should find exact match
         assert!(PricingFetcher::find_model_pricing(&pricing_map, "claude-3-opus").is_some());
 
-        // Should find partial match
-        assert!(PricingFetcher::find_model_pricing(&pricing_map, "opus").is_some());
+        // Should find the key a dated name starts with
+        assert!(
+            PricingFetcher::find_model_pricing(&pricing_map, "claude-3-opus-20240229").is_some()
+        );
+
+        // A key merely containing the name is not a match
+        assert!(PricingFetcher::find_model_pricing(&pricing_map, "opus").is_none());
     }
 
     #[tokio::test]
@@ -271,7 +863,7 @@ mod tests {
         assert!(pricing1.is_some());
 
         // Refresh the cache
-        fetcher.refresh().await.unwrap();
+        assert!(fetcher.refresh().await.unwrap() > 0);
 
         // Should still get pricing after refresh
         let pricing2 = fetcher
@@ -314,6 +906,7 @@ mod tests {
                 output_cost_per_token: Some(0.00002),
                 cache_creation_input_token_cost: None,
                 cache_read_input_token_cost: None,
+                ..Default::default()
             },
         );
 
@@ -324,6 +917,7 @@ mod tests {
                 output_cost_per_token: Some(0.00002),
                 cache_creation_input_token_cost: None,
                 cache_read_input_token_cost: None,
+                ..Default::default()
             },
         );
 
@@ -344,7 +938,9 @@ mod tests {
                 "input_cost_per_token": 0.00001,
                 "output_cost_per_token": 0.00002,
                 "cache_creation_input_token_cost": 0.000015,
-                "cache_read_input_token_cost": 0.000001
+                "cache_read_input_token_cost": 0.000001,
+                "input_cost_per_token_above_200k_tokens": 0.00002,
+                "output_cost_per_token_above_200k_tokens": 0.00003
             }),
         );
 
@@ -369,6 +965,19 @@ mod tests {
             Some(0.000015)
         );
         assert_eq!(model1_pricing.cache_read_input_token_cost, Some(0.000001));
+        assert_eq!(
+            model1_pricing.input_cost_per_token_above_200k_tokens,
+            Some(0.00002)
+        );
+        assert_eq!(
+            model1_pricing.output_cost_per_token_above_200k_tokens,
+            Some(0.00003)
+        );
+        assert!(
+            model1_pricing
+                .cache_read_input_token_cost_above_200k_tokens
+                .is_none()
+        );
 
         // Check that model2 is parsed with None values for invalid fields
         assert!(pricing_map.contains_key("model2"));
@@ -421,6 +1030,7 @@ mod tests {
                 output_cost_per_token: Some(0.00002),
                 cache_creation_input_token_cost: None,
                 cache_read_input_token_cost: None,
+                ..Default::default()
             },
         );
 
@@ -431,6 +1041,7 @@ mod tests {
                 output_cost_per_token: Some(0.00004),
                 cache_creation_input_token_cost: None,
                 cache_read_input_token_cost: None,
+                ..Default::default()
             },
         );
 
@@ -486,6 +1097,21 @@ mod tests {
                 "Wrong cache read cost for {}",
                 model_name
             );
+            assert_eq!(
+                pricing.input_cost_per_token_above_200k_tokens,
+                Some(0.000006),
+                "Wrong long-context input cost for {}",
+                model_name
+            );
+            assert_eq!(
+                pricing
+                    .search_context_cost_per_query
+                    .as_ref()
+                    .and_then(|cost| cost.per_request()),
+                Some(0.01),
+                "Wrong web search cost for {}",
+                model_name
+            );
         }
     }
 }
@@ -0,0 +1,392 @@
+//! User pricing file
+//!
+//! A TOML file with rates for models the fetched pricing lacks or prices
+//! differently, aliases that price one model id like another, percentage
+//! discounts, the earlier rates of models whose price changed, and exchange
+//! rates for displaying costs in other currencies. It is read
+//! from `~/.config/ccstat/pricing.toml`, or the path given with
+//! `--pricing-file`, and takes precedence over fetched and embedded pricing.
+//!
+//! ```toml
+//! # Rates in USD per token, with LiteLLM's field names
+//! [models."gateway/claude-sonnet"]
+//! input_cost_per_token = 0.000003
+//! output_cost_per_token = 0.000015
+//!
+//! # Model ids priced like another model
+//! [aliases]
+//! "anthropic.claude-sonnet-4-5-20250929-v1:0" = "claude-sonnet-4-5-20250929"
+//!
+//! # Percentage discounts; the first rule matching the model applies
+//! [[discounts]]
+//! models = "claude-opus-*"
+//! percent = 20
+//!
+//! # Earlier rates of a model, whatever the source of its current rates
+//! [history."gateway/claude-sonnet"]
+//! effective_from = "2025-07-01"
+//!
+//! [[history."gateway/claude-sonnet".rate_periods]]
+//! effective_from = "2025-01-01"
+//! input_cost_per_token = 0.0000035
+//! output_cost_per_token = 0.0000175
+//!
+//! # Units of each currency per USD, for --currency
+//! [exchange_rates]
+//! as_of = "2025-06-02"
+//! rates = { EUR = 0.88, JPY = 143.5, TWD = 29.9 }
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use ccstat_pricing::user_pricing::UserPricing;
+//!
+//! let user_pricing = UserPricing::parse(
+//!     r#"
+//!     [aliases]
+//!     "my-sonnet" = "claude-sonnet-4-5-20250929"
+//!
+//!     [[discounts]]
+//!     models = "claude-*"
+//!     percent = 10
+//!     "#,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(
+//!     user_pricing.resolve_alias("my-sonnet"),
+//!     Some("claude-sonnet-4-5-20250929")
+//! );
+//! assert_eq!(user_pricing.discount("claude-3-opus"), Some(10.0));
+//! assert_eq!(user_pricing.discount("gpt-5"), None);
+//! ```
+
+use crate::exchange_rates::ExchangeRates;
+use ccstat_core::error::{CcstatError, Result};
+use ccstat_core::types::{ModelPricing, RatePeriod};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Contents of a user pricing file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserPricing {
+    /// Rates per model name
+    #[serde(default)]
+    models: HashMap<String, ModelPricing>,
+    /// Model names priced like another model
+    #[serde(default)]
+    aliases: HashMap<String, String>,
+    /// Discount rules, in order
+    #[serde(default)]
+    discounts: Vec<DiscountRule>,
+    /// Earlier rates per model name
+    #[serde(default)]
+    history: HashMap<String, PricingHistory>,
+    /// Exchange rates for displaying costs in other currencies
+    #[serde(default)]
+    exchange_rates: Option<ExchangeRates>,
+    /// File the pricing was read from
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+/// Percentage discount on the models matching a pattern
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DiscountRule {
+    /// Model name pattern, where `*` matches any run of characters
+    pub models: String,
+    /// Discount in percent, from 0 to 100
+    pub percent: f64,
+}
+
+/// Earlier rates of a model
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PricingHistory {
+    /// Date, in UTC, from which the model's current rates apply
+    pub effective_from: NaiveDate,
+    /// Rates in force before then
+    #[serde(default)]
+    pub rate_periods: Vec<RatePeriod>,
+}
+
+impl PricingHistory {
+    /// Add the history to `pricing`
+    ///
+    /// The history's date replaces the pricing's, and its periods replace
+    /// any period starting on the same date.
+    pub fn apply(&self, pricing: &mut ModelPricing) {
+        pricing.effective_from = Some(self.effective_from);
+        for period in &self.rate_periods {
+            pricing
+                .rate_periods
+                .retain(|existing| existing.effective_from != period.effective_from);
+            pricing.rate_periods.push(period.clone());
+        }
+        pricing
+            .rate_periods
+            .sort_by_key(|period| period.effective_from);
+    }
+}
+
+impl DiscountRule {
+    /// Whether the rule applies to `model`
+    pub fn matches(&self, model: &str) -> bool {
+        glob_matches(&self.models, model)
+    }
+}
+
+impl UserPricing {
+    /// Default location, `~/.config/ccstat/pricing.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".config").join("ccstat").join("pricing.toml"))
+    }
+
+    /// Read the file at the default location, if it exists
+    pub fn load_default() -> Result<Option<Self>> {
+        match Self::default_path() {
+            Some(path) if path.is_file() => Self::load(&path).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Read a pricing file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CcstatError::Config(format!(
+                "Cannot read pricing file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut user_pricing = Self::parse(&content).map_err(|e| {
+            CcstatError::Config(format!("Invalid pricing file {}: {}", path.display(), e))
+        })?;
+        user_pricing.path = Some(path.to_path_buf());
+        Ok(user_pricing)
+    }
+
+    /// Parse pricing file contents
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut user_pricing: Self =
+            toml::from_str(content).map_err(|e| CcstatError::Config(e.to_string()))?;
+        user_pricing.exchange_rates = user_pricing
+            .exchange_rates
+            .map(ExchangeRates::validated)
+            .transpose()?;
+        if let Some(rule) = user_pricing
+            .discounts
+            .iter()
+            .find(|rule| !(0.0..=100.0).contains(&rule.percent))
+        {
+            return Err(CcstatError::Config(format!(
+                "discount for '{}' must be between 0 and 100 percent, got {}",
+                rule.models, rule.percent
+            )));
+        }
+        Ok(user_pricing)
+    }
+
+    /// File the pricing was read from
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Rates defined for exactly `model`
+    pub fn model(&self, model: &str) -> Option<&ModelPricing> {
+        self.models.get(model)
+    }
+
+    /// Model that `model` is priced like, if it is an alias
+    pub fn resolve_alias(&self, model: &str) -> Option<&str> {
+        self.aliases.get(model).map(String::as_str)
+    }
+
+    /// Earlier rates defined for exactly `model`
+    pub fn history(&self, model: &str) -> Option<&PricingHistory> {
+        self.history.get(model)
+    }
+
+    /// Exchange rates for displaying costs in other currencies, if any
+    pub fn exchange_rates(&self) -> Option<&ExchangeRates> {
+        self.exchange_rates.as_ref()
+    }
+
+    /// Discount in percent of the first rule matching `model`
+    pub fn discount(&self, model: &str) -> Option<f64> {
+        self.discounts
+            .iter()
+            .find(|rule| rule.matches(model))
+            .map(|rule| rule.percent)
+    }
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: the whole text must match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_models_aliases_and_discounts() {
+        let user_pricing = UserPricing::parse(
+            r#"
+            [models."gateway/claude-sonnet"]
+            input_cost_per_token = 0.000003
+            output_cost_per_token = 0.000015
+            cache_read_input_token_cost = 0.0000003
+
+            [aliases]
+            "claude-sonnet-4-5@20250929" = "claude-sonnet-4-5-20250929"
+
+            [[discounts]]
+            models = "claude-opus-*"
+            percent = 25
+
+            [[discounts]]
+            models = "*"
+            percent = 10
+            "#,
+        )
+        .unwrap();
+
+        let pricing = user_pricing.model("gateway/claude-sonnet").unwrap();
+        assert_eq!(pricing.input_cost_per_token, Some(0.000003));
+        assert_eq!(pricing.cache_read_input_token_cost, Some(0.0000003));
+        assert!(pricing.cache_creation_input_token_cost.is_none());
+        assert!(user_pricing.model("claude-sonnet").is_none());
+
+        assert_eq!(
+            user_pricing.resolve_alias("claude-sonnet-4-5@20250929"),
+            Some("claude-sonnet-4-5-20250929")
+        );
+        assert_eq!(user_pricing.resolve_alias("claude-sonnet-4-5"), None);
+
+        // The first matching rule wins
+        assert_eq!(user_pricing.discount("claude-opus-4-1"), Some(25.0));
+        assert_eq!(user_pricing.discount("claude-sonnet-4-5"), Some(10.0));
+    }
+
+    #[test]
+    fn test_parse_history() {
+        let user_pricing = UserPricing::parse(
+            r#"
+            [history."claude-2"]
+            effective_from = "2023-11-21"
+
+            [[history."claude-2".rate_periods]]
+            effective_from = "2023-07-11"
+            input_cost_per_token = 0.00001102
+            output_cost_per_token = 0.00003268
+            "#,
+        )
+        .unwrap();
+
+        let history = user_pricing.history("claude-2").unwrap();
+        assert_eq!(
+            history.effective_from,
+            NaiveDate::from_ymd_opt(2023, 11, 21).unwrap()
+        );
+        assert_eq!(history.rate_periods.len(), 1);
+        assert_eq!(
+            history.rate_periods[0].rates.output_cost_per_token,
+            Some(0.00003268)
+        );
+        assert!(user_pricing.history("claude-2.1").is_none());
+
+        // Periods on the same date are replaced, others kept in date order
+        let period = |month, input| RatePeriod {
+            effective_from: NaiveDate::from_ymd_opt(2023, month, 11).unwrap(),
+            rates: ModelPricing {
+                input_cost_per_token: Some(input),
+                ..Default::default()
+            },
+        };
+        let mut pricing = ModelPricing {
+            effective_from: NaiveDate::from_ymd_opt(2023, 10, 1),
+            rate_periods: vec![period(7, 0.1), period(8, 0.2)],
+            ..Default::default()
+        };
+        history.apply(&mut pricing);
+        assert_eq!(pricing.effective_from, Some(history.effective_from));
+        assert_eq!(
+            pricing.rate_periods,
+            [history.rate_periods[0].clone(), period(8, 0.2)]
+        );
+
+        assert!(UserPricing::parse("[history.\"claude-2\"]\nrate_periods = []").is_err());
+    }
+
+    #[test]
+    fn test_parse_exchange_rates() {
+        let user_pricing = UserPricing::parse(
+            r#"
+            [exchange_rates]
+            as_of = "2025-06-02"
+            rates = { EUR = 0.88, TWD = 29.9 }
+            "#,
+        )
+        .unwrap();
+        let rates = user_pricing.exchange_rates().unwrap();
+        assert_eq!(rates.rate("TWD"), Some(29.9));
+        assert!(UserPricing::parse("").unwrap().exchange_rates().is_none());
+        assert!(UserPricing::parse("[exchange_rates]\nrates = { EUR = 0 }").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_files() {
+        assert!(UserPricing::parse("[unknown]\nkey = 1").is_err());
+        assert!(UserPricing::parse("[[discounts]]\nmodels = \"*\"\npercent = 120").is_err());
+        assert!(UserPricing::parse("[[discounts]]\npercent = 10").is_err());
+        assert!(UserPricing::parse("").unwrap().discount("any").is_none());
+    }
+
+    #[test]
+    fn test_load_records_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pricing.toml");
+        std::fs::write(&path, "[aliases]\n\"a\" = \"b\"\n").unwrap();
+
+        let user_pricing = UserPricing::load(&path).unwrap();
+        assert_eq!(user_pricing.path(), Some(path.as_path()));
+
+        let err = UserPricing::load(&dir.path().join("missing.toml")).unwrap_err();
+        assert!(err.to_string().contains("missing.toml"));
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("claude-*", "claude-3-opus"));
+        assert!(glob_matches("*opus*", "claude-3-opus-20240229"));
+        assert!(glob_matches("claude-*-opus", "claude-3-opus"));
+        assert!(glob_matches("exact", "exact"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("exact", "exactly"));
+        assert!(!glob_matches("claude-*-opus", "claude-3-sonnet"));
+        assert!(!glob_matches("a*a", "a"));
+    }
+}
@@ -2,12 +2,13 @@
 //!
 //! Discovers and parses Amp thread JSON files from
 //! `~/.local/share/amp/threads/`. Extracts tokens from usageLedger
-//! events with cache breakdown from messages.
+//! events with cache breakdown from messages, and attributes them to the
+//! first workspace tree recorded in the thread environment.
 
 use async_trait::async_trait;
 use ccstat_core::error::{CcstatError, Result};
 use ccstat_core::provider::ProviderDataLoader;
-use ccstat_core::types::{ISOTimestamp, ModelName, SessionId, TokenCounts, UsageEntry};
+use ccstat_core::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry};
 use chrono::DateTime;
 use futures::stream::Stream;
 use serde::Deserialize;
@@ -18,12 +19,12 @@ use tracing::{debug, warn};
 
 /// Data loader for Amp usage data.
 pub struct DataLoader {
-    threads_dir: PathBuf,
+    threads_dirs: Vec<PathBuf>,
 }
 
 #[async_trait]
 impl ProviderDataLoader for DataLoader {
-    async fn new() -> Result<Self> {
+    fn default_roots() -> Result<Vec<PathBuf>> {
         let base = if let Ok(data_dir) = std::env::var("AMP_DATA_DIR") {
             PathBuf::from(data_dir)
         } else {
@@ -31,38 +32,43 @@ impl ProviderDataLoader for DataLoader {
                 .ok_or_else(|| CcstatError::Config("Cannot determine data directory".into()))?
                 .join("amp")
         };
+        Ok(vec![base])
+    }
 
-        let threads_dir = base.join("threads");
-        if !threads_dir.exists() {
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        let threads_dirs: Vec<_> = roots.iter().map(|root| root.join("threads")).collect();
+        for threads_dir in threads_dirs.iter().filter(|dir| !dir.exists()) {
             debug!("Amp threads directory not found: {}", threads_dir.display());
         }
 
-        Ok(DataLoader { threads_dir })
+        DataLoader { threads_dirs }
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.threads_dirs
     }
 
     fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
         Box::pin(async_stream::try_stream! {
-            if !self.threads_dir.exists() {
-                return;
-            }
-
             let mut json_files = Vec::new();
-            for entry in walkdir::WalkDir::new(&self.threads_dir)
-                .min_depth(1)
-                .max_depth(1)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                let path = entry.path().to_path_buf();
-                let is_thread_file = path
-                    .extension()
-                    .is_some_and(|ext| ext == "json")
-                    && path
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .is_some_and(|name| name.starts_with("T-"));
-                if is_thread_file {
-                    json_files.push(path);
+            for threads_dir in self.threads_dirs.iter().filter(|dir| dir.exists()) {
+                for entry in walkdir::WalkDir::new(threads_dir)
+                    .min_depth(1)
+                    .max_depth(1)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                {
+                    let path = entry.path().to_path_buf();
+                    let is_thread_file = path
+                        .extension()
+                        .is_some_and(|ext| ext == "json")
+                        && path
+                            .file_stem()
+                            .and_then(|s| s.to_str())
+                            .is_some_and(|name| name.starts_with("T-"));
+                    if is_thread_file {
+                        json_files.push(path);
+                    }
                 }
             }
 
@@ -105,6 +111,43 @@ struct AmpThread {
     messages: Vec<AmpMessage>,
     #[serde(rename = "usageLedger")]
     usage_ledger: Option<UsageLedger>,
+    #[serde(default)]
+    env: Option<ThreadEnv>,
+}
+
+#[derive(Deserialize)]
+struct ThreadEnv {
+    #[serde(default)]
+    initial: Option<InitialEnv>,
+}
+
+#[derive(Deserialize)]
+struct InitialEnv {
+    #[serde(default)]
+    trees: Vec<WorkspaceTree>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceTree {
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    uri: Option<String>,
+}
+
+impl AmpThread {
+    /// Project name from the thread's first workspace tree
+    fn project(&self) -> Option<String> {
+        let tree = self.env.as_ref()?.initial.as_ref()?.trees.first()?;
+        tree.display_name
+            .clone()
+            .filter(|name| !name.is_empty())
+            .or_else(|| {
+                let uri = tree.uri.as_deref()?;
+                UsageEntry::project_from_cwd(uri.strip_prefix("file://").unwrap_or(uri))
+            })
+    }
 }
 
 #[derive(Deserialize)]
@@ -162,6 +205,7 @@ fn extract_entries_from_thread(thread: &AmpThread) -> Vec<UsageEntry> {
         .filter_map(|m| m.usage.as_ref().map(|u| (m.id.as_str(), u)))
         .collect();
 
+    let project = thread.project();
     let mut entries = Vec::new();
 
     for event in &ledger.events {
@@ -205,8 +249,9 @@ fn extract_entries_from_thread(thread: &AmpThread) -> Vec<UsageEntry> {
                 cache_read,
             ),
             total_cost: event.credits,
-            project: None,
+            project: project.clone(),
             instance_id: None,
+            provider: ProviderId::new("amp"),
         });
     }
 
@@ -288,11 +333,14 @@ mod tests {
         )
         .unwrap();
 
-        let loader = DataLoader { threads_dir };
+        let loader = DataLoader {
+            threads_dirs: vec![threads_dir],
+        };
         let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries()).await;
         assert_eq!(entries.len(), 1);
         let entry = entries[0].as_ref().unwrap();
         assert_eq!(entry.tokens.input_tokens, 500);
+        assert_eq!(entry.provider.as_str(), "amp");
         assert_eq!(entry.tokens.output_tokens, 200);
         assert_eq!(entry.tokens.cache_creation_tokens, 50);
         assert_eq!(entry.tokens.cache_read_tokens, 100);
@@ -306,6 +354,7 @@ mod tests {
             id: "T-1".to_string(),
             messages: vec![],
             usage_ledger: None,
+            env: None,
         };
         let entries = extract_entries_from_thread(&thread);
         assert!(entries.is_empty());
@@ -336,10 +385,43 @@ mod tests {
         assert_eq!(entries[0].tokens.cache_read_tokens, 0);
     }
 
+    #[tokio::test]
+    async fn test_project_from_env_trees() {
+        let ledger = r#""usageLedger": {"events": [{
+            "model": "claude-sonnet-4",
+            "inputTokens": 100,
+            "outputTokens": 50,
+            "createdAt": "2025-01-01T10:00:00Z"
+        }]}"#;
+
+        let named = format!(
+            r#"{{"id": "T-3", "env": {{"initial": {{"trees": [
+                {{"displayName": "web-app", "uri": "file:///home/me/code/web-app"}}
+            ]}}}}, {ledger}}}"#
+        );
+        let thread: AmpThread = serde_json::from_str(&named).unwrap();
+        let entries = extract_entries_from_thread(&thread);
+        assert_eq!(entries[0].project.as_deref(), Some("web-app"));
+
+        // Fall back to the tree URI when there is no display name
+        let unnamed = format!(
+            r#"{{"id": "T-4", "env": {{"initial": {{"trees": [
+                {{"uri": "file:///home/me/code/api-server"}}
+            ]}}}}, {ledger}}}"#
+        );
+        let thread: AmpThread = serde_json::from_str(&unnamed).unwrap();
+        let entries = extract_entries_from_thread(&thread);
+        assert_eq!(entries[0].project.as_deref(), Some("api-server"));
+
+        let no_env = format!(r#"{{"id": "T-5", {ledger}}}"#);
+        let thread: AmpThread = serde_json::from_str(&no_env).unwrap();
+        assert_eq!(extract_entries_from_thread(&thread)[0].project, None);
+    }
+
     #[tokio::test]
     async fn test_no_dir() {
         let loader = DataLoader {
-            threads_dir: PathBuf::from("/tmp/nonexistent-amp-dir"),
+            threads_dirs: vec![PathBuf::from("/tmp/nonexistent-amp-dir")],
         };
         let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries()).await;
         assert!(entries.is_empty());
@@ -3,6 +3,13 @@
 //! This crate implements the provider trait for Amp,
 //! handling thread-based JSON and usageLedger events.
 
+use ccstat_core::registry::ProviderInfo;
+
 pub mod data_loader;
 
 pub use data_loader::DataLoader;
+
+/// Registry entry for the Amp provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("amp", "Amp usage data").with_env_vars(&["AMP_DATA_DIR"])
+}
@@ -32,8 +32,10 @@
 //! # }
 //! ```
 
+use async_trait::async_trait;
 use ccstat_core::error::{CcstatError, Result};
 use ccstat_core::memory_pool::MemoryPool;
+use ccstat_core::provider::ProviderDataLoader;
 use ccstat_core::string_pool::{InternedModel, InternedSession};
 use ccstat_core::types::{ModelName, RawJsonlEntry, SessionId, UsageEntry};
 use futures::StreamExt;
@@ -42,6 +44,7 @@ use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use std::collections::HashSet;
 use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::sync::mpsc;
@@ -72,22 +75,30 @@ impl DataLoader {
     ///
     /// Returns an error if no Claude data directories are found
     pub async fn new() -> Result<Self> {
-        let paths = Self::discover_claude_paths().await?;
+        let paths = Self::discover_claude_paths()?;
         if paths.is_empty() {
             return Err(CcstatError::NoClaudeDirectory);
         }
 
         debug!("Discovered {} Claude data directories", paths.len());
-        Ok(Self {
-            claude_paths: paths,
+        Ok(Self::from_roots(paths))
+    }
+
+    /// Create a DataLoader reading the given Claude data directories
+    ///
+    /// Each root is a directory like `~/.claude`; JSONL files anywhere
+    /// below it are read.
+    pub fn from_roots(roots: Vec<PathBuf>) -> Self {
+        Self {
+            claude_paths: roots,
             show_progress: false,
             use_interning: false,
             use_arena: false,
-        })
+        }
     }
 
     /// Discover Claude data directories on the system
-    async fn discover_claude_paths() -> Result<Vec<PathBuf>> {
+    fn discover_claude_paths() -> Result<Vec<PathBuf>> {
         let mut paths = Vec::new();
 
         // Check ~/.claude first (common location for Claude Code)
@@ -623,6 +634,32 @@ impl DataLoader {
     }
 }
 
+#[async_trait]
+impl ProviderDataLoader for DataLoader {
+    /// Claude's usage limits reset on a rolling five-hour window.
+    const DEFAULT_BLOCK_HOURS: f64 = 5.0;
+
+    fn default_roots() -> Result<Vec<PathBuf>> {
+        DataLoader::discover_claude_paths()
+    }
+
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        DataLoader::from_roots(roots)
+    }
+
+    async fn new() -> Result<Self> {
+        DataLoader::new().await
+    }
+
+    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+        Box::pin(self.load_usage_entries_parallel())
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.claude_paths
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -711,7 +748,7 @@ mod tests {
         let mut env_guard = EnvVarGuard::new();
         env_guard.set("CLAUDE_DATA_PATH", custom_path.to_str().unwrap());
 
-        let paths = DataLoader::discover_claude_paths().await.unwrap();
+        let paths = DataLoader::discover_claude_paths().unwrap();
         assert!(paths.contains(&custom_path));
 
         // Environment variables will be automatically restored when env_guard drops
@@ -3,9 +3,26 @@
 //! This crate implements the provider trait for Claude Code,
 //! handling JSONL file discovery, parsing, and usage entry extraction.
 
+use ccstat_core::registry::{ProviderInfo, ReportType};
+
 pub mod data_loader;
 
 #[cfg(test)]
 pub mod test_utils;
 
 pub use data_loader::DataLoader;
+
+/// Registry entry for the Claude Code provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("claude", "Claude Code usage data")
+        .with_aliases(&["claude-code"])
+        .with_env_vars(&["CLAUDE_DATA_PATH"])
+        .with_reports(&[
+            ReportType::Daily,
+            ReportType::Monthly,
+            ReportType::Weekly,
+            ReportType::Session,
+            ReportType::Blocks,
+            ReportType::Statusline,
+        ])
+}
@@ -0,0 +1,47 @@
+//! Cline data loader
+//!
+//! Reads Cline task histories from VS Code's global storage,
+//! `<config dir>/Code/User/globalStorage/saoudrizwan.claude-dev/tasks/`.
+//! Other VS Code-based editors (Insiders, VSCodium, Cursor, Windsurf) are
+//! searched too.
+
+use crate::tasks;
+use async_trait::async_trait;
+use ccstat_core::error::Result;
+use ccstat_core::provider::ProviderDataLoader;
+use ccstat_core::types::UsageEntry;
+use futures::stream::Stream;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+/// Marketplace id of the Cline extension
+const EXTENSION_ID: &str = "saoudrizwan.claude-dev";
+
+/// Data loader for Cline usage data.
+pub struct DataLoader {
+    storage_dirs: Vec<PathBuf>,
+}
+
+#[async_trait]
+impl ProviderDataLoader for DataLoader {
+    fn default_roots() -> Result<Vec<PathBuf>> {
+        tasks::storage_dirs(
+            std::env::var_os("CLINE_STORAGE_DIR").map(PathBuf::from),
+            EXTENSION_ID,
+        )
+    }
+
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        DataLoader {
+            storage_dirs: roots,
+        }
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.storage_dirs
+    }
+
+    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+        tasks::load_tasks(&self.storage_dirs, "cline")
+    }
+}
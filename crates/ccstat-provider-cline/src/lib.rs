@@ -0,0 +1,18 @@
+//! Cline provider for ccstat
+//!
+//! This crate implements the provider trait for the Cline VS Code
+//! extension, handling per-task history parsing. The task parsing in
+//! [`tasks`] is shared with Roo Code, which keeps the same task layout.
+
+use ccstat_core::registry::ProviderInfo;
+
+pub mod data_loader;
+pub mod tasks;
+
+pub use data_loader::DataLoader;
+
+/// Registry entry for the Cline provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("cline", "Cline (VS Code extension) usage data")
+        .with_env_vars(&["CLINE_STORAGE_DIR"])
+}
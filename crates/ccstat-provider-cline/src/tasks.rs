@@ -0,0 +1,473 @@
+//! Task history parsing shared by Cline and Roo Code
+//!
+//! Both extensions keep one directory per task under
+//! `<globalStorage>/<extension id>/tasks/<task id>/`:
+//!
+//! - `ui_messages.json` holds an `api_req_started` message per API request,
+//!   whose `text` is a JSON document with token counts and the cost the
+//!   extension calculated
+//! - `api_conversation_history.json` holds the raw conversation, including
+//!   the environment details that name the workspace directory
+//! - `task_metadata.json` (Cline) records which model was in use when
+//!
+//! Each task id becomes the entry's session id, and the workspace folder
+//! its project.
+
+use ccstat_core::error::{CcstatError, Result};
+use ccstat_core::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry};
+use chrono::{TimeZone, Utc};
+use futures::stream::Stream;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use tracing::{debug, warn};
+
+/// VS Code-based editors whose global storage is searched, relative to the
+/// platform config directory
+const EDITORS: &[&str] = &["Code", "Code - Insiders", "VSCodium", "Cursor", "Windsurf"];
+
+/// Locate an extension's global storage directories
+///
+/// `override_dir`, usually from the provider's environment variable,
+/// replaces the search with a single directory. Otherwise every editor that
+/// has storage for `extension_id` is returned, falling back to VS Code's
+/// location when none do.
+pub fn storage_dirs(override_dir: Option<PathBuf>, extension_id: &str) -> Result<Vec<PathBuf>> {
+    if let Some(dir) = override_dir {
+        return Ok(vec![dir]);
+    }
+
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| CcstatError::Config("Cannot determine config directory".into()))?;
+    let candidates: Vec<PathBuf> = EDITORS
+        .iter()
+        .map(|editor| {
+            config_dir
+                .join(editor)
+                .join("User")
+                .join("globalStorage")
+                .join(extension_id)
+        })
+        .collect();
+
+    let existing: Vec<PathBuf> = candidates.iter().filter(|p| p.exists()).cloned().collect();
+    if existing.is_empty() {
+        debug!("No global storage found for {}", extension_id);
+        Ok(candidates.into_iter().take(1).collect())
+    } else {
+        Ok(existing)
+    }
+}
+
+/// Stream usage entries from every task under the given storage roots
+pub fn load_tasks<'a>(
+    roots: &'a [PathBuf],
+    provider: &'static str,
+) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + 'a>> {
+    Box::pin(async_stream::try_stream! {
+        let mut task_dirs = Vec::new();
+        for root in roots {
+            let tasks = root.join("tasks");
+            if !tasks.exists() {
+                continue;
+            }
+            for entry in walkdir::WalkDir::new(&tasks)
+                .min_depth(1)
+                .max_depth(1)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(|e| e.ok())
+            {
+                if entry.file_type().is_dir() {
+                    task_dirs.push(entry.into_path());
+                }
+            }
+        }
+
+        debug!("Found {} {} task directories", task_dirs.len(), provider);
+
+        for task_dir in task_dirs {
+            match parse_task(&task_dir, provider).await {
+                Ok(entries) => {
+                    for entry in entries {
+                        yield entry;
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to parse {} task {}: {}", provider, task_dir.display(), e);
+                }
+            }
+        }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Task file schemas
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct UiMessage {
+    #[serde(default)]
+    ts: Option<i64>,
+    #[serde(default)]
+    say: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+/// Payload of `api_req_started` and `deleted_api_reqs` messages
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiRequestInfo {
+    #[serde(default)]
+    tokens_in: Option<u64>,
+    #[serde(default)]
+    tokens_out: Option<u64>,
+    #[serde(default)]
+    cache_writes: Option<u64>,
+    #[serde(default)]
+    cache_reads: Option<u64>,
+    #[serde(default)]
+    cost: Option<f64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiMessage {
+    #[serde(default)]
+    role: Option<String>,
+    #[serde(default)]
+    content: serde_json::Value,
+    #[serde(default)]
+    model_info: Option<ModelInfo>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelInfo {
+    #[serde(default)]
+    model_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TaskMetadata {
+    #[serde(default)]
+    model_usage: Vec<ModelUsage>,
+}
+
+#[derive(Deserialize)]
+struct ModelUsage {
+    #[serde(default)]
+    ts: i64,
+    #[serde(default)]
+    model_id: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+async fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = tokio::fs::read_to_string(path).await?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| CcstatError::Parse {
+            file: path.to_path_buf(),
+            error: e.to_string(),
+        })
+}
+
+/// Parse one task directory into usage entries
+pub async fn parse_task(task_dir: &Path, provider: &'static str) -> Result<Vec<UsageEntry>> {
+    let Some(messages) = read_json::<Vec<UiMessage>>(&task_dir.join("ui_messages.json")).await?
+    else {
+        return Ok(Vec::new());
+    };
+
+    let history: Vec<ApiMessage> = read_json(&task_dir.join("api_conversation_history.json"))
+        .await
+        .unwrap_or_else(|e| {
+            debug!("Ignoring conversation history: {}", e);
+            None
+        })
+        .unwrap_or_default();
+    let metadata: Option<TaskMetadata> = read_json(&task_dir.join("task_metadata.json"))
+        .await
+        .unwrap_or_else(|e| {
+            debug!("Ignoring task metadata: {}", e);
+            None
+        });
+
+    let task_id = task_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+    let project = workspace_from_history(&history)
+        .as_deref()
+        .and_then(UsageEntry::project_from_cwd);
+    let fallback_model = history
+        .iter()
+        .rev()
+        .filter(|m| m.role.as_deref() == Some("assistant"))
+        .find_map(|m| m.model_info.as_ref()?.model_id.clone());
+
+    let mut entries = Vec::new();
+
+    for message in &messages {
+        if !matches!(
+            message.say.as_deref(),
+            Some("api_req_started" | "deleted_api_reqs")
+        ) {
+            continue;
+        }
+        let Some(info) = message
+            .text
+            .as_deref()
+            .and_then(|t| serde_json::from_str::<ApiRequestInfo>(t).ok())
+        else {
+            continue;
+        };
+
+        let tokens = TokenCounts::new(
+            info.tokens_in.unwrap_or(0),
+            info.tokens_out.unwrap_or(0),
+            info.cache_writes.unwrap_or(0),
+            info.cache_reads.unwrap_or(0),
+        );
+        // Requests still streaming or cancelled before a response
+        if tokens.total() == 0 {
+            continue;
+        }
+
+        let Some(ts) = message.ts else {
+            continue;
+        };
+        let Some(timestamp) = Utc.timestamp_millis_opt(ts).single() else {
+            continue;
+        };
+
+        let model = metadata
+            .as_ref()
+            .and_then(|m| model_at(&m.model_usage, ts))
+            .or_else(|| fallback_model.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        entries.push(UsageEntry {
+            session_id: SessionId::new(task_id.clone()),
+            timestamp: ISOTimestamp::new(timestamp),
+            model: ModelName::new(model),
+            tokens,
+            total_cost: info.cost,
+            project: project.clone(),
+            instance_id: None,
+            provider: ProviderId::new(provider),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Model in use at `ts`: the latest switch at or before it, else the first
+fn model_at(usage: &[ModelUsage], ts: i64) -> Option<String> {
+    usage
+        .iter()
+        .filter(|u| u.ts <= ts)
+        .max_by_key(|u| u.ts)
+        .or_else(|| usage.iter().min_by_key(|u| u.ts))
+        .and_then(|u| u.model_id.clone())
+}
+
+/// Workspace directory named in the environment details of a user message
+///
+/// Cline writes `# Current Working Directory (<path>) Files`; Roo Code
+/// writes `# Current Workspace Directory (<path>) Files`.
+fn workspace_from_history(history: &[ApiMessage]) -> Option<String> {
+    const MARKERS: [&str; 2] = [
+        "# Current Working Directory (",
+        "# Current Workspace Directory (",
+    ];
+
+    history
+        .iter()
+        .filter(|m| m.role.as_deref() == Some("user"))
+        .flat_map(|m| text_blocks(&m.content))
+        .find_map(|text| {
+            MARKERS.iter().find_map(|marker| {
+                let rest = &text[text.find(marker)? + marker.len()..];
+                let end = rest.find(") Files").or_else(|| rest.find(')'))?;
+                Some(rest[..end].to_string())
+            })
+        })
+}
+
+/// Text of a message whose content is a string or an array of blocks
+fn text_blocks(content: &serde_json::Value) -> Vec<&str> {
+    match content {
+        serde_json::Value::String(s) => vec![s.as_str()],
+        serde_json::Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// `api_req_started` message as the extensions serialize it
+    fn api_req(ts: i64, tokens_in: u64, tokens_out: u64, cost: f64) -> serde_json::Value {
+        let info = json!({
+            "request": "<task>...</task>",
+            "tokensIn": tokens_in,
+            "tokensOut": tokens_out,
+            "cacheWrites": 100,
+            "cacheReads": 200,
+            "cost": cost
+        });
+        json!({"ts": ts, "type": "say", "say": "api_req_started", "text": info.to_string()})
+    }
+
+    fn write_task(
+        root: &Path,
+        id: &str,
+        ui: serde_json::Value,
+        history: serde_json::Value,
+    ) -> PathBuf {
+        let dir = root.join("tasks").join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ui_messages.json"), ui.to_string()).unwrap();
+        std::fs::write(
+            dir.join("api_conversation_history.json"),
+            history.to_string(),
+        )
+        .unwrap();
+        dir
+    }
+
+    fn history(marker: &str) -> serde_json::Value {
+        json!([
+            {"role": "user", "content": [
+                {"type": "text", "text": "<task>fix the bug</task>"},
+                {"type": "text", "text": format!("<environment_details>\n{marker} (/home/me/code/web-app) Files\nsrc/\n</environment_details>")}
+            ]},
+            {"role": "assistant", "content": [{"type": "text", "text": "ok"}],
+             "modelInfo": {"modelId": "claude-sonnet-4-20250514", "providerId": "anthropic"}}
+        ])
+    }
+
+    #[tokio::test]
+    async fn test_parse_task() {
+        let dir = TempDir::new().unwrap();
+        let task_dir = write_task(
+            dir.path(),
+            "1735725600000",
+            json!([
+                {"ts": 1735725600000i64, "type": "say", "say": "task", "text": "fix the bug"},
+                api_req(1735725601000, 1500, 300, 0.0123),
+                // Still streaming: no token counts yet
+                {"ts": 1735725602000i64, "type": "say", "say": "api_req_started", "text": "{\"request\":\"...\"}"}
+            ]),
+            history("# Current Working Directory"),
+        );
+
+        let entries = parse_task(&task_dir, "cline").await.unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.session_id.as_str(), "1735725600000");
+        assert_eq!(entry.project.as_deref(), Some("web-app"));
+        assert_eq!(entry.model.as_str(), "claude-sonnet-4-20250514");
+        assert_eq!(entry.tokens.input_tokens, 1500);
+        assert_eq!(entry.tokens.output_tokens, 300);
+        assert_eq!(entry.tokens.cache_creation_tokens, 100);
+        assert_eq!(entry.tokens.cache_read_tokens, 200);
+        assert_eq!(entry.total_cost, Some(0.0123));
+        assert_eq!(entry.provider.as_str(), "cline");
+    }
+
+    #[tokio::test]
+    async fn test_model_from_task_metadata() {
+        let dir = TempDir::new().unwrap();
+        let task_dir = write_task(
+            dir.path(),
+            "task-1",
+            json!([api_req(2_000, 10, 5, 0.01), api_req(4_000, 10, 5, 0.01)]),
+            json!([]),
+        );
+        std::fs::write(
+            task_dir.join("task_metadata.json"),
+            json!({"files_in_context": [], "model_usage": [
+                {"ts": 1_000, "model_id": "claude-sonnet-4", "model_provider_id": "anthropic", "mode": "act"},
+                {"ts": 3_000, "model_id": "gpt-4.1", "model_provider_id": "openai", "mode": "plan"}
+            ]})
+            .to_string(),
+        )
+        .unwrap();
+
+        let entries = parse_task(&task_dir, "cline").await.unwrap();
+        assert_eq!(entries[0].model.as_str(), "claude-sonnet-4");
+        assert_eq!(entries[1].model.as_str(), "gpt-4.1");
+        // No environment details: no project
+        assert_eq!(entries[0].project, None);
+    }
+
+    #[tokio::test]
+    async fn test_roo_workspace_marker() {
+        let history: Vec<ApiMessage> =
+            serde_json::from_value(history("# Current Workspace Directory")).unwrap();
+        assert_eq!(
+            workspace_from_history(&history).as_deref(),
+            Some("/home/me/code/web-app")
+        );
+    }
+
+    #[test]
+    fn test_storage_dir_override() {
+        let dirs = storage_dirs(
+            Some(PathBuf::from("/tmp/task-storage")),
+            "example.extension",
+        )
+        .unwrap();
+        assert_eq!(dirs, [PathBuf::from("/tmp/task-storage")]);
+    }
+
+    #[tokio::test]
+    async fn test_load_tasks_across_roots() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        write_task(
+            first.path(),
+            "a",
+            json!([api_req(1_000, 10, 5, 0.01)]),
+            json!([]),
+        );
+        write_task(
+            second.path(),
+            "b",
+            json!([api_req(1_000, 10, 5, 0.01)]),
+            json!([]),
+        );
+        // A task without ui_messages.json is skipped quietly
+        std::fs::create_dir_all(second.path().join("tasks").join("empty")).unwrap();
+
+        let roots = vec![
+            first.path().to_path_buf(),
+            second.path().to_path_buf(),
+            PathBuf::from("/tmp/nonexistent-cline-storage"),
+        ];
+        let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(load_tasks(&roots, "cline"))
+            .await
+            .into_iter()
+            .map(|e| e.unwrap())
+            .collect();
+        let ids: Vec<_> = entries.iter().map(|e| e.session_id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+    }
+}
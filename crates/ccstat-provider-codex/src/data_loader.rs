@@ -1,17 +1,25 @@
 //! Codex data loader
 //!
-//! Discovers and parses Codex session JSONL files from `~/.codex/sessions/`.
+//! Discovers and parses Codex session JSONL files under `~/.codex/sessions/`,
+//! both the legacy flat layout and the date-partitioned
+//! `YYYY/MM/DD/rollout-*.jsonl` layout. Session ids come from the
+//! `session_meta` event, falling back to the file stem.
 //! Codex uses cumulative token counts that must be converted to per-event deltas.
+//! `token_count` events also carry rate-limit snapshots, exposed through
+//! [`DataLoader::load_rate_limits`].
 
 use async_trait::async_trait;
 use ccstat_core::error::{CcstatError, Result};
 use ccstat_core::provider::ProviderDataLoader;
-use ccstat_core::types::{ISOTimestamp, ModelName, SessionId, TokenCounts, UsageEntry};
-use chrono::DateTime;
+use ccstat_core::types::{
+    ISOTimestamp, ModelName, ProviderId, RateLimitSnapshot, RateLimitWindow, SessionId,
+    TokenCounts, UsageEntry,
+};
+use chrono::{DateTime, Days, Months, NaiveDate, Utc};
 use futures::stream::Stream;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tracing::{debug, warn};
@@ -21,12 +29,19 @@ const FALLBACK_MODEL: &str = "gpt-5";
 
 /// Data loader for Codex usage data.
 pub struct DataLoader {
-    session_dir: PathBuf,
+    session_dirs: Vec<PathBuf>,
+    /// Earliest date a session file must have been modified on to be read
+    since: Option<NaiveDate>,
+    /// Latest date whose partition directories are read
+    until: Option<NaiveDate>,
 }
 
 #[async_trait]
 impl ProviderDataLoader for DataLoader {
-    async fn new() -> Result<Self> {
+    /// Codex enforces its primary rate limit over a rolling five-hour window.
+    const DEFAULT_BLOCK_HOURS: f64 = 5.0;
+
+    fn default_roots() -> Result<Vec<PathBuf>> {
         let base = if let Ok(home) = std::env::var("CODEX_HOME") {
             PathBuf::from(home)
         } else {
@@ -34,50 +49,47 @@ impl ProviderDataLoader for DataLoader {
                 .ok_or_else(|| CcstatError::Config("Cannot determine home directory".into()))?
                 .join(".codex")
         };
+        Ok(vec![base])
+    }
 
-        let session_dir = base.join("sessions");
-        if !session_dir.exists() {
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        let session_dirs: Vec<_> = roots.iter().map(|root| root.join("sessions")).collect();
+        for session_dir in session_dirs.iter().filter(|dir| !dir.exists()) {
             debug!(
                 "Codex sessions directory not found: {}",
                 session_dir.display()
             );
         }
 
-        Ok(DataLoader { session_dir })
+        DataLoader {
+            session_dirs,
+            since: None,
+            until: None,
+        }
     }
 
-    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
-        Box::pin(async_stream::try_stream! {
-            if !self.session_dir.exists() {
-                return;
-            }
+    fn paths(&self) -> &[PathBuf] {
+        &self.session_dirs
+    }
 
-            let mut jsonl_files = Vec::new();
-            for entry in walkdir::WalkDir::new(&self.session_dir)
-                .min_depth(1)
-                .max_depth(1)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                let path = entry.path().to_path_buf();
-                if path.extension().is_some_and(|ext| ext == "jsonl") {
-                    jsonl_files.push(path);
-                }
-            }
+    fn with_date_range(mut self, since: Option<NaiveDate>, until: Option<NaiveDate>) -> Self {
+        // Partition directories are named in Codex's local time, which may
+        // differ from the report timezone by up to a day either way
+        self.since = since.and_then(|d| d.checked_sub_days(Days::new(1)));
+        self.until = until.and_then(|d| d.checked_add_days(Days::new(1)));
+        self
+    }
 
+    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+        Box::pin(async_stream::try_stream! {
+            let jsonl_files = self.session_files();
             debug!("Found {} Codex session files", jsonl_files.len());
 
             for path in jsonl_files {
-                let session_id = path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("unknown")
-                    .to_string();
-
-                let entries = parse_session_file(&path, &session_id).await;
-                match entries {
+                let session_id = session_id_for(&path);
+                match parse_session_file(&path, &session_id).await {
                     Ok(parsed) => {
-                        for entry in parsed {
+                        for entry in parsed.entries {
                             yield entry;
                         }
                     }
@@ -90,6 +102,118 @@ impl ProviderDataLoader for DataLoader {
     }
 }
 
+impl DataLoader {
+    /// Load the most recent rate-limit snapshot across all sessions
+    ///
+    /// Returns `None` when no session has reported rate limits yet.
+    pub async fn load_rate_limits(&self) -> Result<Option<RateLimitSnapshot>> {
+        let mut latest: Option<RateLimitSnapshot> = None;
+        for path in self.session_files() {
+            let session_id = session_id_for(&path);
+            let snapshot = match parse_session_file(&path, &session_id).await {
+                Ok(parsed) => parsed.rate_limits,
+                Err(e) => {
+                    warn!("Failed to parse Codex session {}: {}", session_id, e);
+                    continue;
+                }
+            };
+            if let Some(snapshot) = snapshot
+                && latest
+                    .as_ref()
+                    .is_none_or(|l| snapshot.timestamp > l.timestamp)
+            {
+                latest = Some(snapshot);
+            }
+        }
+        Ok(latest)
+    }
+
+    /// Session JSONL files below the sessions directories
+    ///
+    /// Date partition directories after the configured range are skipped
+    /// without being read. Codex keeps appending a resumed session to the
+    /// file in the directory of the day it started, so older directories are
+    /// still walked, and only files last modified before the range are
+    /// skipped.
+    fn session_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for session_dir in self.session_dirs.iter().filter(|dir| dir.exists()) {
+            files.extend(
+                walkdir::WalkDir::new(session_dir)
+                    .min_depth(1)
+                    .sort_by_file_name()
+                    .into_iter()
+                    .filter_entry(|entry| {
+                        !entry.file_type().is_dir()
+                            || self.partition_in_range(session_dir, entry.path())
+                    })
+                    .filter_map(|e| e.ok())
+                    .map(|entry| entry.path().to_path_buf())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+                    .filter(|path| self.modified_in_range(path)),
+            );
+        }
+        files
+    }
+
+    /// Whether a directory may hold sessions started by the end of the
+    /// configured range
+    fn partition_in_range(&self, session_dir: &Path, dir: &Path) -> bool {
+        let Some((first, _)) = dir.strip_prefix(session_dir).ok().and_then(partition_dates) else {
+            // Not a date partition; always descend
+            return true;
+        };
+        self.until.is_none_or(|until| first <= until)
+    }
+
+    /// Whether a file was modified on or after the start of the configured
+    /// range, so it may hold usage within it
+    ///
+    /// Files whose modification time cannot be read are kept.
+    fn modified_in_range(&self, path: &Path) -> bool {
+        let Some(since) = self.since else {
+            return true;
+        };
+        match path.metadata().and_then(|metadata| metadata.modified()) {
+            Ok(modified) => DateTime::<Utc>::from(modified).date_naive() >= since,
+            Err(_) => true,
+        }
+    }
+}
+
+/// First and last day covered by a `YYYY`, `YYYY/MM`, or `YYYY/MM/DD` directory
+fn partition_dates(relative: &Path) -> Option<(NaiveDate, NaiveDate)> {
+    let parts = relative
+        .components()
+        .map(|c| c.as_os_str().to_str()?.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+
+    match parts[..] {
+        [year] => Some((
+            NaiveDate::from_ymd_opt(year as i32, 1, 1)?,
+            NaiveDate::from_ymd_opt(year as i32, 12, 31)?,
+        )),
+        [year, month] => {
+            let first = NaiveDate::from_ymd_opt(year as i32, month, 1)?;
+            let last = first.checked_add_months(Months::new(1))?.pred_opt()?;
+            Some((first, last))
+        }
+        [year, month, day] => {
+            let date = NaiveDate::from_ymd_opt(year as i32, month, day)?;
+            Some((date, date))
+        }
+        _ => None,
+    }
+}
+
+/// Fallback session id when a file has no `session_meta` event
+fn session_id_for(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("unknown")
+        .to_string()
+}
+
 // ---------------------------------------------------------------------------
 // JSONL event types
 // ---------------------------------------------------------------------------
@@ -102,6 +226,8 @@ struct CodexEvent {
     #[serde(default)]
     model_id: Option<String>,
     #[serde(default)]
+    cwd: Option<String>,
+    #[serde(default)]
     payload: Option<EventPayload>,
 }
 
@@ -111,6 +237,50 @@ struct EventPayload {
     payload_type: Option<String>,
     #[serde(default)]
     info: Option<TokenInfo>,
+    #[serde(default)]
+    rate_limits: Option<RateLimits>,
+    /// Session id, carried by `session_meta` events
+    #[serde(default)]
+    id: Option<String>,
+    /// Working directory, carried by `session_meta` and `turn_context` events
+    #[serde(default)]
+    cwd: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RateLimits {
+    primary: Option<RateLimitEvent>,
+    secondary: Option<RateLimitEvent>,
+}
+
+/// A rate-limit window as logged by Codex
+///
+/// Older Codex versions log the reset as seconds from the event, newer ones
+/// as a Unix timestamp.
+#[derive(Deserialize)]
+struct RateLimitEvent {
+    used_percent: f64,
+    #[serde(default)]
+    window_minutes: Option<u64>,
+    #[serde(default)]
+    resets_in_seconds: Option<i64>,
+    #[serde(default)]
+    resets_at: Option<i64>,
+}
+
+impl RateLimitEvent {
+    fn into_window(self, event_time: DateTime<Utc>) -> RateLimitWindow {
+        let resets_at = match (self.resets_at, self.resets_in_seconds) {
+            (Some(at), _) => DateTime::from_timestamp(at, 0),
+            (None, Some(secs)) => Some(event_time + chrono::Duration::seconds(secs)),
+            (None, None) => None,
+        };
+        RateLimitWindow {
+            used_percent: self.used_percent,
+            window_minutes: self.window_minutes,
+            resets_at,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -131,6 +301,9 @@ struct CumulativeTokens {
     cache_read_tokens: u64,
     #[serde(default)]
     output_tokens: u64,
+    /// Reasoning share of `output_tokens`
+    #[serde(default)]
+    reasoning_output_tokens: u64,
     #[serde(default)]
     total_tokens: u64,
 }
@@ -139,7 +312,18 @@ struct CumulativeTokens {
 // Session file parsing
 // ---------------------------------------------------------------------------
 
-async fn parse_session_file(path: &PathBuf, session_id: &str) -> Result<Vec<UsageEntry>> {
+/// Usage entries and the last rate-limit snapshot from one session file
+struct ParsedSession {
+    entries: Vec<UsageEntry>,
+    rate_limits: Option<RateLimitSnapshot>,
+}
+
+/// Parse one session file
+///
+/// `session_id` is used until the file's `session_meta` event names the session.
+/// Entries are attributed to the project of the most recent working directory
+/// (from `session_meta` or `turn_context`).
+async fn parse_session_file(path: &PathBuf, session_id: &str) -> Result<ParsedSession> {
     let file = tokio::fs::File::open(path).await.map_err(|e| {
         CcstatError::Io(std::io::Error::new(
             e.kind(),
@@ -150,6 +334,10 @@ async fn parse_session_file(path: &PathBuf, session_id: &str) -> Result<Vec<Usag
     let mut lines = reader.lines();
 
     let mut entries = Vec::new();
+    let mut rate_limits: Option<RateLimitSnapshot> = None;
+    let mut meta_session_id: Option<String> = None;
+    let mut meta_project: Option<String> = None;
+    let mut current_project: Option<String> = None;
     let mut current_model: Option<String> = None;
     let mut prev_cumulative = HashMap::<String, CumulativeTokens>::new();
 
@@ -164,21 +352,38 @@ async fn parse_session_file(path: &PathBuf, session_id: &str) -> Result<Vec<Usag
         };
 
         match event.event_type.as_str() {
+            "session_meta" => {
+                let Some(payload) = event.payload else {
+                    continue;
+                };
+                if let Some(id) = payload.id {
+                    meta_session_id = Some(id);
+                }
+                if let Some(project) = payload
+                    .cwd
+                    .as_deref()
+                    .and_then(UsageEntry::project_from_cwd)
+                {
+                    meta_project = Some(project.clone());
+                    current_project = Some(project);
+                }
+            }
             "turn_context" => {
                 if let Some(model) = event.model_id {
                     current_model = Some(normalize_model(&model));
                 }
+                let cwd = event.cwd.or(event.payload.and_then(|p| p.cwd));
+                if let Some(project) = cwd.as_deref().and_then(UsageEntry::project_from_cwd) {
+                    current_project = Some(project);
+                }
             }
             "event_msg" => {
-                let Some(payload) = &event.payload else {
+                let Some(payload) = event.payload else {
                     continue;
                 };
                 if payload.payload_type.as_deref() != Some("token_count") {
                     continue;
                 }
-                let Some(info) = &payload.info else {
-                    continue;
-                };
                 let Some(timestamp_str) = &event.timestamp else {
                     continue;
                 };
@@ -194,6 +399,21 @@ async fn parse_session_file(path: &PathBuf, session_id: &str) -> Result<Vec<Usag
                     }
                 };
 
+                // Rate limits can arrive on events without token info
+                if let Some(limits) = payload.rate_limits {
+                    let event_time = *timestamp.as_ref();
+                    rate_limits = Some(RateLimitSnapshot {
+                        timestamp: event_time,
+                        session_id: SessionId::new(session_id.to_string()),
+                        primary: limits.primary.map(|w| w.into_window(event_time)),
+                        secondary: limits.secondary.map(|w| w.into_window(event_time)),
+                    });
+                }
+
+                let Some(info) = &payload.info else {
+                    continue;
+                };
+
                 let model_name = current_model
                     .as_deref()
                     .unwrap_or(FALLBACK_MODEL)
@@ -208,7 +428,10 @@ async fn parse_session_file(path: &PathBuf, session_id: &str) -> Result<Vec<Usag
                 }
 
                 // Skip zero-token entries
-                if delta.input_tokens == 0 && delta.output_tokens == 0 {
+                if delta.input_tokens == 0
+                    && delta.output_tokens == 0
+                    && delta.reasoning_tokens == 0
+                {
                     continue;
                 }
 
@@ -218,18 +441,41 @@ async fn parse_session_file(path: &PathBuf, session_id: &str) -> Result<Vec<Usag
                     model: ModelName::new(model_name),
                     tokens: delta,
                     total_cost: None,
-                    project: None,
+                    project: current_project.clone(),
                     instance_id: None,
+                    provider: ProviderId::new("codex"),
                 });
             }
             _ => {}
         }
     }
 
-    Ok(entries)
+    // session_meta normally comes first, but don't rely on it
+    if let Some(project) = meta_project {
+        for entry in entries.iter_mut().filter(|e| e.project.is_none()) {
+            entry.project = Some(project.clone());
+        }
+    }
+    if let Some(id) = meta_session_id {
+        let session_id = SessionId::new(id);
+        for entry in &mut entries {
+            entry.session_id = session_id.clone();
+        }
+        if let Some(snapshot) = &mut rate_limits {
+            snapshot.session_id = session_id;
+        }
+    }
+
+    Ok(ParsedSession {
+        entries,
+        rate_limits,
+    })
 }
 
 /// Compute per-event delta tokens from cumulative or last_token_usage data.
+///
+/// Codex counts reasoning inside `output_tokens`; it is split out into
+/// `reasoning_tokens` so the two don't overlap.
 fn compute_delta(info: &TokenInfo, prev: Option<&CumulativeTokens>) -> TokenCounts {
     // Prefer last_token_usage when available (already a delta)
     if let Some(last) = &info.last_token_usage {
@@ -238,7 +484,14 @@ fn compute_delta(info: &TokenInfo, prev: Option<&CumulativeTokens>) -> TokenCoun
         } else {
             last.cache_read_tokens
         };
-        return TokenCounts::new(last.input_tokens, last.output_tokens, 0, cache_read);
+        let reasoning = last.reasoning_output_tokens;
+        return TokenCounts::new(
+            last.input_tokens,
+            last.output_tokens.saturating_sub(reasoning),
+            0,
+            cache_read,
+        )
+        .with_reasoning_tokens(reasoning);
     }
 
     // Fall back to cumulative-to-delta conversion
@@ -249,24 +502,32 @@ fn compute_delta(info: &TokenInfo, prev: Option<&CumulativeTokens>) -> TokenCoun
             total.cache_read_tokens
         };
 
-        let (prev_input, prev_output, prev_cache_read) = match prev {
+        let (prev_input, prev_output, prev_reasoning, prev_cache_read) = match prev {
             Some(p) => {
                 let p_cache = if p.cached_input_tokens > 0 {
                     p.cached_input_tokens
                 } else {
                     p.cache_read_tokens
                 };
-                (p.input_tokens, p.output_tokens, p_cache)
+                (
+                    p.input_tokens,
+                    p.output_tokens,
+                    p.reasoning_output_tokens,
+                    p_cache,
+                )
             }
-            None => (0, 0, 0),
+            None => (0, 0, 0, 0),
         };
 
+        let output = total.output_tokens.saturating_sub(prev_output);
+        let reasoning = total.reasoning_output_tokens.saturating_sub(prev_reasoning);
         TokenCounts::new(
             total.input_tokens.saturating_sub(prev_input),
-            total.output_tokens.saturating_sub(prev_output),
+            output.saturating_sub(reasoning),
             0,
             cache_read_total.saturating_sub(prev_cache_read),
         )
+        .with_reasoning_tokens(reasoning)
     } else {
         TokenCounts::new(0, 0, 0, 0)
     }
@@ -348,8 +609,10 @@ mod tests {
 
         let entries = parse_session_file(&session_file, "test-session")
             .await
-            .unwrap();
+            .unwrap()
+            .entries;
         assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].provider.as_str(), "codex");
         assert_eq!(entries[0].tokens.input_tokens, 100);
         assert_eq!(entries[0].tokens.output_tokens, 50);
         assert_eq!(entries[1].tokens.input_tokens, 200);
@@ -375,13 +638,46 @@ mod tests {
 
         let entries = parse_session_file(&session_file, "test-last")
             .await
-            .unwrap();
+            .unwrap()
+            .entries;
         assert_eq!(entries.len(), 1);
         // Should use last_token_usage (50, 20), not cumulative (500, 200)
         assert_eq!(entries[0].tokens.input_tokens, 50);
         assert_eq!(entries[0].tokens.output_tokens, 20);
     }
 
+    #[test]
+    fn test_reasoning_split_from_output() {
+        let tokens =
+            |json: serde_json::Value| -> CumulativeTokens { serde_json::from_value(json).unwrap() };
+
+        // last_token_usage: reasoning is part of output_tokens
+        let info = TokenInfo {
+            total_token_usage: None,
+            last_token_usage: Some(tokens(serde_json::json!({
+                "input_tokens": 100, "output_tokens": 80, "reasoning_output_tokens": 60
+            }))),
+        };
+        let delta = compute_delta(&info, None);
+        assert_eq!(delta.output_tokens, 20);
+        assert_eq!(delta.reasoning_tokens, 60);
+
+        // Cumulative totals are differenced per field
+        let prev = tokens(serde_json::json!({
+            "input_tokens": 100, "output_tokens": 80, "reasoning_output_tokens": 60
+        }));
+        let info = TokenInfo {
+            total_token_usage: Some(tokens(serde_json::json!({
+                "input_tokens": 300, "output_tokens": 180, "reasoning_output_tokens": 100
+            }))),
+            last_token_usage: None,
+        };
+        let delta = compute_delta(&info, Some(&prev));
+        assert_eq!(delta.input_tokens, 200);
+        assert_eq!(delta.output_tokens, 60);
+        assert_eq!(delta.reasoning_tokens, 40);
+    }
+
     #[tokio::test]
     async fn test_model_fallback() {
         let dir = TempDir::new().unwrap();
@@ -399,7 +695,10 @@ mod tests {
         )
         .unwrap();
 
-        let entries = parse_session_file(&session_file, "no-model").await.unwrap();
+        let entries = parse_session_file(&session_file, "no-model")
+            .await
+            .unwrap()
+            .entries;
         assert_eq!(entries.len(), 1);
         assert_eq!(entries[0].model.as_str(), FALLBACK_MODEL);
     }
@@ -411,6 +710,281 @@ mod tests {
         assert_eq!(normalize_model("o3-mini"), "o3-mini");
     }
 
+    fn make_rate_limit_event(ts: &str, primary: f64, secondary: f64) -> String {
+        format!(
+            r#"{{"type":"event_msg","timestamp":"{}","payload":{{"type":"token_count","info":null,"rate_limits":{{"primary":{{"used_percent":{},"window_minutes":300,"resets_in_seconds":600}},"secondary":{{"used_percent":{},"window_minutes":10080,"resets_at":1735732800}}}}}}}}"#,
+            ts, primary, secondary
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_snapshot() {
+        let dir = TempDir::new().unwrap();
+        let session_file = dir.path().join("limits.jsonl");
+        let mut f = std::fs::File::create(&session_file).unwrap();
+
+        writeln!(
+            f,
+            "{}",
+            make_rate_limit_event("2025-01-01T10:00:00Z", 10.0, 2.0)
+        )
+        .unwrap();
+        writeln!(
+            f,
+            "{}",
+            make_token_event("2025-01-01T10:05:00Z", 100, 50, 0)
+        )
+        .unwrap();
+        writeln!(
+            f,
+            "{}",
+            make_rate_limit_event("2025-01-01T10:10:00Z", 42.5, 7.0)
+        )
+        .unwrap();
+
+        let parsed = parse_session_file(&session_file, "limits").await.unwrap();
+        assert_eq!(parsed.entries.len(), 1);
+
+        let snapshot = parsed.rate_limits.unwrap();
+        assert_eq!(snapshot.session_id.as_str(), "limits");
+        assert_eq!(snapshot.timestamp.to_rfc3339(), "2025-01-01T10:10:00+00:00");
+
+        let primary = snapshot.primary.unwrap();
+        assert_eq!(primary.used_percent, 42.5);
+        assert_eq!(primary.window_minutes, Some(300));
+        // resets_in_seconds is relative to the event
+        assert_eq!(
+            primary.resets_at.unwrap().to_rfc3339(),
+            "2025-01-01T10:20:00+00:00"
+        );
+
+        let secondary = snapshot.secondary.unwrap();
+        assert_eq!(secondary.used_percent, 7.0);
+        // resets_at is a Unix timestamp
+        assert_eq!(
+            secondary.resets_at.unwrap().to_rfc3339(),
+            "2025-01-01T12:00:00+00:00"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_rate_limits_picks_latest_session() {
+        let dir = TempDir::new().unwrap();
+        for (name, ts, used) in [
+            ("newer", "2025-01-02T09:00:00Z", 55.0),
+            ("older", "2025-01-01T09:00:00Z", 90.0),
+        ] {
+            let mut f = std::fs::File::create(dir.path().join(format!("{name}.jsonl"))).unwrap();
+            writeln!(f, "{}", make_rate_limit_event(ts, used, 1.0)).unwrap();
+        }
+        std::fs::File::create(dir.path().join("no-limits.jsonl")).unwrap();
+
+        let loader = DataLoader {
+            session_dirs: vec![dir.path().to_path_buf()],
+            since: None,
+            until: None,
+        };
+        let snapshot = loader.load_rate_limits().await.unwrap().unwrap();
+        assert_eq!(snapshot.session_id.as_str(), "newer");
+        assert_eq!(snapshot.primary.unwrap().used_percent, 55.0);
+
+        let empty = DataLoader {
+            session_dirs: vec![dir.path().join("missing")],
+            since: None,
+            until: None,
+        };
+        assert!(empty.load_rate_limits().await.unwrap().is_none());
+    }
+
+    fn make_session_meta(id: &str) -> String {
+        format!(
+            r#"{{"type":"session_meta","timestamp":"2025-01-01T00:00:00Z","payload":{{"id":"{}","cwd":"/work"}}}}"#,
+            id
+        )
+    }
+
+    /// Write a rollout file in the directory of the day a session started,
+    /// with usage on that day and on each later day in `resumed`
+    fn write_resumed_rollout(
+        sessions_dir: &Path,
+        date: (u32, u32, u32),
+        id: &str,
+        resumed: &[(u32, u32, u32)],
+    ) {
+        let (year, month, day) = date;
+        let day_dir = sessions_dir
+            .join(year.to_string())
+            .join(format!("{month:02}"))
+            .join(format!("{day:02}"));
+        std::fs::create_dir_all(&day_dir).unwrap();
+        let path = day_dir.join(format!("rollout-{id}.jsonl"));
+        let mut f = std::fs::File::create(&path).unwrap();
+        writeln!(f, "{}", make_session_meta(id)).unwrap();
+        let mut last = String::new();
+        for (i, (year, month, day)) in std::iter::once(&date).chain(resumed).enumerate() {
+            last = format!("{year}-{month:02}-{day:02}T12:00:00Z");
+            let total = 100 * (i as u64 + 1);
+            writeln!(f, "{}", make_token_event(&last, total, total / 2, 0)).unwrap();
+        }
+        drop(f);
+
+        // The file was last written with its latest usage
+        let modified = DateTime::parse_from_rfc3339(&last).unwrap();
+        let modified = filetime::FileTime::from_unix_time(modified.timestamp(), 0);
+        filetime::set_file_mtime(&path, modified).unwrap();
+    }
+
+    fn write_rollout(sessions_dir: &Path, date: (u32, u32, u32), id: &str) {
+        write_resumed_rollout(sessions_dir, date, id, &[]);
+    }
+
+    fn loader_for(session_dir: &Path) -> DataLoader {
+        DataLoader {
+            session_dirs: vec![session_dir.to_path_buf()],
+            since: None,
+            until: None,
+        }
+    }
+
+    async fn session_ids(loader: &DataLoader) -> Vec<String> {
+        let entries: Vec<UsageEntry> = futures::TryStreamExt::try_collect(loader.load_entries())
+            .await
+            .unwrap();
+        entries
+            .iter()
+            .map(|e| e.session_id.as_str().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_date_partitioned_discovery() {
+        let dir = TempDir::new().unwrap();
+        write_rollout(dir.path(), (2025, 1, 5), "aaa");
+        write_rollout(dir.path(), (2025, 2, 10), "bbb");
+
+        // Legacy flat layout without session_meta is still read
+        let mut f = std::fs::File::create(dir.path().join("legacy.jsonl")).unwrap();
+        writeln!(f, "{}", make_token_event("2024-12-31T12:00:00Z", 10, 5, 0)).unwrap();
+
+        let ids = session_ids(&loader_for(dir.path())).await;
+        assert_eq!(ids, ["aaa", "bbb", "legacy"]);
+    }
+
+    #[tokio::test]
+    async fn test_session_meta_overrides_file_stem() {
+        let dir = TempDir::new().unwrap();
+        let session_file = dir.path().join("rollout-2025-01-01T10-00-00-abc.jsonl");
+        let mut f = std::fs::File::create(&session_file).unwrap();
+        writeln!(
+            f,
+            "{}",
+            make_token_event("2025-01-01T10:00:00Z", 100, 50, 0)
+        )
+        .unwrap();
+        writeln!(f, "{}", make_session_meta("0199-abc")).unwrap();
+
+        let parsed = parse_session_file(&session_file, "rollout-2025-01-01T10-00-00-abc")
+            .await
+            .unwrap();
+        assert_eq!(parsed.entries[0].session_id.as_str(), "0199-abc");
+    }
+
+    #[tokio::test]
+    async fn test_project_from_cwd() {
+        let dir = TempDir::new().unwrap();
+        let session_file = dir.path().join("rollout-projects.jsonl");
+        let mut f = std::fs::File::create(&session_file).unwrap();
+
+        // session_meta's cwd applies until a turn_context changes it
+        writeln!(f, "{}", make_session_meta("proj-session")).unwrap();
+        writeln!(
+            f,
+            "{}",
+            make_token_event("2025-01-01T10:00:00Z", 100, 50, 0)
+        )
+        .unwrap();
+        writeln!(
+            f,
+            r#"{{"type":"turn_context","payload":{{"cwd":"/home/me/other-repo","model":"gpt-5"}}}}"#
+        )
+        .unwrap();
+        writeln!(
+            f,
+            "{}",
+            make_token_event("2025-01-01T10:05:00Z", 300, 150, 0)
+        )
+        .unwrap();
+
+        let entries = parse_session_file(&session_file, "rollout-projects")
+            .await
+            .unwrap()
+            .entries;
+        assert_eq!(entries[0].project.as_deref(), Some("work"));
+        assert_eq!(entries[1].project.as_deref(), Some("other-repo"));
+    }
+
+    #[tokio::test]
+    async fn test_date_range_prunes_partitions() {
+        let dir = TempDir::new().unwrap();
+        write_rollout(dir.path(), (2024, 12, 30), "old");
+        write_rollout(dir.path(), (2025, 1, 9), "edge");
+        write_rollout(dir.path(), (2025, 1, 10), "inside");
+        write_rollout(dir.path(), (2025, 3, 1), "new");
+
+        let loader = loader_for(dir.path()).with_date_range(
+            NaiveDate::from_ymd_opt(2025, 1, 10),
+            NaiveDate::from_ymd_opt(2025, 1, 31),
+        );
+        // One day of slack on either side for timezone differences
+        assert_eq!(session_ids(&loader).await, ["edge", "inside"]);
+
+        // Directories before the range are walked; their files are skipped
+        // by modification time
+        assert!(loader.partition_in_range(dir.path(), &dir.path().join("2024")));
+        assert!(loader.partition_in_range(dir.path(), &dir.path().join("2025").join("01")));
+        assert!(!loader.partition_in_range(dir.path(), &dir.path().join("2025").join("03")));
+        assert!(loader.partition_in_range(dir.path(), &dir.path().join("archive")));
+    }
+
+    #[tokio::test]
+    async fn test_date_range_keeps_resumed_sessions() {
+        let dir = TempDir::new().unwrap();
+        // Started on the 1st, resumed on the 10th, still in the 1st's directory
+        write_resumed_rollout(dir.path(), (2025, 1, 1), "resumed", &[(2025, 1, 10)]);
+        write_rollout(dir.path(), (2025, 1, 2), "finished");
+
+        let loader = loader_for(dir.path()).with_date_range(
+            NaiveDate::from_ymd_opt(2025, 1, 10),
+            NaiveDate::from_ymd_opt(2025, 1, 31),
+        );
+        let entries: Vec<UsageEntry> = futures::TryStreamExt::try_collect(loader.load_entries())
+            .await
+            .unwrap();
+        let resumed: Vec<_> = entries
+            .iter()
+            .filter(|e| {
+                e.timestamp.inner().date_naive() >= NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()
+            })
+            .collect();
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].session_id.as_str(), "resumed");
+        assert_eq!(resumed[0].tokens.input_tokens, 100);
+        assert!(entries.iter().all(|e| e.session_id.as_str() == "resumed"));
+    }
+
+    #[test]
+    fn test_partition_dates() {
+        assert_eq!(
+            partition_dates(Path::new("2024/02")),
+            Some((
+                NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+            ))
+        );
+        assert_eq!(partition_dates(Path::new("2024/13")), None);
+        assert_eq!(partition_dates(Path::new("misc")), None);
+    }
+
     #[tokio::test]
     async fn test_data_loader_no_dir() {
         // When CODEX_HOME points to nonexistent dir, new() should succeed
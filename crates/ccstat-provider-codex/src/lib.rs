@@ -3,6 +3,21 @@
 //! This crate implements the provider trait for OpenAI Codex,
 //! handling JSONL session parsing with cumulative-to-delta token conversion.
 
+use ccstat_core::registry::{ProviderInfo, ReportType};
+
 pub mod data_loader;
 
 pub use data_loader::DataLoader;
+
+/// Registry entry for the Codex provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("codex", "Codex usage data")
+        .with_env_vars(&["CODEX_HOME"])
+        .with_reports(&[
+            ReportType::Daily,
+            ReportType::Monthly,
+            ReportType::Session,
+            ReportType::Blocks,
+            ReportType::Limits,
+        ])
+}
@@ -0,0 +1,362 @@
+//! Crush data loader
+//!
+//! Crush keeps one SQLite session store per project (`.crush/crush.db` by
+//! default) and lists the projects it has seen in
+//! `~/.local/share/crush/projects.json`. Each session row records its prompt
+//! and completion token totals and the cost Crush calculated for it; messages
+//! carry no token counts. Crush data is therefore per-session only: one usage
+//! entry is produced per session, at its last update and with the model of
+//! its latest reply, and is always priced at the stored cost, since the
+//! summed tokens of a session cannot be priced like a single request.
+//!
+//! Crush rolls the cost of sub-agent sessions into their parent session, so
+//! only top-level sessions are read.
+
+use async_trait::async_trait;
+use ccstat_core::error::{CcstatError, Result};
+use ccstat_core::provider::ProviderDataLoader;
+use ccstat_core::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry};
+use chrono::{TimeZone, Utc};
+use futures::stream::Stream;
+use rusqlite::{Connection, OpenFlags};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use tracing::{debug, warn};
+
+/// Data loader for Crush usage data.
+pub struct DataLoader {
+    data_dirs: Vec<PathBuf>,
+}
+
+#[async_trait]
+impl ProviderDataLoader for DataLoader {
+    fn default_roots() -> Result<Vec<PathBuf>> {
+        let data_dir = if let Ok(data_dir) = std::env::var("CRUSH_DATA_DIR") {
+            PathBuf::from(data_dir)
+        } else {
+            dirs::data_dir()
+                .ok_or_else(|| CcstatError::Config("Cannot determine data directory".into()))?
+                .join("crush")
+        };
+        Ok(vec![data_dir])
+    }
+
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        for data_dir in roots.iter().filter(|dir| !dir.exists()) {
+            debug!("Crush data directory not found: {}", data_dir.display());
+        }
+
+        DataLoader { data_dirs: roots }
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.data_dirs
+    }
+
+    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+        Box::pin(async_stream::try_stream! {
+            let mut projects = Vec::new();
+            for data_dir in &self.data_dirs {
+                let projects_file = data_dir.join("projects.json");
+                if !projects_file.exists() {
+                    continue;
+                }
+
+                let content = tokio::fs::read_to_string(&projects_file).await?;
+                let list: ProjectList = serde_json::from_str(&content).map_err(|e| {
+                    CcstatError::Parse {
+                        file: projects_file.clone(),
+                        error: e.to_string(),
+                    }
+                })?;
+                projects.extend(list.projects);
+            }
+
+            debug!("Found {} Crush projects", projects.len());
+
+            // Several data directories can list the same project
+            let mut seen_dbs = HashSet::new();
+
+            for project in projects {
+                let db_path = PathBuf::from(&project.data_dir).join("crush.db");
+                if !seen_dbs.insert(db_path.clone()) {
+                    continue;
+                }
+                if !db_path.exists() {
+                    debug!("Crush database not found: {}", db_path.display());
+                    continue;
+                }
+
+                let project_name = UsageEntry::project_from_cwd(&project.path);
+                let path = db_path.clone();
+                let loaded = tokio::task::spawn_blocking(move || {
+                    read_sessions(&path, project_name.as_deref())
+                })
+                .await
+                .map_err(|e| CcstatError::Io(std::io::Error::other(e)))?;
+
+                match loaded {
+                    Ok(entries) => {
+                        for entry in entries {
+                            yield entry;
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to read Crush database {}: {}", db_path.display(), e);
+                    }
+                }
+            }
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Project list schema
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct ProjectList {
+    #[serde(default)]
+    projects: Vec<CrushProject>,
+}
+
+#[derive(Deserialize)]
+struct CrushProject {
+    /// Working directory of the project
+    path: String,
+    /// Directory holding the project's `crush.db`
+    data_dir: String,
+}
+
+// ---------------------------------------------------------------------------
+// Session store
+// ---------------------------------------------------------------------------
+
+/// Top-level sessions with their token totals, cost, and the model of the
+/// latest reply
+const SESSIONS_QUERY: &str = "
+    SELECT s.id, s.prompt_tokens, s.completion_tokens, s.cost, s.updated_at,
+        (SELECT m.model FROM messages m
+            WHERE m.session_id = s.id AND m.model IS NOT NULL AND m.model != ''
+            ORDER BY m.created_at DESC LIMIT 1)
+    FROM sessions s
+    WHERE s.parent_session_id IS NULL
+    ORDER BY s.created_at";
+
+fn read_sessions(db_path: &Path, project: Option<&str>) -> Result<Vec<UsageEntry>> {
+    let parse_error = |e: rusqlite::Error| CcstatError::Parse {
+        file: db_path.to_path_buf(),
+        error: e.to_string(),
+    };
+
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(parse_error)?;
+    let mut stmt = conn.prepare(SESSIONS_QUERY).map_err(parse_error)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(SessionRow {
+                id: row.get(0)?,
+                prompt_tokens: row.get::<_, Option<i64>>(1)?.unwrap_or(0),
+                completion_tokens: row.get::<_, Option<i64>>(2)?.unwrap_or(0),
+                cost: row.get(3)?,
+                updated_at: row.get::<_, Option<i64>>(4)?.unwrap_or(0),
+                model: row.get(5)?,
+            })
+        })
+        .map_err(parse_error)?;
+
+    let mut entries = Vec::new();
+    for row in rows {
+        let row = row.map_err(parse_error)?;
+        if let Some(entry) = convert_session(row, project) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+struct SessionRow {
+    id: String,
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    cost: Option<f64>,
+    updated_at: i64,
+    model: Option<String>,
+}
+
+fn convert_session(row: SessionRow, project: Option<&str>) -> Option<UsageEntry> {
+    let input = u64::try_from(row.prompt_tokens).unwrap_or(0);
+    let output = u64::try_from(row.completion_tokens).unwrap_or(0);
+
+    // Skip sessions that never reached the model
+    if input == 0 && output == 0 {
+        return None;
+    }
+
+    // Crush stores Unix seconds; tolerate milliseconds too
+    let timestamp = if row.updated_at > 100_000_000_000 {
+        Utc.timestamp_millis_opt(row.updated_at).single()
+    } else {
+        Utc.timestamp_opt(row.updated_at, 0).single()
+    }?;
+
+    Some(UsageEntry {
+        session_id: SessionId::new(row.id),
+        timestamp: ISOTimestamp::new(timestamp),
+        model: ModelName::new(normalize_model(row.model.as_deref().unwrap_or("unknown"))),
+        tokens: TokenCounts::new(input, output, 0, 0),
+        // Always set, so the stored cost is used in every cost mode
+        total_cost: Some(row.cost.unwrap_or(0.0)),
+        project: project.map(|s| s.to_string()),
+        instance_id: None,
+        provider: ProviderId::new("crush"),
+    })
+}
+
+/// Normalize Crush model names.
+///
+/// Models routed through OpenRouter-style providers are reported as
+/// `vendor/model[:variant]`; pricing data is keyed by the bare model name.
+fn normalize_model(model: &str) -> String {
+    let model = model.rsplit('/').next().unwrap_or(model);
+    model.split(':').next().unwrap_or(model).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_db(data_dir: &Path) -> Connection {
+        std::fs::create_dir_all(data_dir).unwrap();
+        let conn = Connection::open(data_dir.join("crush.db")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sessions (
+                id TEXT PRIMARY KEY,
+                parent_session_id TEXT,
+                title TEXT NOT NULL DEFAULT '',
+                message_count INTEGER NOT NULL DEFAULT 0,
+                prompt_tokens INTEGER NOT NULL DEFAULT 0,
+                completion_tokens INTEGER NOT NULL DEFAULT 0,
+                cost REAL NOT NULL DEFAULT 0.0,
+                updated_at INTEGER NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE messages (
+                id TEXT PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                role TEXT NOT NULL,
+                parts TEXT NOT NULL DEFAULT '[]',
+                model TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn write_projects(data_dir: &Path, projects: &[(&str, &Path)]) {
+        let projects: Vec<_> = projects
+            .iter()
+            .map(|(path, dir)| {
+                serde_json::json!({
+                    "path": path,
+                    "data_dir": dir,
+                    "last_accessed": "2025-01-01T10:00:00Z"
+                })
+            })
+            .collect();
+        std::fs::create_dir_all(data_dir).unwrap();
+        std::fs::write(
+            data_dir.join("projects.json"),
+            serde_json::json!({ "projects": projects }).to_string(),
+        )
+        .unwrap();
+    }
+
+    async fn load(data_dir: PathBuf) -> Vec<UsageEntry> {
+        let loader = DataLoader {
+            data_dirs: vec![data_dir],
+        };
+        futures::StreamExt::collect::<Vec<_>>(loader.load_entries())
+            .await
+            .into_iter()
+            .map(|e| e.unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_parse_sessions() {
+        let dir = TempDir::new().unwrap();
+        let project_data = dir.path().join("my-app").join(".crush");
+        let conn = create_db(&project_data);
+        conn.execute_batch(
+            "INSERT INTO sessions (id, prompt_tokens, completion_tokens, cost, updated_at, created_at)
+                VALUES ('s1', 1200, 300, 0.042, 1735725600, 1735725000),
+                       ('empty', 0, 0, 0.0, 1735725600, 1735725000);
+             INSERT INTO sessions (id, parent_session_id, prompt_tokens, completion_tokens, cost, updated_at, created_at)
+                VALUES ('agent', 's1', 400, 100, 0.01, 1735725400, 1735725200);
+             INSERT INTO messages (id, session_id, role, model, created_at, updated_at)
+                VALUES ('m1', 's1', 'user', NULL, 1735725000, 1735725000),
+                       ('m2', 's1', 'assistant', 'claude-3-5-haiku', 1735725100, 1735725100),
+                       ('m3', 's1', 'assistant', 'anthropic/claude-sonnet-4', 1735725500, 1735725500);",
+        )
+        .unwrap();
+
+        let global = dir.path().join("global");
+        write_projects(&global, &[("/home/me/my-app", &project_data)]);
+
+        let entries = load(global).await;
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.session_id.as_str(), "s1");
+        assert_eq!(entry.model.as_str(), "claude-sonnet-4");
+        assert_eq!(entry.tokens.input_tokens, 1200);
+        assert_eq!(entry.tokens.output_tokens, 300);
+        assert_eq!(entry.total_cost, Some(0.042));
+        assert_eq!(entry.project.as_deref(), Some("my-app"));
+        assert_eq!(entry.provider.as_str(), "crush");
+        assert_eq!(
+            entry.timestamp.inner(),
+            &Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_skips_missing_and_broken_databases() {
+        let dir = TempDir::new().unwrap();
+        let broken = dir.path().join("broken");
+        std::fs::create_dir_all(&broken).unwrap();
+        std::fs::write(broken.join("crush.db"), "not a database").unwrap();
+
+        let global = dir.path().join("global");
+        write_projects(
+            &global,
+            &[
+                ("/home/me/gone", &dir.path().join("gone")),
+                ("/home/me/broken", &broken),
+            ],
+        );
+
+        assert!(load(global).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_model_normalization() {
+        assert_eq!(normalize_model("gpt-4.1"), "gpt-4.1");
+        assert_eq!(
+            normalize_model("anthropic/claude-sonnet-4"),
+            "claude-sonnet-4"
+        );
+        assert_eq!(normalize_model("qwen/qwen3-coder:free"), "qwen3-coder");
+    }
+
+    #[tokio::test]
+    async fn test_no_dir() {
+        let entries = load(PathBuf::from("/tmp/nonexistent-crush-dir")).await;
+        assert!(entries.is_empty());
+    }
+}
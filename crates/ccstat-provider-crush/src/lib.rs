@@ -0,0 +1,22 @@
+//! Crush provider for ccstat
+//!
+//! This crate implements the provider trait for Charm's Crush,
+//! reading per-project SQLite session stores.
+
+use ccstat_core::registry::ProviderInfo;
+
+pub mod data_loader;
+
+pub use data_loader::DataLoader;
+
+/// Registry entry for the Crush provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("crush", "Crush usage data")
+        .with_env_vars(&["CRUSH_DATA_DIR"])
+        .with_stored_costs()
+        .with_note(
+            "Crush records token totals per session only, so each session's usage is \
+             dated at its last update: a session spanning several days counts entirely \
+             on its last day and in one billing block.",
+        )
+}
@@ -0,0 +1,381 @@
+//! Gemini CLI data loader
+//!
+//! Discovers and parses Gemini CLI chat session files from
+//! `~/.gemini/tmp/{project_hash}/chats/session-*.json`. Each Gemini reply
+//! records its prompt, candidate, cached, thought and tool token counts.
+
+use async_trait::async_trait;
+use ccstat_core::error::{CcstatError, Result};
+use ccstat_core::provider::ProviderDataLoader;
+use ccstat_core::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry};
+use chrono::DateTime;
+use futures::stream::Stream;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use tracing::{debug, warn};
+
+/// Data loader for Gemini CLI usage data.
+pub struct DataLoader {
+    tmp_dirs: Vec<PathBuf>,
+}
+
+#[async_trait]
+impl ProviderDataLoader for DataLoader {
+    /// Gemini CLI quotas reset daily.
+    const DEFAULT_BLOCK_HOURS: f64 = 24.0;
+
+    fn default_roots() -> Result<Vec<PathBuf>> {
+        let base = if let Ok(gemini_dir) = std::env::var("GEMINI_DIR") {
+            PathBuf::from(gemini_dir)
+        } else {
+            dirs::home_dir()
+                .ok_or_else(|| CcstatError::Config("Cannot determine home directory".into()))?
+                .join(".gemini")
+        };
+        Ok(vec![base])
+    }
+
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        let tmp_dirs: Vec<_> = roots.iter().map(|root| root.join("tmp")).collect();
+        for tmp_dir in tmp_dirs.iter().filter(|dir| !dir.exists()) {
+            debug!("Gemini tmp directory not found: {}", tmp_dir.display());
+        }
+
+        DataLoader { tmp_dirs }
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.tmp_dirs
+    }
+
+    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+        Box::pin(async_stream::try_stream! {
+            let mut json_files = Vec::new();
+            for tmp_dir in self.tmp_dirs.iter().filter(|dir| dir.exists()) {
+                for entry in walkdir::WalkDir::new(tmp_dir)
+                    .min_depth(3)
+                    .max_depth(3)
+                    .sort_by_file_name()
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                {
+                    let path = entry.path().to_path_buf();
+                    if is_chat_file(&path) {
+                        json_files.push(path);
+                    }
+                }
+            }
+
+            debug!("Found {} Gemini chat files", json_files.len());
+
+            // Resumed sessions can be saved more than once
+            let mut seen_ids = HashSet::new();
+
+            for path in json_files {
+                let content = match tokio::fs::read_to_string(&path).await {
+                    Ok(c) => c,
+                    Err(e) => {
+                        warn!("Failed to read Gemini chat file {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+
+                let session: GeminiSession = match serde_json::from_str(&content) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        warn!("Failed to parse Gemini chat {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+
+                let session_id = session.session_id.clone().unwrap_or_else(|| {
+                    path.file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or("unknown")
+                        .to_string()
+                });
+
+                for entry in extract_entries(&session, &session_id, &mut seen_ids) {
+                    yield entry;
+                }
+            }
+        })
+    }
+}
+
+/// Chat session files live in `{project_hash}/chats/` and are JSON
+fn is_chat_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+        && path
+            .parent()
+            .and_then(|p| p.file_name())
+            .is_some_and(|name| name == "chats")
+}
+
+// ---------------------------------------------------------------------------
+// Session schema
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiSession {
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    messages: Vec<GeminiMessage>,
+}
+
+#[derive(Deserialize)]
+struct GeminiMessage {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    timestamp: Option<String>,
+    #[serde(rename = "type", default)]
+    message_type: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    tokens: Option<GeminiTokens>,
+}
+
+/// Token counts as Gemini CLI records them, also accepting the raw
+/// `usageMetadata` field names from the Gemini API.
+#[derive(Deserialize)]
+struct GeminiTokens {
+    /// Prompt tokens, including the cached portion
+    #[serde(default, alias = "promptTokenCount")]
+    input: u64,
+    /// Candidate (response) tokens
+    #[serde(default, alias = "candidatesTokenCount")]
+    output: u64,
+    #[serde(default, alias = "cachedContentTokenCount")]
+    cached: u64,
+    #[serde(default, alias = "thoughtsTokenCount")]
+    thoughts: u64,
+    #[serde(default, alias = "toolUsePromptTokenCount")]
+    tool: u64,
+}
+
+impl GeminiTokens {
+    /// Map onto ccstat's token buckets
+    ///
+    /// Cached tokens are split out of the prompt count and read from cache;
+    /// tool-use prompt tokens are billed as input and thoughts as reasoning.
+    fn to_token_counts(&self) -> TokenCounts {
+        TokenCounts::new(
+            self.input.saturating_sub(self.cached) + self.tool,
+            self.output,
+            0,
+            self.cached,
+        )
+        .with_reasoning_tokens(self.thoughts)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Extraction
+// ---------------------------------------------------------------------------
+
+fn extract_entries(
+    session: &GeminiSession,
+    session_id: &str,
+    seen_ids: &mut HashSet<(String, String)>,
+) -> Vec<UsageEntry> {
+    let mut entries = Vec::new();
+
+    for message in &session.messages {
+        // Only Gemini replies carry usage
+        if message.message_type.as_deref() != Some("gemini") {
+            continue;
+        }
+        let Some(tokens) = &message.tokens else {
+            continue;
+        };
+
+        // Skip zero-token messages
+        if tokens.input == 0 && tokens.output == 0 && tokens.thoughts == 0 {
+            continue;
+        }
+
+        if let Some(id) = &message.id
+            && !seen_ids.insert((session_id.to_string(), id.clone()))
+        {
+            continue;
+        }
+
+        let timestamp = message
+            .timestamp
+            .as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| ISOTimestamp::new(dt.to_utc()));
+
+        let Some(timestamp) = timestamp else {
+            warn!(
+                "Skipping Gemini message with invalid timestamp in session {}",
+                session_id
+            );
+            continue;
+        };
+
+        entries.push(UsageEntry {
+            session_id: SessionId::new(session_id.to_string()),
+            timestamp,
+            model: ModelName::new(message.model.as_deref().unwrap_or("unknown")),
+            tokens: tokens.to_token_counts(),
+            total_cost: None,
+            project: None,
+            instance_id: None,
+            provider: ProviderId::new("gemini"),
+        });
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_session_json(session_id: &str, message_id: &str, tokens: &str) -> String {
+        format!(
+            r#"{{
+  "sessionId": "{session_id}",
+  "projectHash": "abc123",
+  "startTime": "2025-01-01T09:59:00Z",
+  "messages": [
+    {{"id": "u-1", "timestamp": "2025-01-01T09:59:30Z", "type": "user", "content": "hi"}},
+    {{
+      "id": "{message_id}",
+      "timestamp": "2025-01-01T10:00:00Z",
+      "type": "gemini",
+      "content": "hello",
+      "model": "gemini-2.5-pro",
+      "tokens": {tokens}
+    }}
+  ]
+}}"#
+        )
+    }
+
+    fn write_chat(tmp_dir: &Path, project_hash: &str, name: &str, content: &str) {
+        let chats = tmp_dir.join(project_hash).join("chats");
+        std::fs::create_dir_all(&chats).unwrap();
+        std::fs::write(chats.join(name), content).unwrap();
+    }
+
+    async fn load(tmp_dir: PathBuf) -> Vec<UsageEntry> {
+        let loader = DataLoader {
+            tmp_dirs: vec![tmp_dir],
+        };
+        futures::StreamExt::collect::<Vec<_>>(loader.load_entries())
+            .await
+            .into_iter()
+            .map(|e| e.unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_parse_session() {
+        let dir = TempDir::new().unwrap();
+        write_chat(
+            dir.path(),
+            "abc123",
+            "session-2025-01-01T09-59-s1.json",
+            &make_session_json(
+                "s1",
+                "m-1",
+                r#"{"input": 1000, "output": 200, "cached": 400, "thoughts": 50, "tool": 10, "total": 1260}"#,
+            ),
+        );
+
+        let entries = load(dir.path().to_path_buf()).await;
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.session_id.as_str(), "s1");
+        assert_eq!(entry.model.as_str(), "gemini-2.5-pro");
+        assert_eq!(entry.provider.as_str(), "gemini");
+        assert_eq!(entry.tokens.input_tokens, 610);
+        assert_eq!(entry.tokens.output_tokens, 200);
+        assert_eq!(entry.tokens.reasoning_tokens, 50);
+        assert_eq!(entry.tokens.cache_read_tokens, 400);
+        assert_eq!(entry.tokens.cache_creation_tokens, 0);
+        assert_eq!(entry.total_cost, None);
+    }
+
+    #[tokio::test]
+    async fn test_usage_metadata_field_names() {
+        let tokens: GeminiTokens = serde_json::from_str(
+            r#"{"promptTokenCount": 300, "candidatesTokenCount": 40, "cachedContentTokenCount": 100, "thoughtsTokenCount": 5}"#,
+        )
+        .unwrap();
+        let counts = tokens.to_token_counts();
+        assert_eq!(counts.input_tokens, 200);
+        assert_eq!(counts.output_tokens, 40);
+        assert_eq!(counts.reasoning_tokens, 5);
+        assert_eq!(counts.cache_read_tokens, 100);
+    }
+
+    #[tokio::test]
+    async fn test_dedup_resaved_session() {
+        let dir = TempDir::new().unwrap();
+        let json = make_session_json("s1", "m-1", r#"{"input": 100, "output": 50}"#);
+        write_chat(dir.path(), "abc123", "session-a.json", &json);
+        write_chat(dir.path(), "abc123", "session-b.json", &json);
+
+        assert_eq!(load(dir.path().to_path_buf()).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_skips_non_chat_files() {
+        let dir = TempDir::new().unwrap();
+        let json = make_session_json("s1", "m-1", r#"{"input": 100, "output": 50}"#);
+        // Checkpoints and logs sit next to chats/ and must be ignored
+        std::fs::create_dir_all(dir.path().join("abc123")).unwrap();
+        std::fs::write(dir.path().join("abc123").join("logs.json"), &json).unwrap();
+
+        assert!(load(dir.path().to_path_buf()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_session_id_falls_back_to_file_name() {
+        let dir = TempDir::new().unwrap();
+        let json = r#"{"messages": [{"id": "m-1", "timestamp": "2025-01-01T10:00:00Z", "type": "gemini", "model": "gemini-2.5-flash", "tokens": {"input": 10, "output": 5}}]}"#;
+        write_chat(dir.path(), "abc123", "session-legacy.json", json);
+
+        let entries = load(dir.path().to_path_buf()).await;
+        assert_eq!(entries[0].session_id.as_str(), "session-legacy");
+    }
+
+    #[tokio::test]
+    async fn test_from_roots_reads_every_root() {
+        let live = TempDir::new().unwrap();
+        let backup = TempDir::new().unwrap();
+        let s1 = make_session_json("s1", "m-1", r#"{"input": 100, "output": 50}"#);
+        let s2 = make_session_json("s2", "m-2", r#"{"input": 100, "output": 50}"#);
+        write_chat(&live.path().join("tmp"), "abc123", "session-1.json", &s1);
+        // The backup holds a copy of s1 alongside an older session
+        write_chat(&backup.path().join("tmp"), "abc123", "session-1.json", &s1);
+        write_chat(&backup.path().join("tmp"), "abc123", "session-2.json", &s2);
+
+        let loader =
+            DataLoader::from_roots(vec![live.path().to_path_buf(), backup.path().to_path_buf()]);
+        assert_eq!(loader.paths().len(), 2);
+        let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries()).await;
+        let mut ids: Vec<_> = entries
+            .iter()
+            .map(|e| e.as_ref().unwrap().session_id.as_str().to_string())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, ["s1", "s2"]);
+    }
+
+    #[tokio::test]
+    async fn test_no_dir() {
+        let entries = load(PathBuf::from("/tmp/nonexistent-gemini-dir")).await;
+        assert!(entries.is_empty());
+    }
+}
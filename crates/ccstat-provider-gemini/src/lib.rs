@@ -0,0 +1,17 @@
+//! Gemini CLI provider for ccstat
+//!
+//! This crate implements the provider trait for Gemini CLI,
+//! handling chat session JSON parsing.
+
+use ccstat_core::registry::ProviderInfo;
+
+pub mod data_loader;
+
+pub use data_loader::DataLoader;
+
+/// Registry entry for the Gemini CLI provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("gemini", "Gemini CLI usage data")
+        .with_aliases(&["gemini-cli"])
+        .with_env_vars(&["GEMINI_DIR"])
+}
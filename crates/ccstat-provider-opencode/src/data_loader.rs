@@ -1,28 +1,30 @@
 //! OpenCode data loader
 //!
 //! Discovers and parses OpenCode per-message JSON files from
-//! `~/.local/share/opencode/storage/message/`.
+//! `~/.local/share/opencode/storage/message/`. Messages are attributed to the
+//! working directory recorded in the matching session info file under
+//! `storage/session/`.
 
 use async_trait::async_trait;
 use ccstat_core::error::{CcstatError, Result};
 use ccstat_core::provider::ProviderDataLoader;
-use ccstat_core::types::{ISOTimestamp, ModelName, SessionId, TokenCounts, UsageEntry};
+use ccstat_core::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry};
 use chrono::{TimeZone, Utc};
 use futures::stream::Stream;
 use serde::Deserialize;
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use tracing::{debug, warn};
 
 /// Data loader for OpenCode usage data.
 pub struct DataLoader {
-    message_dir: PathBuf,
+    message_dirs: Vec<PathBuf>,
 }
 
 #[async_trait]
 impl ProviderDataLoader for DataLoader {
-    async fn new() -> Result<Self> {
+    fn default_roots() -> Result<Vec<PathBuf>> {
         let base = if let Ok(data_dir) = std::env::var("OPENCODE_DATA_DIR") {
             PathBuf::from(data_dir)
         } else {
@@ -30,34 +32,47 @@ impl ProviderDataLoader for DataLoader {
                 .ok_or_else(|| CcstatError::Config("Cannot determine data directory".into()))?
                 .join("opencode")
         };
+        Ok(vec![base])
+    }
 
-        let message_dir = base.join("storage").join("message");
-        if !message_dir.exists() {
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        let message_dirs: Vec<_> = roots
+            .iter()
+            .map(|root| root.join("storage").join("message"))
+            .collect();
+        for message_dir in message_dirs.iter().filter(|dir| !dir.exists()) {
             debug!(
                 "OpenCode message directory not found: {}",
                 message_dir.display()
             );
         }
 
-        Ok(DataLoader { message_dir })
+        DataLoader { message_dirs }
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.message_dirs
     }
 
     fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
         Box::pin(async_stream::try_stream! {
-            if !self.message_dir.exists() {
-                return;
-            }
-
             let mut json_files = Vec::new();
-            for entry in walkdir::WalkDir::new(&self.message_dir)
-                .min_depth(1)
-                .max_depth(1)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                let path = entry.path().to_path_buf();
-                if path.extension().is_some_and(|ext| ext == "json") {
-                    json_files.push(path);
+            let mut projects = HashMap::new();
+            for message_dir in self.message_dirs.iter().filter(|dir| dir.exists()) {
+                for entry in walkdir::WalkDir::new(message_dir)
+                    .min_depth(1)
+                    .max_depth(1)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                {
+                    let path = entry.path().to_path_buf();
+                    if path.extension().is_some_and(|ext| ext == "json") {
+                        json_files.push(path);
+                    }
+                }
+
+                if let Some(storage) = message_dir.parent() {
+                    projects.extend(load_session_projects(&storage.join("session")).await);
                 }
             }
 
@@ -87,7 +102,7 @@ impl ProviderDataLoader for DataLoader {
                     continue;
                 }
 
-                if let Some(entry) = convert_message(msg) {
+                if let Some(entry) = convert_message(msg, &projects) {
                     yield entry;
                 }
             }
@@ -124,6 +139,8 @@ struct MessageTokens {
     #[serde(default)]
     output: u64,
     #[serde(default)]
+    reasoning: u64,
+    #[serde(default)]
     cache: Option<CacheTokens>,
 }
 
@@ -135,15 +152,62 @@ struct CacheTokens {
     write: u64,
 }
 
+/// Session info file, stored under `storage/session/<project>/<session>.json`
+#[derive(Deserialize)]
+struct OpenCodeSession {
+    id: String,
+    #[serde(default)]
+    directory: Option<String>,
+}
+
+/// Map session ids to the project name of their working directory
+async fn load_session_projects(session_dir: &Path) -> HashMap<String, String> {
+    let mut projects = HashMap::new();
+    if !session_dir.exists() {
+        return projects;
+    }
+
+    let files: Vec<PathBuf> = walkdir::WalkDir::new(session_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+
+    for path in files {
+        let session: OpenCodeSession = match tokio::fs::read_to_string(&path)
+            .await
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+        {
+            Some(s) => s,
+            None => {
+                debug!("Skipping OpenCode session file {}", path.display());
+                continue;
+            }
+        };
+        if let Some(project) = session
+            .directory
+            .as_deref()
+            .and_then(UsageEntry::project_from_cwd)
+        {
+            projects.insert(session.id, project);
+        }
+    }
+
+    debug!("Found projects for {} OpenCode sessions", projects.len());
+    projects
+}
+
 // ---------------------------------------------------------------------------
 // Conversion
 // ---------------------------------------------------------------------------
 
-fn convert_message(msg: OpenCodeMessage) -> Option<UsageEntry> {
+fn convert_message(msg: OpenCodeMessage, projects: &HashMap<String, String>) -> Option<UsageEntry> {
     let tokens = msg.tokens.as_ref()?;
 
     // Skip zero-token messages
-    if tokens.input == 0 && tokens.output == 0 {
+    if tokens.input == 0 && tokens.output == 0 && tokens.reasoning == 0 {
         return None;
     }
 
@@ -164,15 +228,18 @@ fn convert_message(msg: OpenCodeMessage) -> Option<UsageEntry> {
     };
 
     let model_name = normalize_model(&msg.model_id);
+    let project = projects.get(&msg.session_id).cloned();
 
     Some(UsageEntry {
         session_id: SessionId::new(msg.session_id),
         timestamp: ISOTimestamp::new(timestamp),
         model: ModelName::new(model_name),
-        tokens: TokenCounts::new(tokens.input, tokens.output, cache_write, cache_read),
+        tokens: TokenCounts::new(tokens.input, tokens.output, cache_write, cache_read)
+            .with_reasoning_tokens(tokens.reasoning),
         total_cost: msg.cost,
-        project: None,
+        project,
         instance_id: None,
+        provider: ProviderId::new("opencode"),
     })
 }
 
@@ -191,6 +258,7 @@ mod tests {
     use std::io::Write as IoWrite;
     use tempfile::TempDir;
 
+    #[allow(clippy::too_many_arguments)]
     fn make_message_json(
         id: &str,
         session_id: &str,
@@ -238,7 +306,7 @@ mod tests {
         .unwrap();
 
         let loader = DataLoader {
-            message_dir: msg_dir,
+            message_dirs: vec![msg_dir],
         };
         let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries()).await;
         assert_eq!(entries.len(), 1);
@@ -248,6 +316,28 @@ mod tests {
         assert_eq!(entry.tokens.cache_read_tokens, 10);
         assert_eq!(entry.tokens.cache_creation_tokens, 5);
         assert_eq!(entry.total_cost, Some(0.01));
+        assert_eq!(entry.provider.as_str(), "opencode");
+        assert_eq!(entry.tokens.reasoning_tokens, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reasoning_tokens() {
+        let dir = TempDir::new().unwrap();
+        let msg_dir = dir.path().join("storage").join("message");
+        std::fs::create_dir_all(&msg_dir).unwrap();
+        std::fs::write(
+            msg_dir.join("msg1.json"),
+            r#"{"id":"msg1","sessionID":"sess1","modelID":"gpt-5","time":{"created":1735689600.0},"tokens":{"input":100,"output":50,"reasoning":200,"cache":{"read":0,"write":0}}}"#,
+        )
+        .unwrap();
+
+        let loader = DataLoader {
+            message_dirs: vec![msg_dir],
+        };
+        let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries()).await;
+        let entry = entries[0].as_ref().unwrap();
+        assert_eq!(entry.tokens.output_tokens, 50);
+        assert_eq!(entry.tokens.reasoning_tokens, 200);
     }
 
     #[tokio::test]
@@ -279,7 +369,7 @@ mod tests {
         }
 
         let loader = DataLoader {
-            message_dir: msg_dir,
+            message_dirs: vec![msg_dir],
         };
         let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries()).await;
         // Should be deduped to 1
@@ -304,17 +394,64 @@ mod tests {
             tokens: Some(MessageTokens {
                 input: 0,
                 output: 0,
+                reasoning: 0,
                 cache: None,
             }),
             cost: None,
         };
-        assert!(convert_message(msg).is_none());
+        assert!(convert_message(msg, &HashMap::new()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_project_from_session_info() {
+        let dir = TempDir::new().unwrap();
+        let storage = dir.path().join("storage");
+        let msg_dir = storage.join("message");
+        let session_dir = storage.join("session").join("proj-hash");
+        std::fs::create_dir_all(&msg_dir).unwrap();
+        std::fs::create_dir_all(&session_dir).unwrap();
+
+        std::fs::write(
+            session_dir.join("sess1.json"),
+            r#"{"id":"sess1","projectID":"proj-hash","directory":"/home/me/my-app","title":"t"}"#,
+        )
+        .unwrap();
+        for (id, session) in [("msg1", "sess1"), ("msg2", "sess2")] {
+            std::fs::write(
+                msg_dir.join(format!("{id}.json")),
+                make_message_json(
+                    id,
+                    session,
+                    "claude-sonnet-4",
+                    1735689600.0,
+                    100,
+                    50,
+                    0,
+                    0,
+                    None,
+                ),
+            )
+            .unwrap();
+        }
+
+        let loader = DataLoader {
+            message_dirs: vec![msg_dir],
+        };
+        let mut entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries())
+            .await
+            .into_iter()
+            .map(|e| e.unwrap())
+            .collect();
+        entries.sort_by(|a, b| a.session_id.as_str().cmp(b.session_id.as_str()));
+        assert_eq!(entries[0].project.as_deref(), Some("my-app"));
+        // No session info file: unattributed
+        assert_eq!(entries[1].project, None);
     }
 
     #[tokio::test]
     async fn test_no_dir() {
         let loader = DataLoader {
-            message_dir: PathBuf::from("/tmp/nonexistent-opencode-dir"),
+            message_dirs: vec![PathBuf::from("/tmp/nonexistent-opencode-dir")],
         };
         let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries()).await;
         assert!(entries.is_empty());
@@ -3,6 +3,21 @@
 //! This crate implements the provider trait for OpenCode,
 //! handling per-message JSON files and session metadata.
 
+use ccstat_core::registry::{ProviderInfo, ReportType};
+
 pub mod data_loader;
 
 pub use data_loader::DataLoader;
+
+/// Registry entry for the OpenCode provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("opencode", "OpenCode usage data")
+        .with_env_vars(&["OPENCODE_DATA_DIR"])
+        .with_reports(&[
+            ReportType::Daily,
+            ReportType::Monthly,
+            ReportType::Weekly,
+            ReportType::Session,
+            ReportType::Blocks,
+        ])
+}
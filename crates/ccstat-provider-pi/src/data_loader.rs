@@ -6,7 +6,7 @@
 use async_trait::async_trait;
 use ccstat_core::error::{CcstatError, Result};
 use ccstat_core::provider::ProviderDataLoader;
-use ccstat_core::types::{ISOTimestamp, ModelName, SessionId, TokenCounts, UsageEntry};
+use ccstat_core::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry};
 use chrono::DateTime;
 use futures::stream::Stream;
 use serde::Deserialize;
@@ -17,12 +17,12 @@ use tracing::{debug, warn};
 
 /// Data loader for Pi usage data.
 pub struct DataLoader {
-    sessions_dir: PathBuf,
+    sessions_dirs: Vec<PathBuf>,
 }
 
 #[async_trait]
 impl ProviderDataLoader for DataLoader {
-    async fn new() -> Result<Self> {
+    fn default_roots() -> Result<Vec<PathBuf>> {
         let base = if let Ok(agent_dir) = std::env::var("PI_AGENT_DIR") {
             PathBuf::from(agent_dir)
         } else {
@@ -31,34 +31,39 @@ impl ProviderDataLoader for DataLoader {
                 .join(".pi")
                 .join("agent")
         };
+        Ok(vec![base])
+    }
 
-        let sessions_dir = base.join("sessions");
-        if !sessions_dir.exists() {
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        let sessions_dirs: Vec<_> = roots.iter().map(|root| root.join("sessions")).collect();
+        for sessions_dir in sessions_dirs.iter().filter(|dir| !dir.exists()) {
             debug!(
                 "Pi sessions directory not found: {}",
                 sessions_dir.display()
             );
         }
 
-        Ok(DataLoader { sessions_dir })
+        DataLoader { sessions_dirs }
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.sessions_dirs
     }
 
     fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
         Box::pin(async_stream::try_stream! {
-            if !self.sessions_dir.exists() {
-                return;
-            }
-
             let mut jsonl_files = Vec::new();
-            for entry in walkdir::WalkDir::new(&self.sessions_dir)
-                .min_depth(2)
-                .max_depth(2)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                let path = entry.path().to_path_buf();
-                if path.extension().is_some_and(|ext| ext == "jsonl") {
-                    jsonl_files.push(path);
+            for sessions_dir in self.sessions_dirs.iter().filter(|dir| dir.exists()) {
+                for entry in walkdir::WalkDir::new(sessions_dir)
+                    .min_depth(2)
+                    .max_depth(2)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                {
+                    let path = entry.path().to_path_buf();
+                    if path.extension().is_some_and(|ext| ext == "jsonl") {
+                        jsonl_files.push(path);
+                    }
                 }
             }
 
@@ -195,12 +200,7 @@ async fn parse_session_file(
             continue;
         };
 
-        // Add [pi] prefix to model name
-        let model_name = message
-            .model
-            .as_deref()
-            .map(|m| format!("[pi] {}", m))
-            .unwrap_or_else(|| "[pi] unknown".to_string());
+        let model_name = message.model.as_deref().unwrap_or("unknown");
 
         let total_cost = usage.cost.as_ref().and_then(|c| c.total);
 
@@ -217,6 +217,7 @@ async fn parse_session_file(
             total_cost,
             project: project.map(|s| s.to_string()),
             instance_id: None,
+            provider: ProviderId::new("pi"),
         });
     }
 
@@ -229,6 +230,7 @@ mod tests {
     use std::io::Write;
     use tempfile::TempDir;
 
+    #[allow(clippy::too_many_arguments)]
     fn make_pi_entry(
         ts: &str,
         role: &str,
@@ -278,7 +280,8 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(entries.len(), 1);
-        assert_eq!(entries[0].model.as_str(), "[pi] claude-opus-4");
+        assert_eq!(entries[0].model.as_str(), "claude-opus-4");
+        assert_eq!(entries[0].provider.as_str(), "pi");
         assert_eq!(entries[0].tokens.input_tokens, 500);
         assert_eq!(entries[0].tokens.output_tokens, 200);
         assert_eq!(entries[0].tokens.cache_read_tokens, 50);
@@ -364,13 +367,13 @@ mod tests {
         let entries = parse_session_file(&session_file, "sess3", Some("proj"))
             .await
             .unwrap();
-        assert_eq!(entries[0].model.as_str(), "[pi] claude-sonnet-4");
+        assert_eq!(entries[0].model.as_str(), "claude-sonnet-4");
     }
 
     #[tokio::test]
     async fn test_no_dir() {
         let loader = DataLoader {
-            sessions_dir: PathBuf::from("/tmp/nonexistent-pi-dir"),
+            sessions_dirs: vec![PathBuf::from("/tmp/nonexistent-pi-dir")],
         };
         let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries()).await;
         assert!(entries.is_empty());
@@ -1,8 +1,15 @@
 //! Pi provider for ccstat
 //!
 //! This crate implements the provider trait for Pi,
-//! handling JSONL session parsing with [pi] model prefix.
+//! handling JSONL session parsing.
+
+use ccstat_core::registry::ProviderInfo;
 
 pub mod data_loader;
 
 pub use data_loader::DataLoader;
+
+/// Registry entry for the Pi provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("pi", "Pi Agent usage data").with_env_vars(&["PI_AGENT_DIR"])
+}
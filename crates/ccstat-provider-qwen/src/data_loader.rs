@@ -0,0 +1,332 @@
+//! Qwen Code data loader
+//!
+//! Discovers and parses Qwen Code chat records from
+//! `~/.qwen/projects/{project}/chats/{session_id}.jsonl` (and the
+//! `~/.qwen/tmp/{project_hash}/chats/` layout inherited from Gemini CLI).
+//! Assistant records carry Gemini-style `usageMetadata` token counts and the
+//! working directory the session ran in.
+
+use async_trait::async_trait;
+use ccstat_core::error::{CcstatError, Result};
+use ccstat_core::provider::ProviderDataLoader;
+use ccstat_core::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry};
+use chrono::DateTime;
+use futures::stream::Stream;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tracing::{debug, warn};
+
+/// Data loader for Qwen Code usage data.
+pub struct DataLoader {
+    qwen_dirs: Vec<PathBuf>,
+}
+
+#[async_trait]
+impl ProviderDataLoader for DataLoader {
+    /// Qwen Code's free tier quotas reset daily.
+    const DEFAULT_BLOCK_HOURS: f64 = 24.0;
+
+    fn default_roots() -> Result<Vec<PathBuf>> {
+        let qwen_dir = if let Ok(qwen_dir) = std::env::var("QWEN_DIR") {
+            PathBuf::from(qwen_dir)
+        } else {
+            dirs::home_dir()
+                .ok_or_else(|| CcstatError::Config("Cannot determine home directory".into()))?
+                .join(".qwen")
+        };
+        Ok(vec![qwen_dir])
+    }
+
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        for qwen_dir in roots.iter().filter(|dir| !dir.exists()) {
+            debug!("Qwen directory not found: {}", qwen_dir.display());
+        }
+
+        DataLoader { qwen_dirs: roots }
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.qwen_dirs
+    }
+
+    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+        Box::pin(async_stream::try_stream! {
+            let mut jsonl_files = Vec::new();
+            for qwen_dir in &self.qwen_dirs {
+                for root in ["projects", "tmp"] {
+                    let root = qwen_dir.join(root);
+                    if !root.exists() {
+                        continue;
+                    }
+                    for entry in walkdir::WalkDir::new(&root)
+                        .min_depth(3)
+                        .max_depth(3)
+                        .sort_by_file_name()
+                        .into_iter()
+                        .filter_map(|e| e.ok())
+                    {
+                        let path = entry.path().to_path_buf();
+                        if is_chat_file(&path) {
+                            jsonl_files.push(path);
+                        }
+                    }
+                }
+            }
+
+            debug!("Found {} Qwen chat files", jsonl_files.len());
+
+            let mut seen_ids = HashSet::new();
+
+            for path in jsonl_files {
+                match parse_chat_file(&path, &mut seen_ids).await {
+                    Ok(parsed) => {
+                        for entry in parsed {
+                            yield entry;
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to parse Qwen chat {}: {}", path.display(), e);
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Chat records live in `{project}/chats/` as JSONL
+fn is_chat_file(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "jsonl")
+        && path
+            .parent()
+            .and_then(|p| p.file_name())
+            .is_some_and(|name| name == "chats")
+}
+
+// ---------------------------------------------------------------------------
+// Record schema
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChatRecord {
+    #[serde(default)]
+    uuid: Option<String>,
+    #[serde(default)]
+    session_id: Option<String>,
+    #[serde(default)]
+    timestamp: Option<String>,
+    #[serde(rename = "type", default)]
+    record_type: Option<String>,
+    #[serde(default)]
+    cwd: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    /// Prompt tokens, including the cached portion
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
+    #[serde(default)]
+    cached_content_token_count: u64,
+    #[serde(default)]
+    thoughts_token_count: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+async fn parse_chat_file(path: &Path, seen_ids: &mut HashSet<String>) -> Result<Vec<UsageEntry>> {
+    let file = tokio::fs::File::open(path).await.map_err(|e| {
+        CcstatError::Io(std::io::Error::new(
+            e.kind(),
+            format!("{}: {}", path.display(), e),
+        ))
+    })?;
+    let mut lines = BufReader::new(file).lines();
+
+    let fallback_session_id = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let mut entries = Vec::new();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: ChatRecord = match serde_json::from_str(&line) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+
+        if record.record_type.as_deref() != Some("assistant") {
+            continue;
+        }
+        let Some(usage) = &record.usage_metadata else {
+            continue;
+        };
+
+        // Skip zero-token records
+        if usage.prompt_token_count == 0
+            && usage.candidates_token_count == 0
+            && usage.thoughts_token_count == 0
+        {
+            continue;
+        }
+
+        // Resumed sessions replay earlier records
+        if let Some(uuid) = &record.uuid
+            && !seen_ids.insert(uuid.clone())
+        {
+            continue;
+        }
+
+        let timestamp = record
+            .timestamp
+            .as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| ISOTimestamp::new(dt.to_utc()));
+
+        let Some(timestamp) = timestamp else {
+            continue;
+        };
+
+        let session_id = record
+            .session_id
+            .clone()
+            .unwrap_or_else(|| fallback_session_id.clone());
+
+        entries.push(UsageEntry {
+            session_id: SessionId::new(session_id),
+            timestamp,
+            model: ModelName::new(normalize_model(
+                record.model.as_deref().unwrap_or("unknown"),
+            )),
+            tokens: TokenCounts::new(
+                usage
+                    .prompt_token_count
+                    .saturating_sub(usage.cached_content_token_count),
+                usage.candidates_token_count,
+                0,
+                usage.cached_content_token_count,
+            )
+            .with_reasoning_tokens(usage.thoughts_token_count),
+            total_cost: None,
+            project: record.cwd.as_deref().and_then(UsageEntry::project_from_cwd),
+            instance_id: None,
+            provider: ProviderId::new("qwen"),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Normalize Qwen model names.
+///
+/// OpenAI-compatible endpoints report models as `vendor/model`; pricing
+/// data is keyed by the bare, lowercase model name.
+fn normalize_model(model: &str) -> String {
+    let model = model.rsplit('/').next().unwrap_or(model);
+    model.to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_record(uuid: &str, record_type: &str, model: &str, prompt: u64, cached: u64) -> String {
+        format!(
+            r#"{{"uuid":"{uuid}","sessionId":"sess-1","timestamp":"2025-01-01T10:00:00Z","type":"{record_type}","cwd":"/home/me/qwen-app","model":"{model}","usageMetadata":{{"promptTokenCount":{prompt},"candidatesTokenCount":80,"cachedContentTokenCount":{cached},"thoughtsTokenCount":20,"totalTokenCount":{}}}}}"#,
+            prompt + 100
+        )
+    }
+
+    fn write_chat(dir: &Path, layout: &str, project: &str, name: &str, lines: &[String]) {
+        let chats = dir.join(layout).join(project).join("chats");
+        std::fs::create_dir_all(&chats).unwrap();
+        std::fs::write(chats.join(name), lines.join("\n")).unwrap();
+    }
+
+    async fn load(qwen_dir: PathBuf) -> Vec<UsageEntry> {
+        let loader = DataLoader {
+            qwen_dirs: vec![qwen_dir],
+        };
+        futures::StreamExt::collect::<Vec<_>>(loader.load_entries())
+            .await
+            .into_iter()
+            .map(|e| e.unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_parse_assistant_records() {
+        let dir = TempDir::new().unwrap();
+        write_chat(
+            dir.path(),
+            "projects",
+            "-home-me-qwen-app",
+            "sess-1.jsonl",
+            &[
+                make_record("u-1", "user", "qwen3-coder-plus", 0, 0),
+                make_record("a-1", "assistant", "Qwen/Qwen3-Coder-Plus", 500, 200),
+            ],
+        );
+
+        let entries = load(dir.path().to_path_buf()).await;
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.session_id.as_str(), "sess-1");
+        assert_eq!(entry.model.as_str(), "qwen3-coder-plus");
+        assert_eq!(entry.provider.as_str(), "qwen");
+        assert_eq!(entry.project.as_deref(), Some("qwen-app"));
+        assert_eq!(entry.tokens.input_tokens, 300);
+        assert_eq!(entry.tokens.output_tokens, 80);
+        assert_eq!(entry.tokens.reasoning_tokens, 20);
+        assert_eq!(entry.tokens.cache_read_tokens, 200);
+        assert_eq!(entry.total_cost, None);
+    }
+
+    #[tokio::test]
+    async fn test_tmp_layout_and_dedup() {
+        let dir = TempDir::new().unwrap();
+        let record = make_record("a-1", "assistant", "qwen3-coder-plus", 100, 0);
+        // The same record in both layouts (e.g. after a migration) counts once
+        write_chat(
+            dir.path(),
+            "projects",
+            "p",
+            "sess-1.jsonl",
+            std::slice::from_ref(&record),
+        );
+        write_chat(dir.path(), "tmp", "hash", "sess-1.jsonl", &[record]);
+
+        assert_eq!(load(dir.path().to_path_buf()).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_model_normalization() {
+        assert_eq!(normalize_model("qwen3-coder-plus"), "qwen3-coder-plus");
+        assert_eq!(normalize_model("Qwen/Qwen3-Coder-480B"), "qwen3-coder-480b");
+    }
+
+    #[tokio::test]
+    async fn test_no_dir() {
+        let entries = load(PathBuf::from("/tmp/nonexistent-qwen-dir")).await;
+        assert!(entries.is_empty());
+    }
+}
@@ -0,0 +1,17 @@
+//! Qwen Code provider for ccstat
+//!
+//! This crate implements the provider trait for Qwen Code,
+//! handling JSONL chat record parsing.
+
+use ccstat_core::registry::ProviderInfo;
+
+pub mod data_loader;
+
+pub use data_loader::DataLoader;
+
+/// Registry entry for the Qwen Code provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("qwen", "Qwen Code usage data")
+        .with_aliases(&["qwen-code"])
+        .with_env_vars(&["QWEN_DIR"])
+}
@@ -0,0 +1,82 @@
+//! Roo Code data loader
+//!
+//! Reads Roo Code task histories from VS Code's global storage,
+//! `<config dir>/Code/User/globalStorage/rooveterinaryinc.roo-cline/tasks/`.
+//! Roo Code is a fork of Cline and keeps the same task files, so parsing is
+//! shared with [`ccstat_provider_cline::tasks`].
+
+use async_trait::async_trait;
+use ccstat_core::error::Result;
+use ccstat_core::provider::ProviderDataLoader;
+use ccstat_core::types::UsageEntry;
+use ccstat_provider_cline::tasks;
+use futures::stream::Stream;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+/// Marketplace id of the Roo Code extension
+const EXTENSION_ID: &str = "rooveterinaryinc.roo-cline";
+
+/// Data loader for Roo Code usage data.
+pub struct DataLoader {
+    storage_dirs: Vec<PathBuf>,
+}
+
+#[async_trait]
+impl ProviderDataLoader for DataLoader {
+    fn default_roots() -> Result<Vec<PathBuf>> {
+        tasks::storage_dirs(
+            std::env::var_os("ROO_CODE_STORAGE_DIR").map(PathBuf::from),
+            EXTENSION_ID,
+        )
+    }
+
+    fn from_roots(roots: Vec<PathBuf>) -> Self {
+        DataLoader {
+            storage_dirs: roots,
+        }
+    }
+
+    fn paths(&self) -> &[PathBuf] {
+        &self.storage_dirs
+    }
+
+    fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+        tasks::load_tasks(&self.storage_dirs, "roo")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_roo_task() {
+        let dir = TempDir::new().unwrap();
+        let task_dir = dir.path().join("tasks").join("0b6c4f1e-roo-task");
+        std::fs::create_dir_all(&task_dir).unwrap();
+        std::fs::write(
+            task_dir.join("ui_messages.json"),
+            r#"[{"ts":1735725600000,"type":"say","say":"api_req_started","text":"{\"apiProtocol\":\"anthropic\",\"tokensIn\":900,\"tokensOut\":120,\"cacheWrites\":0,\"cacheReads\":4000,\"cost\":0.0081}"}]"#,
+        )
+        .unwrap();
+        std::fs::write(
+            task_dir.join("api_conversation_history.json"),
+            r#"[{"role":"user","content":[{"type":"text","text":"<environment_details>\n# Current Workspace Directory (/home/me/roo-project) Files\n</environment_details>"}]}]"#,
+        )
+        .unwrap();
+
+        let loader = DataLoader {
+            storage_dirs: vec![dir.path().to_path_buf()],
+        };
+        let entries: Vec<_> = futures::StreamExt::collect::<Vec<_>>(loader.load_entries()).await;
+        assert_eq!(entries.len(), 1);
+        let entry = entries[0].as_ref().unwrap();
+        assert_eq!(entry.session_id.as_str(), "0b6c4f1e-roo-task");
+        assert_eq!(entry.project.as_deref(), Some("roo-project"));
+        assert_eq!(entry.provider.as_str(), "roo");
+        assert_eq!(entry.tokens.cache_read_tokens, 4000);
+        assert_eq!(entry.total_cost, Some(0.0081));
+    }
+}
@@ -0,0 +1,17 @@
+//! Roo Code provider for ccstat
+//!
+//! This crate implements the provider trait for the Roo Code VS Code
+//! extension, reusing Cline's task history parsing.
+
+use ccstat_core::registry::ProviderInfo;
+
+pub mod data_loader;
+
+pub use data_loader::DataLoader;
+
+/// Registry entry for the Roo Code provider
+pub fn provider_info() -> ProviderInfo {
+    ProviderInfo::new::<DataLoader>("roo", "Roo Code (VS Code extension) usage data")
+        .with_aliases(&["roo-code"])
+        .with_env_vars(&["ROO_CODE_STORAGE_DIR"])
+}
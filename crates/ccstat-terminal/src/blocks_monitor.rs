@@ -5,6 +5,7 @@
 //! and usage projections.
 
 use ccstat_core::aggregation_types::SessionBlock;
+use ccstat_core::currency::Currency;
 use ccstat_core::model_formatter::format_model_name;
 use chrono::{DateTime, Duration, Utc};
 use colored::*;
@@ -33,6 +34,19 @@ const PROJECTION_EXCEED_THRESHOLD: f64 = 100.0;
 /// Threshold for approaching projection limit (percentage)
 const PROJECTION_APPROACHING_THRESHOLD: f64 = 80.0;
 
+/// Characters cost amounts are clamped and padded to
+const COST_WIDTH: usize = 8;
+
+/// Characters burn rate amounts are clamped to
+const BURN_RATE_WIDTH: usize = 7;
+
+/// Largest amount that fits in `width` characters with `decimals` decimals
+fn max_amount(width: usize, decimals: usize) -> f64 {
+    let point = usize::from(decimals > 0);
+    let integer_digits = width.saturating_sub(decimals + point);
+    10f64.powi(integer_digits as i32) - 10f64.powi(-(decimals as i32))
+}
+
 /// Enhanced display for billing blocks
 pub struct BlocksMonitor {
     width: usize,
@@ -40,6 +54,8 @@ pub struct BlocksMonitor {
     max_historical_cost: f64,
     /// Whether to use colored output (respects NO_COLOR environment variable)
     colored_output: bool,
+    /// Currency costs are displayed in
+    currency: Currency,
 }
 
 impl BlocksMonitor {
@@ -62,9 +78,43 @@ impl BlocksMonitor {
             timezone,
             max_historical_cost,
             colored_output,
+            currency: Currency::usd(),
         }
     }
 
+    /// Display costs in `currency`
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    /// Format a USD cost in the display currency, with the amount clamped and
+    /// padded to 8 characters so the layout holds in any currency
+    fn format_cost(&self, cost: f64) -> String {
+        let decimals = self.currency.decimals();
+        let amount = self
+            .currency
+            .convert(cost)
+            .min(max_amount(COST_WIDTH, decimals));
+        format!(
+            "{}{:>width$.decimals$}",
+            self.currency.symbol(),
+            amount,
+            width = COST_WIDTH,
+        )
+    }
+
+    /// Format a USD burn rate per minute in the display currency, with one
+    /// more decimal than costs and the amount clamped to 7 characters
+    fn format_burn_rate(&self, burn_rate: f64) -> String {
+        let decimals = self.currency.decimals() + 1;
+        let amount = self
+            .currency
+            .convert(burn_rate)
+            .min(max_amount(BURN_RATE_WIDTH, decimals));
+        format!("{}{:.decimals$}", self.currency.symbol(), amount)
+    }
+
     /// Render the active block with enhanced UI
     pub fn render_active_block(&self, block: &SessionBlock, now: DateTime<Utc>) -> String {
         let mut output = String::new();
@@ -82,7 +132,7 @@ impl BlocksMonitor {
         // Calculate burn rate and projection based on cost
         // Note: For blocks with less than one minute elapsed, burn rate may be overestimated.
         // To avoid this, use seconds for very short elapsed times.
-        let current_cost = block.total_cost;
+        let current_cost = block.total_cost.to_usd();
         let elapsed_minutes = elapsed.num_minutes();
         let burn_rate = if elapsed_minutes < 1 && elapsed.num_seconds() > 0 {
             // Use seconds for more accurate burn rate when elapsed time is less than a minute
@@ -284,20 +334,20 @@ impl BlocksMonitor {
         let bar = self.create_colored_progress_bar(usage_percentage, 40, status_color);
         // Use fixed width for cost values to prevent layout issues
         let usage_line = format!(
-            "USAGE        {}  {:5.1}% (${:8.2}/${:8.2})",
+            "USAGE        {}  {:5.1}% ({}/{})",
             bar,
             usage_percentage.min(999.9),
-            current_cost.min(99999.99),
-            self.max_historical_cost.min(99999.99)
+            self.format_cost(current_cost),
+            self.format_cost(self.max_historical_cost)
         );
         output.push_str(&self.draw_line(&usage_line));
 
         // Burn rate and tokens
         let burn_status = self.get_burn_status(burn_rate);
         let detail_line = format!(
-            "   Cost: ${:8.2}  (Burn: ${:.3}/min {})  Tokens: {}",
-            current_cost.min(99999.99),
-            burn_rate.min(999.999),
+            "   Cost: {}  (Burn: {}/min {})  Tokens: {}",
+            self.format_cost(current_cost),
+            self.format_burn_rate(burn_rate),
             burn_status,
             self.format_number(total_tokens as u64)
         );
@@ -319,11 +369,11 @@ impl BlocksMonitor {
         let bar = self.create_colored_progress_bar(projection_percentage, 40, status_color);
         // Use fixed width for cost values to prevent layout issues
         let projection_line = format!(
-            "PROJECTION   {}  {:5.1}% (${:8.2}/${:8.2})",
+            "PROJECTION   {}  {:5.1}% ({}/{})",
             bar,
             projection_percentage.min(999.9),
-            projected_cost.min(99999.99),
-            self.max_historical_cost.min(99999.99)
+            self.format_cost(projected_cost),
+            self.format_cost(self.max_historical_cost)
         );
         output.push_str(&self.draw_line(&projection_line));
 
@@ -347,9 +397,9 @@ impl BlocksMonitor {
         };
 
         let status_line = format!(
-            "   Status: {}  Projected Cost: ${:8.2}",
+            "   Status: {}  Projected Cost: {}",
             status,
-            projected_cost.min(99999.99)
+            self.format_cost(projected_cost)
         );
         output.push_str(&self.draw_line(&status_line));
 
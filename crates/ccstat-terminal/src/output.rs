@@ -9,6 +9,8 @@
 //! ```no_run
 //! use ccstat_terminal::output::get_formatter;
 //! use ccstat_core::aggregation_types::{DailyUsage, Totals};
+//! use ccstat_core::cost::Cost;
+//! use ccstat_core::currency::Currency;
 //! use ccstat_core::types::{DailyDate, TokenCounts};
 //! use chrono::NaiveDate;
 //!
@@ -16,8 +18,9 @@
 //!     DailyUsage {
 //!         date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
 //!         tokens: TokenCounts::new(1000, 500, 100, 50),
-//!         total_cost: 0.025,
+//!         total_cost: Cost::from_usd(0.025),
 //!         models_used: vec!["claude-3-opus".to_string()],
+//!         model_breakdowns: Vec::new(),
 //!         entries: None,
 //!     },
 //! ];
@@ -25,18 +28,22 @@
 //! let totals = Totals::from_daily(&daily_data);
 //!
 //! // Get table formatter for human-readable output
-//! let formatter = get_formatter(false, false);
+//! let formatter = get_formatter(false, false, Currency::usd());
 //! println!("{}", formatter.format_daily(&daily_data, &totals));
 //!
 //! // Get JSON formatter for machine-readable output
-//! let json_formatter = get_formatter(true, false);
+//! let json_formatter = get_formatter(true, false, Currency::usd());
 //! println!("{}", json_formatter.format_daily(&daily_data, &totals));
 //! ```
 
 use ccstat_core::aggregation_types::{
-    DailyInstanceUsage, DailyUsage, MonthlyUsage, SessionBlock, SessionUsage, Totals, WeeklyUsage,
+    DailyInstanceUsage, DailyUsage, ModelBreakdown, MonthlyUsage, ProviderUsage, SessionBlock,
+    SessionUsage, Totals, WeeklyUsage,
 };
+use ccstat_core::cost::Cost;
+use ccstat_core::currency::Currency;
 use ccstat_core::model_formatter::{format_model_list, format_model_name};
+use ccstat_core::types::{RateLimitSnapshot, RateLimitWindow, TokenCounts};
 use prettytable::{Cell, Row, Table, format, row};
 use serde_json::json;
 
@@ -49,13 +56,14 @@ use serde_json::json;
 ///
 /// ```
 /// use ccstat_terminal::output::OutputFormatter;
-/// use ccstat_core::aggregation_types::{DailyUsage, DailyInstanceUsage, SessionUsage, MonthlyUsage, WeeklyUsage, SessionBlock, Totals};
+/// use ccstat_core::aggregation_types::{DailyUsage, DailyInstanceUsage, SessionUsage, MonthlyUsage, WeeklyUsage, SessionBlock, ProviderUsage, Totals};
+/// use ccstat_core::types::RateLimitSnapshot;
 ///
 /// struct CustomFormatter;
 ///
 /// impl OutputFormatter for CustomFormatter {
 ///     fn format_daily(&self, data: &[DailyUsage], totals: &Totals) -> String {
-///         format!("Total days: {}, Total cost: ${:.2}", data.len(), totals.total_cost)
+///         format!("Total days: {}, Total cost: ${:.2}", data.len(), totals.total_cost.to_usd())
 ///     }
 ///
 ///     fn format_daily_by_instance(&self, data: &[DailyInstanceUsage], totals: &Totals) -> String {
@@ -77,6 +85,26 @@ use serde_json::json;
 ///     fn format_blocks(&self, data: &[SessionBlock], _tz: &chrono_tz::Tz) -> String {
 ///         format!("Total blocks: {}", data.len())
 ///     }
+///
+///     fn format_daily_by_provider(&self, data: &[ProviderUsage<DailyUsage>], totals: &Totals) -> String {
+///         format!("Total providers: {}", data.len())
+///     }
+///
+///     fn format_sessions_by_provider(&self, data: &[ProviderUsage<SessionUsage>], totals: &Totals, _tz: &chrono_tz::Tz) -> String {
+///         format!("Total providers: {}", data.len())
+///     }
+///
+///     fn format_monthly_by_provider(&self, data: &[ProviderUsage<MonthlyUsage>], totals: &Totals) -> String {
+///         format!("Total providers: {}", data.len())
+///     }
+///
+///     fn format_weekly_by_provider(&self, data: &[ProviderUsage<WeeklyUsage>], totals: &Totals) -> String {
+///         format!("Total providers: {}", data.len())
+///     }
+///
+///     fn format_rate_limits(&self, snapshot: Option<&RateLimitSnapshot>, _tz: &chrono_tz::Tz) -> String {
+///         format!("Has rate limits: {}", snapshot.is_some())
+///     }
 /// }
 /// ```
 pub trait OutputFormatter {
@@ -98,22 +126,69 @@ pub trait OutputFormatter {
 
     /// Format billing blocks (5-hour windows)
     fn format_blocks(&self, data: &[SessionBlock], tz: &chrono_tz::Tz) -> String;
+
+    /// Format daily usage from several providers with per-provider subtotals
+    fn format_daily_by_provider(
+        &self,
+        data: &[ProviderUsage<DailyUsage>],
+        totals: &Totals,
+    ) -> String;
+
+    /// Format session usage from several providers with per-provider subtotals
+    fn format_sessions_by_provider(
+        &self,
+        data: &[ProviderUsage<SessionUsage>],
+        totals: &Totals,
+        tz: &chrono_tz::Tz,
+    ) -> String;
+
+    /// Format monthly usage from several providers with per-provider subtotals
+    fn format_monthly_by_provider(
+        &self,
+        data: &[ProviderUsage<MonthlyUsage>],
+        totals: &Totals,
+    ) -> String;
+
+    /// Format weekly usage from several providers with per-provider subtotals
+    fn format_weekly_by_provider(
+        &self,
+        data: &[ProviderUsage<WeeklyUsage>],
+        totals: &Totals,
+    ) -> String;
+
+    /// Format the latest rate-limit snapshot, if any
+    fn format_rate_limits(
+        &self,
+        snapshot: Option<&RateLimitSnapshot>,
+        tz: &chrono_tz::Tz,
+    ) -> String;
 }
 
 /// Table formatter for human-readable output
 ///
 /// Produces nicely formatted ASCII tables suitable for terminal display.
 /// Numbers are formatted with thousands separators and costs are shown
-/// with dollar signs for clarity.
+/// with the display currency's symbol, in USD unless set otherwise.
 pub struct TableFormatter {
     /// Whether to show full model names or shortened versions
     pub full_model_names: bool,
+    /// Currency costs are shown in
+    currency: Currency,
 }
 
 impl TableFormatter {
     /// Create a new TableFormatter
     pub fn new(full_model_names: bool) -> Self {
-        Self { full_model_names }
+        Self {
+            full_model_names,
+            currency: Currency::usd(),
+        }
+    }
+
+    /// Show costs converted to `currency`
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
     }
 
     /// Format a number with thousands separators
@@ -131,25 +206,116 @@ impl TableFormatter {
         result.chars().rev().collect()
     }
 
-    /// Format currency with dollar sign
-    fn format_currency(amount: f64) -> String {
-        format!("${amount:.2}")
+    /// Format a cost in the display currency
+    fn format_currency(&self, cost: Cost) -> String {
+        self.currency.format(cost.to_usd())
     }
 
     /// Create a totals row for tables
-    fn format_totals_row(totals: &Totals) -> Row {
+    fn format_totals_row(&self, totals: &Totals) -> Row {
         row![
             b -> "TOTAL",
             b -> Self::format_number(totals.tokens.input_tokens),
             b -> Self::format_number(totals.tokens.output_tokens),
+            b -> Self::format_number(totals.tokens.reasoning_tokens),
             b -> Self::format_number(totals.tokens.cache_creation_tokens),
             b -> Self::format_number(totals.tokens.cache_read_tokens),
             b -> Self::format_number(totals.tokens.total()),
-            b -> Self::format_currency(totals.total_cost),
+            b -> self.format_currency(totals.total_cost),
             ""
         ]
     }
 
+    /// Build a period table (daily, weekly, monthly) with a provider column
+    ///
+    /// Rows are ordered by period and then by provider, followed by one
+    /// subtotal row per provider and the grand total.
+    fn format_provider_period_table(
+        &self,
+        period_title: &str,
+        last_title: &str,
+        rows: Vec<ProviderPeriodRow<'_>>,
+        subtotals: Vec<(&str, &Totals)>,
+        totals: &Totals,
+    ) -> String {
+        let mut rows = rows;
+        // Stable sort keeps provider order within a period
+        rows.sort_by(|a, b| a.period.cmp(&b.period));
+
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        table.set_titles(row![
+            b -> period_title,
+            b -> "Provider",
+            b -> "Input",
+            b -> "Output",
+            b -> "Reasoning",
+            b -> "Cache Create",
+            b -> "Cache Read",
+            b -> "Total",
+            b -> "Cost",
+            b -> last_title
+        ]);
+
+        for entry in rows {
+            table.add_row(row![
+                entry.period,
+                entry.provider,
+                r -> Self::format_number(entry.tokens.input_tokens),
+                r -> Self::format_number(entry.tokens.output_tokens),
+                r -> Self::format_number(entry.tokens.reasoning_tokens),
+                r -> Self::format_number(entry.tokens.cache_creation_tokens),
+                r -> Self::format_number(entry.tokens.cache_read_tokens),
+                r -> Self::format_number(entry.tokens.total()),
+                r -> self.format_currency(entry.cost),
+                entry.last
+            ]);
+        }
+
+        // Add separator
+        table.add_row(Row::new(vec![Cell::new(""); 10]));
+
+        for (provider, subtotal) in subtotals {
+            table.add_row(self.format_subtotal_row(provider, subtotal, 10));
+        }
+
+        // Add separator
+        table.add_row(Row::new(vec![Cell::new(""); 10]));
+
+        table.add_row(row![
+            b -> "TOTAL",
+            "",
+            b -> Self::format_number(totals.tokens.input_tokens),
+            b -> Self::format_number(totals.tokens.output_tokens),
+            b -> Self::format_number(totals.tokens.reasoning_tokens),
+            b -> Self::format_number(totals.tokens.cache_creation_tokens),
+            b -> Self::format_number(totals.tokens.cache_read_tokens),
+            b -> Self::format_number(totals.tokens.total()),
+            b -> self.format_currency(totals.total_cost),
+            ""
+        ]);
+
+        table.to_string()
+    }
+
+    /// Create a per-provider subtotal row for period tables
+    fn format_subtotal_row(&self, provider: &str, subtotal: &Totals, columns: usize) -> Row {
+        let mut cells = vec![
+            Cell::new("Subtotal"),
+            Cell::new(provider),
+            Cell::new(&Self::format_number(subtotal.tokens.input_tokens)).style_spec("r"),
+            Cell::new(&Self::format_number(subtotal.tokens.output_tokens)).style_spec("r"),
+            Cell::new(&Self::format_number(subtotal.tokens.reasoning_tokens)).style_spec("r"),
+            Cell::new(&Self::format_number(subtotal.tokens.cache_creation_tokens)).style_spec("r"),
+            Cell::new(&Self::format_number(subtotal.tokens.cache_read_tokens)).style_spec("r"),
+            Cell::new(&Self::format_number(subtotal.tokens.total())).style_spec("r"),
+            Cell::new(&self.format_currency(subtotal.total_cost)).style_spec("r"),
+        ];
+        cells.resize(columns, Cell::new(""));
+        Row::new(cells)
+    }
+
     /// Format a datetime with the specified timezone
     fn format_datetime_with_tz(dt: &chrono::DateTime<chrono::Utc>, tz: &chrono_tz::Tz) -> String {
         dt.with_timezone(tz).format("%Y-%m-%d %H:%M %Z").to_string()
@@ -170,6 +336,77 @@ impl TableFormatter {
         }
     }
 
+    /// Format a rate-limit window length as days, hours, or minutes
+    fn format_window_length(minutes: u64) -> String {
+        if minutes > 0 && minutes.is_multiple_of(24 * 60) {
+            format!("{}d", minutes / (24 * 60))
+        } else if minutes > 0 && minutes.is_multiple_of(60) {
+            format!("{}h", minutes / 60)
+        } else {
+            format!("{}m", minutes)
+        }
+    }
+
+    /// Format rate limits with custom current time (for testing)
+    pub(crate) fn format_rate_limits_with_now(
+        &self,
+        snapshot: Option<&RateLimitSnapshot>,
+        tz: &chrono_tz::Tz,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> String {
+        let Some(snapshot) = snapshot else {
+            return "No rate limit data found".to_string();
+        };
+
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+        table.set_titles(row![
+            b -> "Window",
+            b -> "Used",
+            b -> "Length",
+            b -> "Resets At",
+            b -> "Resets In"
+        ]);
+
+        let windows: [(&str, Option<&RateLimitWindow>); 2] = [
+            ("Primary", snapshot.primary.as_ref()),
+            ("Secondary", snapshot.secondary.as_ref()),
+        ];
+        for (name, window) in windows {
+            let Some(window) = window else {
+                continue;
+            };
+            let (resets_at, resets_in) = match window.resets_at {
+                Some(reset) if reset > now => (
+                    Self::format_datetime_with_tz(&reset, tz),
+                    Self::format_duration(reset - now),
+                ),
+                Some(reset) => (
+                    Self::format_datetime_with_tz(&reset, tz),
+                    "Expired".to_string(),
+                ),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            table.add_row(row![
+                name,
+                r -> format!("{:.1}%", window.used_percent),
+                r -> window
+                    .window_minutes
+                    .map(Self::format_window_length)
+                    .unwrap_or_else(|| "-".to_string()),
+                resets_at,
+                resets_in
+            ]);
+        }
+
+        format!(
+            "Rate limits as of {} (session {})\n{}",
+            Self::format_datetime_with_tz(&snapshot.timestamp, tz),
+            snapshot.session_id,
+            table
+        )
+    }
+
     /// Format blocks with custom current time (for testing)
     pub(crate) fn format_blocks_with_now(
         &self,
@@ -254,7 +491,7 @@ impl TableFormatter {
                 r -> Self::format_number(block.tokens.input_tokens),
                 r -> Self::format_number(block.tokens.output_tokens),
                 r -> Self::format_number(block.tokens.total()),
-                r -> Self::format_currency(block.total_cost),
+                r -> self.format_currency(block.total_cost),
                 time_remaining
             ]);
         }
@@ -286,6 +523,7 @@ impl OutputFormatter for TableFormatter {
                         b -> "Model",
                         b -> "Input",
                         b -> "Output",
+                        b -> "Reasoning",
                         b -> "Cache Create",
                         b -> "Cache Read",
                         b -> "Total",
@@ -299,10 +537,11 @@ impl OutputFormatter for TableFormatter {
                             format_model_name(&entry.model, self.full_model_names),
                             r -> Self::format_number(entry.tokens.input_tokens),
                             r -> Self::format_number(entry.tokens.output_tokens),
+                            r -> Self::format_number(entry.tokens.reasoning_tokens),
                             r -> Self::format_number(entry.tokens.cache_creation_tokens),
                             r -> Self::format_number(entry.tokens.cache_read_tokens),
                             r -> Self::format_number(entry.tokens.total()),
-                            r -> Self::format_currency(entry.cost)
+                            r -> self.format_currency(entry.cost)
                         ]);
                     }
 
@@ -313,7 +552,7 @@ impl OutputFormatter for TableFormatter {
                 output.push_str(&format!(
                     "\nDay Total: {} tokens, {}\n",
                     Self::format_number(daily.tokens.total()),
-                    Self::format_currency(daily.total_cost)
+                    self.format_currency(daily.total_cost)
                 ));
             }
 
@@ -329,8 +568,10 @@ impl OutputFormatter for TableFormatter {
             b -> "Date",
             b -> "Input",
             b -> "Output",
+            b -> "Reasoning",
             b -> "Cache Create",
             b -> "Cache Read",
+            b -> "Web Search",
             b -> "Total",
             b -> "Cost",
             b -> "Models"
@@ -341,19 +582,32 @@ impl OutputFormatter for TableFormatter {
                 entry.date.format("%Y-%m-%d"),
                 r -> Self::format_number(entry.tokens.input_tokens),
                 r -> Self::format_number(entry.tokens.output_tokens),
+                r -> Self::format_number(entry.tokens.reasoning_tokens),
                 r -> Self::format_number(entry.tokens.cache_creation_tokens),
                 r -> Self::format_number(entry.tokens.cache_read_tokens),
+                r -> Self::format_number(entry.tokens.web_search_requests),
                 r -> Self::format_number(entry.tokens.total()),
-                r -> Self::format_currency(entry.total_cost),
+                r -> self.format_currency(entry.total_cost),
                 format_model_list(&entry.models_used, self.full_model_names, ", ")
             ]);
         }
 
         // Add separator
-        table.add_row(Row::new(vec![Cell::new(""); 8]));
+        table.add_row(Row::new(vec![Cell::new(""); 10]));
 
         // Add totals row
-        table.add_row(Self::format_totals_row(totals));
+        table.add_row(row![
+            b -> "TOTAL",
+            b -> Self::format_number(totals.tokens.input_tokens),
+            b -> Self::format_number(totals.tokens.output_tokens),
+            b -> Self::format_number(totals.tokens.reasoning_tokens),
+            b -> Self::format_number(totals.tokens.cache_creation_tokens),
+            b -> Self::format_number(totals.tokens.cache_read_tokens),
+            b -> Self::format_number(totals.tokens.web_search_requests),
+            b -> Self::format_number(totals.tokens.total()),
+            b -> self.format_currency(totals.total_cost),
+            ""
+        ]);
 
         output.push_str(&table.to_string());
         output
@@ -368,6 +622,7 @@ impl OutputFormatter for TableFormatter {
             b -> "Instance",
             b -> "Input",
             b -> "Output",
+            b -> "Reasoning",
             b -> "Cache Create",
             b -> "Cache Read",
             b -> "Total Tokens",
@@ -381,16 +636,17 @@ impl OutputFormatter for TableFormatter {
                 entry.instance_id,
                 r -> Self::format_number(entry.tokens.input_tokens),
                 r -> Self::format_number(entry.tokens.output_tokens),
+                r -> Self::format_number(entry.tokens.reasoning_tokens),
                 r -> Self::format_number(entry.tokens.cache_creation_tokens),
                 r -> Self::format_number(entry.tokens.cache_read_tokens),
                 r -> Self::format_number(entry.tokens.total()),
-                r -> Self::format_currency(entry.total_cost),
+                r -> self.format_currency(entry.total_cost),
                 format_model_list(&entry.models_used, self.full_model_names, ", ")
             ]);
         }
 
         // Add separator
-        table.add_row(Row::new(vec![Cell::new(""); 9]));
+        table.add_row(Row::new(vec![Cell::new(""); 10]));
 
         // Add totals row with extra column for instance
         table.add_row(row![
@@ -398,10 +654,11 @@ impl OutputFormatter for TableFormatter {
             "",
             b -> Self::format_number(totals.tokens.input_tokens),
             b -> Self::format_number(totals.tokens.output_tokens),
+            b -> Self::format_number(totals.tokens.reasoning_tokens),
             b -> Self::format_number(totals.tokens.cache_creation_tokens),
             b -> Self::format_number(totals.tokens.cache_read_tokens),
             b -> Self::format_number(totals.tokens.total()),
-            b -> Self::format_currency(totals.total_cost),
+            b -> self.format_currency(totals.total_cost),
             ""
         ]);
 
@@ -423,6 +680,8 @@ impl OutputFormatter for TableFormatter {
             b -> "Duration",
             b -> "Input",
             b -> "Output",
+            b -> "Reasoning",
+            b -> "Web Search",
             b -> "Total Tokens",
             b -> "Cost",
             b -> "Model"
@@ -441,14 +700,16 @@ impl OutputFormatter for TableFormatter {
                 duration_str,
                 r -> Self::format_number(session.tokens.input_tokens),
                 r -> Self::format_number(session.tokens.output_tokens),
+                r -> Self::format_number(session.tokens.reasoning_tokens),
+                r -> Self::format_number(session.tokens.web_search_requests),
                 r -> Self::format_number(session.tokens.total()),
-                r -> Self::format_currency(session.total_cost),
+                r -> self.format_currency(session.total_cost),
                 format_model_name(session.model.as_str(), self.full_model_names)
             ]);
         }
 
         // Add separator
-        table.add_row(Row::new(vec![Cell::new(""); 8]));
+        table.add_row(Row::new(vec![Cell::new(""); 10]));
 
         // Add totals row
         table.add_row(row![
@@ -457,8 +718,10 @@ impl OutputFormatter for TableFormatter {
             "",
             b -> Self::format_number(totals.tokens.input_tokens),
             b -> Self::format_number(totals.tokens.output_tokens),
+            b -> Self::format_number(totals.tokens.reasoning_tokens),
+            b -> Self::format_number(totals.tokens.web_search_requests),
             b -> Self::format_number(totals.tokens.total()),
-            b -> Self::format_currency(totals.total_cost),
+            b -> self.format_currency(totals.total_cost),
             ""
         ]);
 
@@ -473,6 +736,7 @@ impl OutputFormatter for TableFormatter {
             b -> "Month",
             b -> "Input",
             b -> "Output",
+            b -> "Reasoning",
             b -> "Cache Create",
             b -> "Cache Read",
             b -> "Total",
@@ -485,19 +749,20 @@ impl OutputFormatter for TableFormatter {
                 entry.month,
                 r -> Self::format_number(entry.tokens.input_tokens),
                 r -> Self::format_number(entry.tokens.output_tokens),
+                r -> Self::format_number(entry.tokens.reasoning_tokens),
                 r -> Self::format_number(entry.tokens.cache_creation_tokens),
                 r -> Self::format_number(entry.tokens.cache_read_tokens),
                 r -> Self::format_number(entry.tokens.total()),
-                r -> Self::format_currency(entry.total_cost),
+                r -> self.format_currency(entry.total_cost),
                 c -> entry.active_days
             ]);
         }
 
         // Add separator
-        table.add_row(Row::new(vec![Cell::new(""); 8]));
+        table.add_row(Row::new(vec![Cell::new(""); 9]));
 
         // Add totals row
-        table.add_row(Self::format_totals_row(totals));
+        table.add_row(self.format_totals_row(totals));
 
         table.to_string()
     }
@@ -510,6 +775,7 @@ impl OutputFormatter for TableFormatter {
             b -> "Week",
             b -> "Input",
             b -> "Output",
+            b -> "Reasoning",
             b -> "Cache Create",
             b -> "Cache Read",
             b -> "Total",
@@ -522,19 +788,20 @@ impl OutputFormatter for TableFormatter {
                 entry.week,
                 r -> Self::format_number(entry.tokens.input_tokens),
                 r -> Self::format_number(entry.tokens.output_tokens),
+                r -> Self::format_number(entry.tokens.reasoning_tokens),
                 r -> Self::format_number(entry.tokens.cache_creation_tokens),
                 r -> Self::format_number(entry.tokens.cache_read_tokens),
                 r -> Self::format_number(entry.tokens.total()),
-                r -> Self::format_currency(entry.total_cost),
+                r -> self.format_currency(entry.total_cost),
                 c -> entry.active_days
             ]);
         }
 
         // Add separator
-        table.add_row(Row::new(vec![Cell::new(""); 8]));
+        table.add_row(Row::new(vec![Cell::new(""); 9]));
 
         // Add totals row
-        table.add_row(Self::format_totals_row(totals));
+        table.add_row(self.format_totals_row(totals));
 
         table.to_string()
     }
@@ -542,6 +809,179 @@ impl OutputFormatter for TableFormatter {
     fn format_blocks(&self, data: &[SessionBlock], tz: &chrono_tz::Tz) -> String {
         self.format_blocks_with_now(data, tz, chrono::Utc::now())
     }
+
+    fn format_daily_by_provider(
+        &self,
+        data: &[ProviderUsage<DailyUsage>],
+        totals: &Totals,
+    ) -> String {
+        let rows = data
+            .iter()
+            .flat_map(|p| {
+                p.data.iter().map(|d| ProviderPeriodRow {
+                    period: d.date.format("%Y-%m-%d"),
+                    provider: p.provider.as_str(),
+                    tokens: d.tokens,
+                    cost: d.total_cost,
+                    last: format_model_list(&d.models_used, self.full_model_names, ", "),
+                })
+            })
+            .collect();
+        let subtotals = data
+            .iter()
+            .map(|p| (p.provider.as_str(), &p.totals))
+            .collect();
+        self.format_provider_period_table("Date", "Models", rows, subtotals, totals)
+    }
+
+    fn format_sessions_by_provider(
+        &self,
+        data: &[ProviderUsage<SessionUsage>],
+        totals: &Totals,
+        tz: &chrono_tz::Tz,
+    ) -> String {
+        let mut sessions: Vec<(&str, &SessionUsage)> = data
+            .iter()
+            .flat_map(|p| p.data.iter().map(move |s| (p.provider.as_str(), s)))
+            .collect();
+        sessions.sort_by_key(|(_, s)| s.start_time);
+
+        let mut table = Table::new();
+        table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+
+        table.set_titles(row![
+            b -> "Session ID",
+            b -> "Provider",
+            b -> "Start Time",
+            b -> "Duration",
+            b -> "Input",
+            b -> "Output",
+            b -> "Reasoning",
+            b -> "Total Tokens",
+            b -> "Cost",
+            b -> "Model"
+        ]);
+
+        for (provider, session) in sessions {
+            let duration = session.end_time - session.start_time;
+            let duration_str =
+                format!("{}h {}m", duration.num_hours(), duration.num_minutes() % 60);
+
+            table.add_row(row![
+                session.session_id.as_str(),
+                provider,
+                Self::format_datetime_with_tz(&session.start_time, tz),
+                duration_str,
+                r -> Self::format_number(session.tokens.input_tokens),
+                r -> Self::format_number(session.tokens.output_tokens),
+                r -> Self::format_number(session.tokens.reasoning_tokens),
+                r -> Self::format_number(session.tokens.total()),
+                r -> self.format_currency(session.total_cost),
+                format_model_name(session.model.as_str(), self.full_model_names)
+            ]);
+        }
+
+        // Add separator
+        table.add_row(Row::new(vec![Cell::new(""); 10]));
+
+        for p in data {
+            table.add_row(row![
+                "Subtotal",
+                p.provider,
+                "",
+                "",
+                r -> Self::format_number(p.totals.tokens.input_tokens),
+                r -> Self::format_number(p.totals.tokens.output_tokens),
+                r -> Self::format_number(p.totals.tokens.reasoning_tokens),
+                r -> Self::format_number(p.totals.tokens.total()),
+                r -> self.format_currency(p.totals.total_cost),
+                ""
+            ]);
+        }
+
+        // Add separator
+        table.add_row(Row::new(vec![Cell::new(""); 10]));
+
+        table.add_row(row![
+            b -> "TOTAL",
+            "",
+            "",
+            "",
+            b -> Self::format_number(totals.tokens.input_tokens),
+            b -> Self::format_number(totals.tokens.output_tokens),
+            b -> Self::format_number(totals.tokens.reasoning_tokens),
+            b -> Self::format_number(totals.tokens.total()),
+            b -> self.format_currency(totals.total_cost),
+            ""
+        ]);
+
+        table.to_string()
+    }
+
+    fn format_monthly_by_provider(
+        &self,
+        data: &[ProviderUsage<MonthlyUsage>],
+        totals: &Totals,
+    ) -> String {
+        let rows = data
+            .iter()
+            .flat_map(|p| {
+                p.data.iter().map(|m| ProviderPeriodRow {
+                    period: m.month.clone(),
+                    provider: p.provider.as_str(),
+                    tokens: m.tokens,
+                    cost: m.total_cost,
+                    last: m.active_days.to_string(),
+                })
+            })
+            .collect();
+        let subtotals = data
+            .iter()
+            .map(|p| (p.provider.as_str(), &p.totals))
+            .collect();
+        self.format_provider_period_table("Month", "Active Days", rows, subtotals, totals)
+    }
+
+    fn format_weekly_by_provider(
+        &self,
+        data: &[ProviderUsage<WeeklyUsage>],
+        totals: &Totals,
+    ) -> String {
+        let rows = data
+            .iter()
+            .flat_map(|p| {
+                p.data.iter().map(|w| ProviderPeriodRow {
+                    period: w.week.clone(),
+                    provider: p.provider.as_str(),
+                    tokens: w.tokens,
+                    cost: w.total_cost,
+                    last: w.active_days.to_string(),
+                })
+            })
+            .collect();
+        let subtotals = data
+            .iter()
+            .map(|p| (p.provider.as_str(), &p.totals))
+            .collect();
+        self.format_provider_period_table("Week", "Active Days", rows, subtotals, totals)
+    }
+
+    fn format_rate_limits(
+        &self,
+        snapshot: Option<&RateLimitSnapshot>,
+        tz: &chrono_tz::Tz,
+    ) -> String {
+        self.format_rate_limits_with_now(snapshot, tz, chrono::Utc::now())
+    }
+}
+
+/// A single row of a per-provider period table
+struct ProviderPeriodRow<'a> {
+    period: String,
+    provider: &'a str,
+    tokens: TokenCounts,
+    cost: Cost,
+    last: String,
 }
 
 /// JSON formatter for machine-readable output
@@ -549,7 +989,15 @@ impl OutputFormatter for TableFormatter {
 /// Produces structured JSON output that can be easily parsed by other tools
 /// or used in automation pipelines. All data is preserved in its raw form
 /// for maximum flexibility.
-pub struct JsonFormatter;
+///
+/// Costs are in USD. With another display currency, every `cost` and
+/// `*_cost` field gains a `*_converted` sibling, and a top-level `currency`
+/// object records the code, rate, and rate date used.
+#[derive(Debug, Clone, Default)]
+pub struct JsonFormatter {
+    /// Currency costs are converted to alongside USD
+    currency: Currency,
+}
 
 impl OutputFormatter for JsonFormatter {
     fn format_daily(&self, data: &[DailyUsage], totals: &Totals) -> String {
@@ -557,15 +1005,10 @@ impl OutputFormatter for JsonFormatter {
             "daily": data.iter().map(|d| {
                 let mut day_json = json!({
                     "date": d.date.format("%Y-%m-%d"),
-                    "tokens": {
-                        "input_tokens": d.tokens.input_tokens,
-                        "output_tokens": d.tokens.output_tokens,
-                        "cache_creation_tokens": d.tokens.cache_creation_tokens,
-                        "cache_read_tokens": d.tokens.cache_read_tokens,
-                        "total": d.tokens.total(),
-                    },
+                    "tokens": Self::tokens_json(&d.tokens),
                     "total_cost": d.total_cost,
                     "models_used": d.models_used,
+                    "model_breakdowns": Self::model_breakdowns_json(&d.model_breakdowns),
                 });
 
                 // Add verbose entries if available
@@ -574,13 +1017,7 @@ impl OutputFormatter for JsonFormatter {
                         "timestamp": e.timestamp.to_rfc3339(),
                         "session_id": e.session_id,
                         "model": e.model,
-                        "tokens": {
-                            "input_tokens": e.tokens.input_tokens,
-                            "output_tokens": e.tokens.output_tokens,
-                            "cache_creation_tokens": e.tokens.cache_creation_tokens,
-                            "cache_read_tokens": e.tokens.cache_read_tokens,
-                            "total": e.tokens.total(),
-                        },
+                        "tokens": Self::tokens_json(&e.tokens),
                         "cost": e.cost,
                     })).collect::<Vec<_>>());
                 }
@@ -588,18 +1025,12 @@ impl OutputFormatter for JsonFormatter {
                 day_json
             }).collect::<Vec<_>>(),
             "totals": {
-                "tokens": {
-                    "input_tokens": totals.tokens.input_tokens,
-                    "output_tokens": totals.tokens.output_tokens,
-                    "cache_creation_tokens": totals.tokens.cache_creation_tokens,
-                    "cache_read_tokens": totals.tokens.cache_read_tokens,
-                    "total": totals.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&totals.tokens),
                 "total_cost": totals.total_cost,
             }
         });
 
-        serde_json::to_string_pretty(&output).unwrap()
+        self.render(output)
     }
 
     fn format_daily_by_instance(&self, data: &[DailyInstanceUsage], totals: &Totals) -> String {
@@ -607,29 +1038,17 @@ impl OutputFormatter for JsonFormatter {
             "daily_by_instance": data.iter().map(|d| json!({
                 "date": d.date.format("%Y-%m-%d"),
                 "instance_id": d.instance_id,
-                "tokens": {
-                    "input_tokens": d.tokens.input_tokens,
-                    "output_tokens": d.tokens.output_tokens,
-                    "cache_creation_tokens": d.tokens.cache_creation_tokens,
-                    "cache_read_tokens": d.tokens.cache_read_tokens,
-                    "total": d.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&d.tokens),
                 "total_cost": d.total_cost,
                 "models_used": d.models_used,
             })).collect::<Vec<_>>(),
             "totals": {
-                "tokens": {
-                    "input_tokens": totals.tokens.input_tokens,
-                    "output_tokens": totals.tokens.output_tokens,
-                    "cache_creation_tokens": totals.tokens.cache_creation_tokens,
-                    "cache_read_tokens": totals.tokens.cache_read_tokens,
-                    "total": totals.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&totals.tokens),
                 "total_cost": totals.total_cost,
             }
         });
 
-        serde_json::to_string_pretty(&output).unwrap()
+        self.render(output)
     }
 
     fn format_sessions(
@@ -644,87 +1063,52 @@ impl OutputFormatter for JsonFormatter {
                 "start_time": s.start_time.to_rfc3339(),
                 "end_time": s.end_time.to_rfc3339(),
                 "duration_seconds": (s.end_time - s.start_time).num_seconds(),
-                "tokens": {
-                    "input_tokens": s.tokens.input_tokens,
-                    "output_tokens": s.tokens.output_tokens,
-                    "cache_creation_tokens": s.tokens.cache_creation_tokens,
-                    "cache_read_tokens": s.tokens.cache_read_tokens,
-                    "total": s.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&s.tokens),
                 "total_cost": s.total_cost,
                 "model": s.model.as_str(),
+                "provider": s.provider,
             })).collect::<Vec<_>>(),
             "totals": {
-                "tokens": {
-                    "input_tokens": totals.tokens.input_tokens,
-                    "output_tokens": totals.tokens.output_tokens,
-                    "cache_creation_tokens": totals.tokens.cache_creation_tokens,
-                    "cache_read_tokens": totals.tokens.cache_read_tokens,
-                    "total": totals.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&totals.tokens),
                 "total_cost": totals.total_cost,
             }
         });
 
-        serde_json::to_string_pretty(&output).unwrap()
+        self.render(output)
     }
 
     fn format_monthly(&self, data: &[MonthlyUsage], totals: &Totals) -> String {
         let output = json!({
             "monthly": data.iter().map(|m| json!({
                 "month": m.month,
-                "tokens": {
-                    "input_tokens": m.tokens.input_tokens,
-                    "output_tokens": m.tokens.output_tokens,
-                    "cache_creation_tokens": m.tokens.cache_creation_tokens,
-                    "cache_read_tokens": m.tokens.cache_read_tokens,
-                    "total": m.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&m.tokens),
                 "total_cost": m.total_cost,
                 "active_days": m.active_days,
             })).collect::<Vec<_>>(),
             "totals": {
-                "tokens": {
-                    "input_tokens": totals.tokens.input_tokens,
-                    "output_tokens": totals.tokens.output_tokens,
-                    "cache_creation_tokens": totals.tokens.cache_creation_tokens,
-                    "cache_read_tokens": totals.tokens.cache_read_tokens,
-                    "total": totals.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&totals.tokens),
                 "total_cost": totals.total_cost,
             }
         });
 
-        serde_json::to_string_pretty(&output).unwrap()
+        self.render(output)
     }
 
     fn format_weekly(&self, data: &[WeeklyUsage], totals: &Totals) -> String {
         let output = json!({
             "weekly": data.iter().map(|w| json!({
                 "week": w.week,
-                "tokens": {
-                    "input_tokens": w.tokens.input_tokens,
-                    "output_tokens": w.tokens.output_tokens,
-                    "cache_creation_tokens": w.tokens.cache_creation_tokens,
-                    "cache_read_tokens": w.tokens.cache_read_tokens,
-                    "total": w.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&w.tokens),
                 "total_cost": w.total_cost,
                 "active_days": w.active_days,
             })).collect::<Vec<_>>(),
             "totals": {
-                "tokens": {
-                    "input_tokens": totals.tokens.input_tokens,
-                    "output_tokens": totals.tokens.output_tokens,
-                    "cache_creation_tokens": totals.tokens.cache_creation_tokens,
-                    "cache_read_tokens": totals.tokens.cache_read_tokens,
-                    "total": totals.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&totals.tokens),
                 "total_cost": totals.total_cost,
             }
         });
 
-        serde_json::to_string_pretty(&output).unwrap()
+        self.render(output)
     }
 
     fn format_blocks(&self, data: &[SessionBlock], _tz: &chrono_tz::Tz) -> String {
@@ -735,21 +1119,239 @@ impl OutputFormatter for JsonFormatter {
                 "is_active": b.is_active,
                 "is_gap": b.is_gap,
                 "session_count": b.sessions.len(),
-                "tokens": {
-                    "input_tokens": b.tokens.input_tokens,
-                    "output_tokens": b.tokens.output_tokens,
-                    "cache_creation_tokens": b.tokens.cache_creation_tokens,
-                    "cache_read_tokens": b.tokens.cache_read_tokens,
-                    "total": b.tokens.total(),
-                },
+                "tokens": Self::tokens_json(&b.tokens),
                 "total_cost": b.total_cost,
                 "sessions": b.sessions.iter().map(|s| s.session_id.as_str()).collect::<Vec<_>>(),
                 "models_used": &b.models_used,
             })).collect::<Vec<_>>()
         });
 
+        self.render(output)
+    }
+
+    fn format_daily_by_provider(
+        &self,
+        data: &[ProviderUsage<DailyUsage>],
+        totals: &Totals,
+    ) -> String {
+        let mut rows: Vec<(&str, &DailyUsage)> = data
+            .iter()
+            .flat_map(|p| p.data.iter().map(move |d| (p.provider.as_str(), d)))
+            .collect();
+        rows.sort_by_key(|(_, d)| d.date);
+
+        let output = json!({
+            "daily": rows.iter().map(|(provider, d)| json!({
+                "date": d.date.format("%Y-%m-%d"),
+                "provider": provider,
+                "tokens": Self::tokens_json(&d.tokens),
+                "total_cost": d.total_cost,
+                "models_used": d.models_used,
+                "model_breakdowns": Self::model_breakdowns_json(&d.model_breakdowns),
+            })).collect::<Vec<_>>(),
+            "providers": Self::provider_subtotals_json(data),
+            "totals": {
+                "tokens": Self::tokens_json(&totals.tokens),
+                "total_cost": totals.total_cost,
+            }
+        });
+
+        self.render(output)
+    }
+
+    fn format_sessions_by_provider(
+        &self,
+        data: &[ProviderUsage<SessionUsage>],
+        totals: &Totals,
+        _tz: &chrono_tz::Tz,
+    ) -> String {
+        let mut rows: Vec<(&str, &SessionUsage)> = data
+            .iter()
+            .flat_map(|p| p.data.iter().map(move |s| (p.provider.as_str(), s)))
+            .collect();
+        rows.sort_by_key(|(_, s)| s.start_time);
+
+        let output = json!({
+            "sessions": rows.iter().map(|(provider, s)| json!({
+                "session_id": s.session_id.as_str(),
+                "provider": provider,
+                "start_time": s.start_time.to_rfc3339(),
+                "end_time": s.end_time.to_rfc3339(),
+                "duration_seconds": (s.end_time - s.start_time).num_seconds(),
+                "tokens": Self::tokens_json(&s.tokens),
+                "total_cost": s.total_cost,
+                "model": s.model.as_str(),
+            })).collect::<Vec<_>>(),
+            "providers": Self::provider_subtotals_json(data),
+            "totals": {
+                "tokens": Self::tokens_json(&totals.tokens),
+                "total_cost": totals.total_cost,
+            }
+        });
+
+        self.render(output)
+    }
+
+    fn format_monthly_by_provider(
+        &self,
+        data: &[ProviderUsage<MonthlyUsage>],
+        totals: &Totals,
+    ) -> String {
+        let mut rows: Vec<(&str, &MonthlyUsage)> = data
+            .iter()
+            .flat_map(|p| p.data.iter().map(move |m| (p.provider.as_str(), m)))
+            .collect();
+        rows.sort_by(|a, b| a.1.month.cmp(&b.1.month));
+
+        let output = json!({
+            "monthly": rows.iter().map(|(provider, m)| json!({
+                "month": m.month,
+                "provider": provider,
+                "tokens": Self::tokens_json(&m.tokens),
+                "total_cost": m.total_cost,
+                "active_days": m.active_days,
+            })).collect::<Vec<_>>(),
+            "providers": Self::provider_subtotals_json(data),
+            "totals": {
+                "tokens": Self::tokens_json(&totals.tokens),
+                "total_cost": totals.total_cost,
+            }
+        });
+
+        self.render(output)
+    }
+
+    fn format_weekly_by_provider(
+        &self,
+        data: &[ProviderUsage<WeeklyUsage>],
+        totals: &Totals,
+    ) -> String {
+        let mut rows: Vec<(&str, &WeeklyUsage)> = data
+            .iter()
+            .flat_map(|p| p.data.iter().map(move |w| (p.provider.as_str(), w)))
+            .collect();
+        rows.sort_by(|a, b| a.1.week.cmp(&b.1.week));
+
+        let output = json!({
+            "weekly": rows.iter().map(|(provider, w)| json!({
+                "week": w.week,
+                "provider": provider,
+                "tokens": Self::tokens_json(&w.tokens),
+                "total_cost": w.total_cost,
+                "active_days": w.active_days,
+            })).collect::<Vec<_>>(),
+            "providers": Self::provider_subtotals_json(data),
+            "totals": {
+                "tokens": Self::tokens_json(&totals.tokens),
+                "total_cost": totals.total_cost,
+            }
+        });
+
+        self.render(output)
+    }
+
+    fn format_rate_limits(
+        &self,
+        snapshot: Option<&RateLimitSnapshot>,
+        _tz: &chrono_tz::Tz,
+    ) -> String {
+        let output = json!({ "rate_limits": snapshot });
+        self.render(output)
+    }
+}
+
+impl JsonFormatter {
+    /// Create a new JsonFormatter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add costs converted to `currency` next to the USD amounts
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    /// Serialize output, adding converted costs for a non-USD currency
+    fn render(&self, mut output: serde_json::Value) -> String {
+        if !self.currency.is_usd() {
+            self.add_converted_costs(&mut output);
+            if let Some(object) = output.as_object_mut() {
+                object.insert("currency".to_string(), json!(self.currency));
+            }
+        }
         serde_json::to_string_pretty(&output).unwrap()
     }
+
+    /// Add a `*_converted` field next to every cost field, recursively
+    fn add_converted_costs(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(object) => {
+                let converted: Vec<_> = object
+                    .iter()
+                    .filter(|(key, _)| *key == "cost" || key.ends_with("_cost"))
+                    .filter_map(|(key, cost)| {
+                        let cost = cost.as_f64()?;
+                        Some((format!("{key}_converted"), self.currency.convert(cost)))
+                    })
+                    .collect();
+                for nested in object.values_mut() {
+                    self.add_converted_costs(nested);
+                }
+                for (key, cost) in converted {
+                    object.insert(key, json!(cost));
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    self.add_converted_costs(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Token counts as a JSON object
+    fn tokens_json(tokens: &TokenCounts) -> serde_json::Value {
+        json!({
+            "input_tokens": tokens.input_tokens,
+            "output_tokens": tokens.output_tokens,
+            "cache_creation_tokens": tokens.cache_creation_tokens,
+            "cache_read_tokens": tokens.cache_read_tokens,
+            "reasoning_tokens": tokens.reasoning_tokens,
+            "web_search_requests": tokens.web_search_requests,
+            "web_fetch_requests": tokens.web_fetch_requests,
+            "total": tokens.total(),
+        })
+    }
+
+    /// Per-provider, per-model usage within a day
+    fn model_breakdowns_json(breakdowns: &[ModelBreakdown]) -> Vec<serde_json::Value> {
+        breakdowns
+            .iter()
+            .map(|m| {
+                json!({
+                    "provider": m.provider,
+                    "model": m.model,
+                    "tokens": Self::tokens_json(&m.tokens),
+                    "cost": m.cost,
+                })
+            })
+            .collect()
+    }
+
+    /// Per-provider subtotals for combined reports
+    fn provider_subtotals_json<T>(data: &[ProviderUsage<T>]) -> Vec<serde_json::Value> {
+        data.iter()
+            .map(|p| {
+                json!({
+                    "provider": p.provider,
+                    "tokens": Self::tokens_json(&p.totals.tokens),
+                    "total_cost": p.totals.total_cost,
+                })
+            })
+            .collect()
+    }
 }
 
 /// Get appropriate formatter based on JSON flag
@@ -761,6 +1363,7 @@ impl OutputFormatter for JsonFormatter {
 ///
 /// * `json` - If true, returns a JSON formatter; otherwise returns a table formatter
 /// * `full_model_names` - If true, shows full model names; otherwise shows shortened versions
+/// * `currency` - Currency costs are shown in, next to USD in JSON output
 ///
 /// # Returns
 ///
@@ -771,22 +1374,25 @@ impl OutputFormatter for JsonFormatter {
 /// ```
 /// use ccstat_terminal::output::{get_formatter, OutputFormatter};
 /// use ccstat_core::aggregation_types::{DailyUsage, Totals};
+/// use ccstat_core::cost::Cost;
+/// use ccstat_core::currency::Currency;
 /// use ccstat_core::types::{DailyDate, TokenCounts};
 /// use chrono::NaiveDate;
 ///
 /// // Get table formatter for human-readable output
-/// let formatter = get_formatter(false, false);
+/// let formatter = get_formatter(false, false, Currency::usd());
 ///
-/// // Get JSON formatter for machine-readable output
-/// let json_formatter = get_formatter(true, false);
+/// // Get JSON formatter for machine-readable output, with costs also in EUR
+/// let json_formatter = get_formatter(true, false, Currency::new("EUR", 0.9));
 ///
 /// // Use with data
 /// let daily_data = vec![
 ///     DailyUsage {
 ///         date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
 ///         tokens: TokenCounts::new(1000, 500, 0, 0),
-///         total_cost: 0.025,
+///         total_cost: Cost::from_usd(0.025),
 ///         models_used: vec!["claude-3-opus".to_string()],
+///         model_breakdowns: Vec::new(),
 ///         entries: None,
 ///     },
 /// ];
@@ -794,11 +1400,15 @@ impl OutputFormatter for JsonFormatter {
 ///
 /// let output = formatter.format_daily(&daily_data, &totals);
 /// ```
-pub fn get_formatter(json: bool, full_model_names: bool) -> Box<dyn OutputFormatter> {
+pub fn get_formatter(
+    json: bool,
+    full_model_names: bool,
+    currency: Currency,
+) -> Box<dyn OutputFormatter> {
     if json {
-        Box::new(JsonFormatter)
+        Box::new(JsonFormatter::new().with_currency(currency))
     } else {
-        Box::new(TableFormatter::new(full_model_names))
+        Box::new(TableFormatter::new(full_model_names).with_currency(currency))
     }
 }
 
@@ -806,7 +1416,7 @@ pub fn get_formatter(json: bool, full_model_names: bool) -> Box<dyn OutputFormat
 mod tests {
     use super::*;
     use ccstat_core::aggregation_types::{MonthlyUsage, SessionBlock, SessionUsage};
-    use ccstat_core::types::{DailyDate, ModelName, SessionId, TokenCounts};
+    use ccstat_core::types::{DailyDate, ModelName, ProviderId, SessionId, TokenCounts};
     use chrono::{NaiveDate, TimeZone, Utc};
 
     #[test]
@@ -820,17 +1430,32 @@ mod tests {
 
     #[test]
     fn test_currency_formatting() {
-        assert_eq!(TableFormatter::format_currency(12.345), "$12.35");
-        assert_eq!(TableFormatter::format_currency(0.0), "$0.00");
-        assert_eq!(TableFormatter::format_currency(1000.0), "$1000.00");
-        assert_eq!(TableFormatter::format_currency(0.001), "$0.00");
-        assert_eq!(TableFormatter::format_currency(999999.99), "$999999.99");
+        assert_eq!(
+            TableFormatter::new(false).format_currency(Cost::from_usd(12.345)),
+            "$12.35"
+        );
+        assert_eq!(
+            TableFormatter::new(false).format_currency(Cost::from_usd(0.0)),
+            "$0.00"
+        );
+        assert_eq!(
+            TableFormatter::new(false).format_currency(Cost::from_usd(1000.0)),
+            "$1000.00"
+        );
+        assert_eq!(
+            TableFormatter::new(false).format_currency(Cost::from_usd(0.001)),
+            "$0.00"
+        );
+        assert_eq!(
+            TableFormatter::new(false).format_currency(Cost::from_usd(999999.99)),
+            "$999999.99"
+        );
     }
 
     #[test]
     fn test_get_formatter() {
         // Test JSON formatter
-        let json_formatter = get_formatter(true, false);
+        let json_formatter = get_formatter(true, false, Currency::usd());
         assert!(
             json_formatter
                 .format_daily(&[], &Totals::default())
@@ -838,12 +1463,13 @@ mod tests {
         );
 
         // Test table formatter with full model names
-        let table_formatter = get_formatter(false, true);
+        let table_formatter = get_formatter(false, true, Currency::usd());
         let daily_data = vec![DailyUsage {
             date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
             tokens: TokenCounts::new(100, 50, 10, 5),
-            total_cost: 1.25,
+            total_cost: Cost::from_usd(1.25),
             models_used: vec!["claude-3-opus".to_string()],
+            model_breakdowns: Vec::new(),
             entries: None,
         }];
         let totals = Totals::from_daily(&daily_data);
@@ -864,8 +1490,9 @@ mod tests {
         let daily_data = vec![DailyUsage {
             date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()),
             tokens: TokenCounts::new(1000, 500, 100, 50),
-            total_cost: 2.50,
+            total_cost: Cost::from_usd(2.50),
             models_used: vec!["claude-3-opus".to_string(), "claude-3-sonnet".to_string()],
+            model_breakdowns: Vec::new(),
             entries: None,
         }];
         let totals = Totals::from_daily(&daily_data);
@@ -876,21 +1503,25 @@ mod tests {
         assert!(output.contains("500"));
         assert!(output.contains("$2.50"));
         assert!(output.contains("TOTAL"));
+        assert!(output.contains("Reasoning"));
+        assert!(output.contains("Web Search"));
 
         // Test with multiple days
         let multi_day_data = vec![
             DailyUsage {
                 date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()),
                 tokens: TokenCounts::new(1000, 500, 0, 0),
-                total_cost: 1.50,
+                total_cost: Cost::from_usd(1.50),
                 models_used: vec!["claude-3-opus".to_string()],
+                model_breakdowns: Vec::new(),
                 entries: None,
             },
             DailyUsage {
                 date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 3, 16).unwrap()),
                 tokens: TokenCounts::new(2000, 1000, 200, 100),
-                total_cost: 3.00,
+                total_cost: Cost::from_usd(3.00),
                 models_used: vec!["claude-3-sonnet".to_string()],
+                model_breakdowns: Vec::new(),
                 entries: None,
             },
         ];
@@ -913,14 +1544,15 @@ mod tests {
             session_id: "test-session".to_string(),
             model: "claude-3-opus".to_string(),
             tokens: TokenCounts::new(100, 50, 10, 5),
-            cost: 0.25,
+            cost: Cost::from_usd(0.25),
         };
 
         let daily_data = vec![DailyUsage {
             date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()),
             tokens: TokenCounts::new(100, 50, 10, 5),
-            total_cost: 0.25,
+            total_cost: Cost::from_usd(0.25),
             models_used: vec!["claude-3-opus".to_string()],
+            model_breakdowns: Vec::new(),
             entries: Some(vec![verbose_entry]),
         }];
 
@@ -944,14 +1576,14 @@ mod tests {
                 date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()),
                 instance_id: "instance-1".to_string(),
                 tokens: TokenCounts::new(1000, 500, 0, 0),
-                total_cost: 1.50,
+                total_cost: Cost::from_usd(1.50),
                 models_used: vec!["claude-3-opus".to_string()],
             },
             DailyInstanceUsage {
                 date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()),
                 instance_id: "instance-2".to_string(),
                 tokens: TokenCounts::new(2000, 1000, 100, 50),
-                total_cost: 3.00,
+                total_cost: Cost::from_usd(3.00),
                 models_used: vec!["claude-3-sonnet".to_string()],
             },
         ];
@@ -979,8 +1611,9 @@ mod tests {
             start_time,
             end_time,
             tokens: TokenCounts::new(5000, 2500, 500, 250),
-            total_cost: 7.50,
+            total_cost: Cost::from_usd(7.50),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         }];
 
         let totals = Totals::from_sessions(&sessions);
@@ -1001,13 +1634,13 @@ mod tests {
             MonthlyUsage {
                 month: "2024-01".to_string(),
                 tokens: TokenCounts::new(100000, 50000, 10000, 5000),
-                total_cost: 150.00,
+                total_cost: Cost::from_usd(150.00),
                 active_days: 15,
             },
             MonthlyUsage {
                 month: "2024-02".to_string(),
                 tokens: TokenCounts::new(200000, 100000, 20000, 10000),
-                total_cost: 300.00,
+                total_cost: Cost::from_usd(300.00),
                 active_days: 20,
             },
         ];
@@ -1024,6 +1657,51 @@ mod tests {
         assert!(output.contains("20"));
     }
 
+    fn provider_daily_fixture() -> Vec<ProviderUsage<DailyUsage>> {
+        let day = |d: u32, cost: f64| DailyUsage {
+            date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 1, d).unwrap()),
+            tokens: TokenCounts::new(1000, 500, 0, 0),
+            total_cost: Cost::from_usd(cost),
+            models_used: vec!["claude-sonnet-4".to_string()],
+            model_breakdowns: Vec::new(),
+            entries: None,
+        };
+        let claude = vec![day(1, 1.0), day(2, 2.0)];
+        let codex = vec![day(1, 0.5)];
+        vec![
+            ProviderUsage {
+                provider: ProviderId::new("claude"),
+                totals: Totals::from_daily(&claude),
+                data: claude,
+            },
+            ProviderUsage {
+                provider: ProviderId::new("codex"),
+                totals: Totals::from_daily(&codex),
+                data: codex,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_table_formatter_daily_by_provider() {
+        let formatter = TableFormatter::new(false);
+        let data = provider_daily_fixture();
+        let totals = Totals::from_providers(&data);
+        let output = formatter.format_daily_by_provider(&data, &totals);
+
+        assert!(output.contains("Provider"));
+        assert!(output.contains("Subtotal"));
+        assert!(output.contains("$3.00")); // claude subtotal
+        assert!(output.contains("$3.50")); // grand total
+
+        // Rows are ordered by date, then provider
+        let first_claude = output.find("| claude").unwrap();
+        let first_codex = output.find("| codex").unwrap();
+        let second_day = output.find("2024-01-02").unwrap();
+        assert!(first_claude < first_codex);
+        assert!(first_codex < second_day);
+    }
+
     #[test]
     fn test_table_formatter_blocks() {
         let formatter = TableFormatter::new(false);
@@ -1038,8 +1716,9 @@ mod tests {
             start_time: now - chrono::Duration::hours(2),
             end_time: now - chrono::Duration::hours(1),
             tokens: TokenCounts::new(1500, 750, 150, 75),
-            total_cost: 2.25,
+            total_cost: Cost::from_usd(2.25),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         };
 
         let session2 = SessionUsage {
@@ -1047,8 +1726,9 @@ mod tests {
             start_time: now - chrono::Duration::hours(1),
             end_time: now,
             tokens: TokenCounts::new(1500, 750, 150, 75),
-            total_cost: 2.25,
+            total_cost: Cost::from_usd(2.25),
             model: ModelName::new("claude-3-sonnet"),
+            provider: ProviderId::new("claude"),
         };
 
         let session3 = SessionUsage {
@@ -1056,8 +1736,9 @@ mod tests {
             start_time: now - chrono::Duration::hours(10),
             end_time: now - chrono::Duration::hours(9),
             tokens: TokenCounts::new(1000, 500, 100, 50),
-            total_cost: 1.50,
+            total_cost: Cost::from_usd(1.50),
             model: ModelName::new("claude-3-haiku"),
+            provider: ProviderId::new("claude"),
         };
 
         let active_block = SessionBlock {
@@ -1069,7 +1750,7 @@ mod tests {
             is_gap: false,
             sessions: vec![session1, session2],
             tokens: TokenCounts::new(3000, 1500, 300, 150),
-            total_cost: 4.50,
+            total_cost: Cost::from_usd(4.50),
             models_used: vec!["claude-3-opus".to_string(), "claude-3-sonnet".to_string()],
             projects_used: vec![],
             warning: None,
@@ -1084,7 +1765,7 @@ mod tests {
             is_gap: false,
             sessions: vec![session3],
             tokens: TokenCounts::new(1000, 500, 100, 50),
-            total_cost: 1.50,
+            total_cost: Cost::from_usd(1.50),
             models_used: vec!["claude-3-haiku".to_string()],
             projects_used: vec![],
             warning: None,
@@ -1100,7 +1781,7 @@ mod tests {
             is_gap: true,
             sessions: vec![],
             tokens: TokenCounts::new(0, 0, 0, 0),
-            total_cost: 0.0,
+            total_cost: Cost::from_usd(0.0),
             models_used: vec![],
             projects_used: vec![],
             warning: None,
@@ -1122,13 +1803,16 @@ mod tests {
 
     #[test]
     fn test_json_formatter_daily() {
-        let formatter = JsonFormatter;
+        let formatter = JsonFormatter::new();
 
         let daily_data = vec![DailyUsage {
             date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()),
-            tokens: TokenCounts::new(1000, 500, 100, 50),
-            total_cost: 2.50,
+            tokens: TokenCounts::new(1000, 500, 100, 50)
+                .with_reasoning_tokens(300)
+                .with_server_tool_use(2, 1),
+            total_cost: Cost::from_usd(2.50),
             models_used: vec!["claude-3-opus".to_string()],
+            model_breakdowns: Vec::new(),
             entries: None,
         }];
 
@@ -1140,19 +1824,69 @@ mod tests {
             serde_json::from_str(&output).expect("Failed to parse JSON output");
         assert_eq!(json["daily"][0]["date"], "2024-03-15");
         assert_eq!(json["daily"][0]["tokens"]["input_tokens"], 1000);
+        assert_eq!(json["daily"][0]["tokens"]["reasoning_tokens"], 300);
+        assert_eq!(json["totals"]["tokens"]["reasoning_tokens"], 300);
+        assert_eq!(json["daily"][0]["tokens"]["web_search_requests"], 2);
+        assert_eq!(json["totals"]["tokens"]["web_fetch_requests"], 1);
         assert_eq!(json["daily"][0]["total_cost"], 2.5);
         assert_eq!(json["totals"]["total_cost"], 2.5);
     }
 
+    #[test]
+    fn test_currency_conversion() {
+        let daily_data = vec![DailyUsage {
+            date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()),
+            tokens: TokenCounts::new(1000, 500, 100, 50),
+            total_cost: Cost::from_usd(2.50),
+            models_used: vec!["claude-3-opus".to_string()],
+            model_breakdowns: vec![ModelBreakdown {
+                provider: ProviderId::new("claude"),
+                model: "claude-3-opus".to_string(),
+                tokens: TokenCounts::new(1000, 500, 100, 50),
+                cost: Cost::from_usd(2.50),
+            }],
+            entries: None,
+        }];
+        let totals = Totals::from_daily(&daily_data);
+        let jpy = Currency::new("JPY", 150.0).with_as_of(NaiveDate::from_ymd_opt(2025, 6, 2));
+
+        let output = TableFormatter::new(false)
+            .with_currency(jpy.clone())
+            .format_daily(&daily_data, &totals);
+        assert!(output.contains("¥375"));
+        assert!(!output.contains("$2.50"));
+
+        let output = JsonFormatter::new()
+            .with_currency(jpy)
+            .format_daily(&daily_data, &totals);
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["daily"][0]["total_cost"], 2.5);
+        assert_eq!(json["daily"][0]["total_cost_converted"], 375.0);
+        assert_eq!(
+            json["daily"][0]["model_breakdowns"][0]["cost_converted"],
+            375.0
+        );
+        assert_eq!(json["totals"]["total_cost_converted"], 375.0);
+        assert_eq!(json["currency"]["code"], "JPY");
+        assert_eq!(json["currency"]["rate"], 150.0);
+        assert_eq!(json["currency"]["as_of"], "2025-06-02");
+
+        // USD output is unchanged
+        let output = JsonFormatter::new().format_daily(&daily_data, &totals);
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert!(json.get("currency").is_none());
+        assert!(json["totals"].get("total_cost_converted").is_none());
+    }
+
     #[test]
     fn test_json_formatter_daily_by_instance() {
-        let formatter = JsonFormatter;
+        let formatter = JsonFormatter::new();
 
         let instance_data = vec![DailyInstanceUsage {
             date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()),
             instance_id: "instance-1".to_string(),
             tokens: TokenCounts::new(1000, 500, 0, 0),
-            total_cost: 1.50,
+            total_cost: Cost::from_usd(1.50),
             models_used: vec!["claude-3-opus".to_string()],
         }];
 
@@ -1167,7 +1901,7 @@ mod tests {
 
     #[test]
     fn test_json_formatter_sessions() {
-        let formatter = JsonFormatter;
+        let formatter = JsonFormatter::new();
         let tz = chrono_tz::UTC;
 
         let start_time = Utc.with_ymd_and_hms(2024, 3, 15, 10, 0, 0).unwrap();
@@ -1178,8 +1912,9 @@ mod tests {
             start_time,
             end_time,
             tokens: TokenCounts::new(5000, 2500, 0, 0),
-            total_cost: 7.50,
+            total_cost: Cost::from_usd(7.50),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         }];
 
         let totals = Totals::from_sessions(&sessions);
@@ -1190,16 +1925,17 @@ mod tests {
         assert_eq!(json["sessions"][0]["session_id"], "session-123");
         assert_eq!(json["sessions"][0]["duration_seconds"], 9000); // 2.5 hours
         assert_eq!(json["sessions"][0]["total_cost"], 7.5);
+        assert_eq!(json["sessions"][0]["provider"], "claude");
     }
 
     #[test]
     fn test_json_formatter_monthly() {
-        let formatter = JsonFormatter;
+        let formatter = JsonFormatter::new();
 
         let monthly_data = vec![MonthlyUsage {
             month: "2024-01".to_string(),
             tokens: TokenCounts::new(100000, 50000, 0, 0),
-            total_cost: 150.00,
+            total_cost: Cost::from_usd(150.00),
             active_days: 15,
         }];
 
@@ -1213,9 +1949,30 @@ mod tests {
         assert_eq!(json["totals"]["total_cost"], 150.0);
     }
 
+    #[test]
+    fn test_json_formatter_daily_by_provider() {
+        let formatter = JsonFormatter::new();
+        let data = provider_daily_fixture();
+        let totals = Totals::from_providers(&data);
+        let output = formatter.format_daily_by_provider(&data, &totals);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&output).expect("Failed to parse JSON output");
+        let days = json["daily"].as_array().unwrap();
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0]["provider"], "claude");
+        assert_eq!(days[1]["provider"], "codex");
+        assert_eq!(days[2]["date"], "2024-01-02");
+        assert_eq!(json["providers"][0]["provider"], "claude");
+        assert_eq!(json["providers"][0]["total_cost"], 3.0);
+        assert_eq!(json["providers"][1]["total_cost"], 0.5);
+        assert_eq!(json["totals"]["total_cost"], 3.5);
+        assert_eq!(json["totals"]["tokens"]["input_tokens"], 3000);
+    }
+
     #[test]
     fn test_json_formatter_blocks() {
-        let formatter = JsonFormatter;
+        let formatter = JsonFormatter::new();
         let tz = chrono_tz::UTC;
 
         // Use fixed time for deterministic testing
@@ -1226,8 +1983,9 @@ mod tests {
             start_time: now - chrono::Duration::hours(2),
             end_time: now - chrono::Duration::hours(1),
             tokens: TokenCounts::new(3000, 1500, 0, 0),
-            total_cost: 4.50,
+            total_cost: Cost::from_usd(4.50),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         };
 
         let block = SessionBlock {
@@ -1238,8 +1996,8 @@ mod tests {
             is_active: true,
             is_gap: false,
             sessions: vec![session],
-            tokens: TokenCounts::new(3000, 1500, 0, 0),
-            total_cost: 4.50,
+            tokens: TokenCounts::new(3000, 1500, 0, 0).with_server_tool_use(2, 1),
+            total_cost: Cost::from_usd(4.50),
             models_used: vec!["claude-3-opus".to_string()],
             projects_used: vec![],
             warning: None,
@@ -1253,6 +2011,74 @@ mod tests {
         assert_eq!(json["blocks"][0]["is_active"], true);
         assert_eq!(json["blocks"][0]["session_count"], 1);
         assert_eq!(json["blocks"][0]["total_cost"], 4.5);
+        assert_eq!(json["blocks"][0]["tokens"]["web_search_requests"], 2);
+        assert_eq!(json["blocks"][0]["tokens"]["web_fetch_requests"], 1);
+    }
+
+    fn rate_limit_fixture() -> RateLimitSnapshot {
+        let at = Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+        RateLimitSnapshot {
+            timestamp: at,
+            session_id: SessionId::new("rollout-1"),
+            primary: Some(RateLimitWindow {
+                used_percent: 42.5,
+                window_minutes: Some(300),
+                resets_at: Some(at + chrono::Duration::minutes(90)),
+            }),
+            secondary: Some(RateLimitWindow {
+                used_percent: 7.0,
+                window_minutes: Some(10080),
+                resets_at: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_table_formatter_rate_limits() {
+        let formatter = TableFormatter::new(false);
+        let snapshot = rate_limit_fixture();
+        let now = snapshot.timestamp + chrono::Duration::minutes(30);
+        let output = formatter.format_rate_limits_with_now(Some(&snapshot), &chrono_tz::UTC, now);
+
+        assert!(output.contains("Rate limits as of 2025-01-01 10:00 UTC (session rollout-1)"));
+        assert!(output.contains("Primary"));
+        assert!(output.contains("42.5%"));
+        assert!(output.contains("5h"));
+        assert!(output.contains("2025-01-01 11:30 UTC"));
+        assert!(output.contains("1h 0m"));
+        assert!(output.contains("Secondary"));
+        assert!(output.contains("7d"));
+
+        let expired = formatter.format_rate_limits_with_now(
+            Some(&snapshot),
+            &chrono_tz::UTC,
+            now + chrono::Duration::hours(2),
+        );
+        assert!(expired.contains("Expired"));
+
+        assert_eq!(
+            formatter.format_rate_limits_with_now(None, &chrono_tz::UTC, now),
+            "No rate limit data found"
+        );
+    }
+
+    #[test]
+    fn test_json_formatter_rate_limits() {
+        let snapshot = rate_limit_fixture();
+        let output = JsonFormatter::new().format_rate_limits(Some(&snapshot), &chrono_tz::UTC);
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        let limits = &json["rate_limits"];
+        assert_eq!(limits["session_id"], "rollout-1");
+        assert_eq!(limits["primary"]["used_percent"], 42.5);
+        assert_eq!(limits["primary"]["window_minutes"], 300);
+        assert_eq!(limits["primary"]["resets_at"], "2025-01-01T11:30:00Z");
+        assert!(limits["secondary"]["resets_at"].is_null());
+
+        let empty: serde_json::Value =
+            serde_json::from_str(&JsonFormatter::new().format_rate_limits(None, &chrono_tz::UTC))
+                .unwrap();
+        assert!(empty["rate_limits"].is_null());
     }
 
     #[test]
@@ -1280,8 +2106,9 @@ mod tests {
         let zero_data = vec![DailyUsage {
             date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
             tokens: TokenCounts::new(0, 0, 0, 0),
-            total_cost: 0.0,
+            total_cost: Cost::from_usd(0.0),
             models_used: vec![],
+            model_breakdowns: Vec::new(),
             entries: None,
         }];
         let zero_totals = Totals::from_daily(&zero_data);
@@ -1292,8 +2119,9 @@ mod tests {
         let large_data = vec![DailyUsage {
             date: DailyDate::new(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
             tokens: TokenCounts::new(999999999, 888888888, 777777777, 666666666),
-            total_cost: 9999999.99,
+            total_cost: Cost::from_usd(9999999.99),
             models_used: vec!["model".to_string()],
+            model_breakdowns: Vec::new(),
             entries: None,
         }];
         let large_totals = Totals::from_daily(&large_data);
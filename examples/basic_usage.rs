@@ -3,8 +3,9 @@
 //! This example shows how to use ccstat as a library to analyze Claude usage data.
 
 use ccstat::{
-    Result, aggregation::Aggregator, cost_calculator::CostCalculator, data_loader::DataLoader,
-    pricing_fetcher::PricingFetcher, timezone::TimezoneConfig, types::CostMode,
+    Cost, Result, aggregation::Aggregator, cost_calculator::CostCalculator,
+    data_loader::DataLoader, pricing_fetcher::PricingFetcher, timezone::TimezoneConfig,
+    types::CostMode,
 };
 use std::sync::Arc;
 
@@ -42,7 +43,7 @@ async fn main() -> Result<()> {
 
     // Calculate totals
     let total_tokens: u64 = daily_data.iter().map(|d| d.tokens.total()).sum();
-    let total_cost: f64 = daily_data.iter().map(|d| d.total_cost).sum();
+    let total_cost: Cost = daily_data.iter().map(|d| d.total_cost).sum();
 
     println!("\nTotal Usage:");
     println!("============");
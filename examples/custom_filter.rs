@@ -3,9 +3,9 @@
 //! This example demonstrates how to filter usage data by date range and project.
 
 use ccstat::{
-    Result, aggregation::Aggregator, cost_calculator::CostCalculator, data_loader::DataLoader,
-    filters::UsageFilter, pricing_fetcher::PricingFetcher, timezone::TimezoneConfig,
-    types::CostMode,
+    Cost, Result, aggregation::Aggregator, cost_calculator::CostCalculator,
+    data_loader::DataLoader, filters::UsageFilter, pricing_fetcher::PricingFetcher,
+    timezone::TimezoneConfig, types::CostMode,
 };
 use chrono::NaiveDate;
 use std::sync::Arc;
@@ -57,7 +57,7 @@ async fn main() -> Result<()> {
 
         // Monthly summary
         let total_tokens: u64 = daily_data.iter().map(|d| d.tokens.total()).sum();
-        let total_cost: f64 = daily_data.iter().map(|d| d.total_cost).sum();
+        let total_cost: Cost = daily_data.iter().map(|d| d.total_cost).sum();
 
         println!("\nJanuary 2024 Summary:");
         println!("====================");
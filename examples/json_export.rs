@@ -6,6 +6,7 @@ use ccstat::{
     Result,
     aggregation::{Aggregator, Totals},
     cost_calculator::CostCalculator,
+    currency::Currency,
     data_loader::DataLoader,
     output::get_formatter,
     pricing_fetcher::PricingFetcher,
@@ -39,7 +40,7 @@ async fn main() -> Result<()> {
     let totals = Totals::from_daily(&daily_data);
 
     // Export to JSON
-    let json_formatter = get_formatter(true, false);
+    let json_formatter = get_formatter(true, false, Currency::usd());
     let json_output = json_formatter.format_daily(&daily_data, &totals);
 
     // Save to file
@@ -51,12 +51,15 @@
 //! # }
 //! ```
 
+use crate::cost::Cost;
 use crate::cost_calculator::CostCalculator;
-use crate::data_loader::DataLoader;
 use crate::error::{CcstatError, Result};
 use crate::filters::MonthFilter;
+use crate::provider::DynDataLoader;
 use crate::timezone::TimezoneConfig;
-use crate::types::{CostMode, DailyDate, ModelName, SessionId, TokenCounts, UsageEntry};
+use crate::types::{
+    CostMode, DailyDate, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry,
+};
 use chrono::Datelike;
 use futures::stream::{Stream, StreamExt, TryStreamExt};
 use indicatif::{ProgressBar, ProgressStyle};
@@ -65,15 +68,15 @@ use std::sync::Arc;
 
 // Re-export aggregation data types from ccstat-core
 pub use ccstat_core::aggregation_types::{
-    DailyInstanceUsage, DailyUsage, MonthlyUsage, SessionBlock, SessionUsage, Totals, VerboseEntry,
-    WeeklyUsage,
+    DailyInstanceUsage, DailyUsage, ModelBreakdown, MonthlyUsage, ProviderUsage, SessionBlock,
+    SessionUsage, Totals, VerboseEntry, WeeklyUsage,
 };
 
 /// Accumulator for daily aggregation
 struct DailyAccumulator {
     tokens: TokenCounts,
-    cost: f64,
-    models: HashSet<ModelName>,
+    cost: Cost,
+    models: HashMap<(ProviderId, ModelName), (TokenCounts, Cost)>,
     verbose_entries: Option<Vec<VerboseEntry>>,
 }
 
@@ -81,16 +84,21 @@ impl DailyAccumulator {
     fn new(detailed: bool) -> Self {
         Self {
             tokens: TokenCounts::default(),
-            cost: 0.0,
-            models: HashSet::new(),
+            cost: Cost::ZERO,
+            models: HashMap::new(),
             verbose_entries: if detailed { Some(Vec::new()) } else { None },
         }
     }
 
-    fn add_entry(&mut self, entry: &UsageEntry, calculated_cost: f64) {
+    fn add_entry(&mut self, entry: &UsageEntry, calculated_cost: Cost) {
         self.tokens += entry.tokens;
         self.cost += calculated_cost;
-        self.models.insert(entry.model.clone());
+        let model = self
+            .models
+            .entry((entry.provider.clone(), entry.model.clone()))
+            .or_default();
+        model.0 += entry.tokens;
+        model.1 += calculated_cost;
 
         if let Some(ref mut entries) = self.verbose_entries {
             entries.push(VerboseEntry {
@@ -104,14 +112,29 @@ impl DailyAccumulator {
     }
 
     fn into_daily_usage(self, date: DailyDate) -> DailyUsage {
-        let mut models_used: Vec<String> = self.models.into_iter().map(|m| m.to_string()).collect();
+        let mut model_breakdowns: Vec<ModelBreakdown> = self
+            .models
+            .into_iter()
+            .map(|((provider, model), (tokens, cost))| ModelBreakdown {
+                provider,
+                model: model.to_string(),
+                tokens,
+                cost,
+            })
+            .collect();
+        model_breakdowns.sort_by(|a, b| (&a.provider, &a.model).cmp(&(&b.provider, &b.model)));
+
+        let mut models_used: Vec<String> =
+            model_breakdowns.iter().map(|m| m.model.clone()).collect();
         models_used.sort();
+        models_used.dedup();
 
         DailyUsage {
             date,
             tokens: self.tokens,
             total_cost: self.cost,
             models_used,
+            model_breakdowns,
             entries: self.verbose_entries,
         }
     }
@@ -122,7 +145,7 @@ struct SessionAccumulator {
     start_time: Option<chrono::DateTime<chrono::Utc>>,
     end_time: Option<chrono::DateTime<chrono::Utc>>,
     tokens: TokenCounts,
-    cost: f64,
+    cost: Cost,
     primary_model: Option<ModelName>,
 }
 
@@ -132,12 +155,12 @@ impl SessionAccumulator {
             start_time: None,
             end_time: None,
             tokens: TokenCounts::default(),
-            cost: 0.0,
+            cost: Cost::ZERO,
             primary_model: None,
         }
     }
 
-    fn add_entry(&mut self, entry: &UsageEntry, calculated_cost: f64) {
+    fn add_entry(&mut self, entry: &UsageEntry, calculated_cost: Cost) {
         let timestamp = entry.timestamp.inner();
 
         // Update time bounds
@@ -156,7 +179,7 @@ impl SessionAccumulator {
         }
     }
 
-    fn into_session_usage(self, session_id: SessionId) -> SessionUsage {
+    fn into_session_usage(self, provider: ProviderId, session_id: SessionId) -> SessionUsage {
         SessionUsage {
             session_id,
             start_time: self.start_time.unwrap_or_default(),
@@ -166,6 +189,7 @@ impl SessionAccumulator {
             model: self
                 .primary_model
                 .unwrap_or_else(|| ModelName::new("unknown")),
+            provider,
         }
     }
 }
@@ -175,6 +199,8 @@ pub struct Aggregator {
     cost_calculator: Arc<CostCalculator>,
     show_progress: bool,
     timezone_config: TimezoneConfig,
+    /// Providers whose usage is priced only at the costs they recorded
+    stored_cost_providers: Vec<&'static str>,
 }
 
 /// Helper struct to group block parameters for finalize_block function
@@ -184,11 +210,11 @@ struct BlockData {
     first_entry_time: Option<chrono::DateTime<chrono::Utc>>,
     last_entry_time: Option<chrono::DateTime<chrono::Utc>>,
     tokens: TokenCounts,
-    cost: f64,
+    cost: Cost,
     models: HashSet<ModelName>,
     projects: HashSet<String>,
     now: chrono::DateTime<chrono::Utc>,
-    entries: Vec<(UsageEntry, f64)>, // Store entries with their calculated costs
+    entries: Vec<(UsageEntry, Cost)>, // Store entries with their calculated costs
 }
 
 impl Aggregator {
@@ -198,6 +224,11 @@ impl Aggregator {
             cost_calculator,
             show_progress: false,
             timezone_config,
+            stored_cost_providers: crate::providers::registry()
+                .iter()
+                .filter(|info| info.stored_costs)
+                .map(|info| info.name)
+                .collect(),
         }
     }
 
@@ -212,6 +243,28 @@ impl Aggregator {
         &self.timezone_config
     }
 
+    /// Cost of an entry, at the provider's recorded cost when the provider
+    /// only has stored costs
+    async fn entry_cost(&self, entry: &UsageEntry, cost_mode: CostMode) -> Result<Cost> {
+        let cost_mode = if self
+            .stored_cost_providers
+            .contains(&entry.provider.as_str())
+        {
+            CostMode::Display
+        } else {
+            cost_mode
+        };
+        self.cost_calculator
+            .calculate_with_mode(
+                &entry.tokens,
+                &entry.model,
+                *entry.timestamp.inner(),
+                entry.total_cost,
+                cost_mode,
+            )
+            .await
+    }
+
     /// Aggregate entries by day and instance
     pub async fn aggregate_daily_by_instance(
         &self,
@@ -248,10 +301,7 @@ impl Aggregator {
                 .unwrap_or_else(|| "default".to_string());
 
             // Calculate cost
-            let cost = self
-                .cost_calculator
-                .calculate_with_mode(&entry.tokens, &entry.model, entry.total_cost, cost_mode)
-                .await?;
+            let cost = self.entry_cost(&entry, cost_mode).await?;
 
             daily_map
                 .entry((date, instance_id.clone()))
@@ -271,9 +321,13 @@ impl Aggregator {
         Ok(daily_map
             .into_iter()
             .map(|((date, instance_id), acc)| {
-                let mut models_used: Vec<String> =
-                    acc.models.into_iter().map(|m| m.to_string()).collect();
+                let mut models_used: Vec<String> = acc
+                    .models
+                    .into_keys()
+                    .map(|(_, model)| model.to_string())
+                    .collect();
                 models_used.sort();
+                models_used.dedup();
 
                 DailyInstanceUsage {
                     date,
@@ -329,10 +383,7 @@ impl Aggregator {
                 DailyDate::from_timestamp_with_tz(&entry.timestamp, &self.timezone_config.tz);
 
             // Calculate cost
-            let cost = self
-                .cost_calculator
-                .calculate_with_mode(&entry.tokens, &entry.model, entry.total_cost, cost_mode)
-                .await?;
+            let cost = self.entry_cost(&entry, cost_mode).await?;
 
             daily_map
                 .entry(date)
@@ -365,7 +416,9 @@ impl Aggregator {
         entries: impl Stream<Item = Result<UsageEntry>>,
         cost_mode: CostMode,
     ) -> Result<Vec<SessionUsage>> {
-        let mut session_map: BTreeMap<SessionId, SessionAccumulator> = BTreeMap::new();
+        // Keyed by provider too, so equal session ids from different tools stay apart
+        let mut session_map: BTreeMap<(ProviderId, SessionId), SessionAccumulator> =
+            BTreeMap::new();
 
         // Create progress spinner if enabled
         let progress = if self.show_progress {
@@ -387,16 +440,13 @@ impl Aggregator {
         tokio::pin!(entries);
         while let Some(result) = entries.next().await {
             let entry = result?;
-            let session_id = entry.session_id.clone();
+            let key = (entry.provider.clone(), entry.session_id.clone());
 
             // Calculate cost
-            let cost = self
-                .cost_calculator
-                .calculate_with_mode(&entry.tokens, &entry.model, entry.total_cost, cost_mode)
-                .await?;
+            let cost = self.entry_cost(&entry, cost_mode).await?;
 
             session_map
-                .entry(session_id)
+                .entry(key)
                 .or_insert_with(SessionAccumulator::new)
                 .add_entry(&entry, cost);
 
@@ -416,7 +466,7 @@ impl Aggregator {
 
         let mut sessions: Vec<_> = session_map
             .into_iter()
-            .map(|(id, acc)| acc.into_session_usage(id))
+            .map(|((provider, id), acc)| acc.into_session_usage(provider, id))
             .collect();
 
         // Sort by start time
@@ -427,13 +477,13 @@ impl Aggregator {
 
     /// Aggregate daily usage into monthly summaries
     pub fn aggregate_monthly(daily_usage: &[DailyUsage]) -> Vec<MonthlyUsage> {
-        let mut monthly_map: BTreeMap<String, (TokenCounts, f64, usize)> = BTreeMap::new();
+        let mut monthly_map: BTreeMap<String, (TokenCounts, Cost, usize)> = BTreeMap::new();
 
         for daily in daily_usage {
             let month = daily.date.format("%Y-%m");
             let entry = monthly_map
                 .entry(month)
-                .or_insert((TokenCounts::default(), 0.0, 0));
+                .or_insert((TokenCounts::default(), Cost::ZERO, 0));
 
             entry.0 += daily.tokens;
             entry.1 += daily.total_cost;
@@ -459,7 +509,7 @@ impl Aggregator {
         daily_usage: &[DailyUsage],
         start_of_week: chrono::Weekday,
     ) -> Vec<WeeklyUsage> {
-        let mut weekly_map: BTreeMap<String, (TokenCounts, f64, usize)> = BTreeMap::new();
+        let mut weekly_map: BTreeMap<String, (TokenCounts, Cost, usize)> = BTreeMap::new();
 
         for daily in daily_usage {
             let date = *daily.date.inner();
@@ -471,9 +521,10 @@ impl Aggregator {
             let week_start = date - chrono::Duration::days(days_since_start);
             let week_key = week_start.format("%Y-%m-%d").to_string();
 
-            let entry = weekly_map
-                .entry(week_key)
-                .or_insert((TokenCounts::default(), 0.0, 0));
+            let entry =
+                weekly_map
+                    .entry(week_key)
+                    .or_insert((TokenCounts::default(), Cost::ZERO, 0));
 
             entry.0 += daily.tokens;
             entry.1 += daily.total_cost;
@@ -511,7 +562,7 @@ impl Aggregator {
         let mut current_block_start: Option<chrono::DateTime<chrono::Utc>> = None;
         let mut current_sessions = Vec::new();
         let mut current_tokens = TokenCounts::default();
-        let mut current_cost = 0.0;
+        let mut current_cost = Cost::ZERO;
         let mut models_used = HashSet::new();
 
         let now = chrono::Utc::now();
@@ -607,7 +658,7 @@ impl Aggregator {
         projects_used.sort();
 
         // Group entries by session_id to create SessionUsage objects
-        let mut session_map: HashMap<SessionId, Vec<(UsageEntry, f64)>> = HashMap::new();
+        let mut session_map: HashMap<SessionId, Vec<(UsageEntry, Cost)>> = HashMap::new();
         for (entry, cost) in data.entries {
             session_map
                 .entry(entry.session_id.clone())
@@ -633,13 +684,13 @@ impl Aggregator {
                 .max()
                 .unwrap();
             let mut tokens = TokenCounts::default();
-            let mut total_cost = 0.0;
+            let mut total_cost = Cost::ZERO;
 
             // Use the most frequently used model in the session
             let mut model_counts: HashMap<ModelName, usize> = HashMap::new();
             for (entry, cost) in &entries {
                 tokens += entry.tokens;
-                total_cost += cost;
+                total_cost += *cost;
                 *model_counts.entry(entry.model.clone()).or_default() += 1;
             }
 
@@ -656,6 +707,7 @@ impl Aggregator {
                 tokens,
                 total_cost,
                 model,
+                provider: entries[0].0.provider.clone(),
             });
         }
 
@@ -719,10 +771,10 @@ impl Aggregator {
         let mut blocks = Vec::new();
         let mut current_block_start: Option<chrono::DateTime<chrono::Utc>> = None;
         let mut current_tokens = TokenCounts::default();
-        let mut current_cost = 0.0;
+        let mut current_cost = Cost::ZERO;
         let mut current_models = HashSet::new();
         let mut current_projects = HashSet::new();
-        let mut current_entries: Vec<(UsageEntry, f64)> = Vec::new(); // Track entries for current block
+        let mut current_entries: Vec<(UsageEntry, Cost)> = Vec::new(); // Track entries for current block
         let mut first_entry_time: Option<chrono::DateTime<chrono::Utc>> = None;
         let mut last_entry_time: Option<chrono::DateTime<chrono::Utc>> = None;
 
@@ -785,7 +837,7 @@ impl Aggregator {
                             actual_end_time: None,
                             sessions: Vec::new(),
                             tokens: TokenCounts::default(),
-                            total_cost: 0.0,
+                            total_cost: Cost::ZERO,
                             models_used: Vec::new(),
                             projects_used: Vec::new(),
                             is_active: false,
@@ -804,10 +856,7 @@ impl Aggregator {
             first_entry_time.get_or_insert(entry_time);
 
             // Calculate cost for this entry
-            let entry_cost = self
-                .cost_calculator
-                .calculate_with_mode(&entry.tokens, &entry.model, entry.total_cost, cost_mode)
-                .await?;
+            let entry_cost = self.entry_cost(&entry, cost_mode).await?;
 
             // Add entry to current block
             current_tokens += entry.tokens;
@@ -956,9 +1005,9 @@ pub fn apply_token_limit_warnings(
 }
 
 /// Parameters for creating and filtering billing blocks
-pub struct BillingBlockParams<'a> {
-    /// DataLoader instance to load usage entries
-    pub data_loader: &'a DataLoader,
+pub struct BillingBlockParams<'a, L: DynDataLoader + ?Sized> {
+    /// Provider data loader to load usage entries from
+    pub data_loader: &'a L,
     /// Aggregator instance to create billing blocks
     pub aggregator: &'a Aggregator,
     /// Cost calculation mode
@@ -987,17 +1036,17 @@ pub struct BillingBlockParams<'a> {
 /// 1. Creating billing blocks from all entries (to ensure correct block boundaries)
 /// 2. Filtering blocks by date, project, and other criteria
 /// 3. Applying additional filters (active, recent, token limit)
-pub async fn create_and_filter_billing_blocks(
-    params: BillingBlockParams<'_>,
+pub async fn create_and_filter_billing_blocks<L: DynDataLoader + ?Sized>(
+    params: BillingBlockParams<'_, L>,
 ) -> Result<Vec<SessionBlock>> {
-    let entries = params.data_loader.load_usage_entries_parallel();
+    let entries = params.data_loader.load_entries();
 
     // Always process all entries first to correctly calculate block boundaries,
     // especially for blocks that span across date filter boundaries. Then, filter the blocks.
     let mut blocks = params
         .aggregator
         .create_billing_blocks_from_entries(
-            Box::pin(entries),
+            entries,
             params.cost_mode,
             params.session_duration_hours,
         )
@@ -1039,11 +1088,12 @@ mod tests {
             total_cost: Some(0.01),
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
-        acc.add_entry(&entry, 0.01);
+        acc.add_entry(&entry, Cost::from_usd(0.01));
         assert_eq!(acc.tokens.input_tokens, 100);
-        assert_eq!(acc.cost, 0.01);
+        assert_eq!(acc.cost, Cost::from_usd(0.01));
         assert_eq!(acc.models.len(), 1);
     }
 
@@ -1059,11 +1109,12 @@ mod tests {
             total_cost: Some(0.01),
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
-        acc.add_entry(&entry, 0.01);
+        acc.add_entry(&entry, Cost::from_usd(0.01));
         assert_eq!(acc.tokens.input_tokens, 100);
-        assert_eq!(acc.cost, 0.01);
+        assert_eq!(acc.cost, Cost::from_usd(0.01));
         assert_eq!(acc.models.len(), 1);
         assert!(acc.verbose_entries.is_some());
         assert_eq!(acc.verbose_entries.unwrap().len(), 1);
@@ -1078,24 +1129,27 @@ mod tests {
                 start_time: base_time,
                 end_time: base_time + chrono::Duration::hours(1),
                 tokens: TokenCounts::new(100, 50, 0, 0),
-                total_cost: 0.01,
+                total_cost: Cost::from_usd(0.01),
                 model: ModelName::new("claude-3-opus"),
+                provider: ProviderId::new("claude"),
             },
             SessionUsage {
                 session_id: SessionId::new("s2"),
                 start_time: base_time + chrono::Duration::hours(3),
                 end_time: base_time + chrono::Duration::hours(4),
                 tokens: TokenCounts::new(200, 100, 0, 0),
-                total_cost: 0.02,
+                total_cost: Cost::from_usd(0.02),
                 model: ModelName::new("claude-3-opus"),
+                provider: ProviderId::new("claude"),
             },
             SessionUsage {
                 session_id: SessionId::new("s3"),
                 start_time: base_time + chrono::Duration::hours(6),
                 end_time: base_time + chrono::Duration::hours(7),
                 tokens: TokenCounts::new(150, 75, 0, 0),
-                total_cost: 0.015,
+                total_cost: Cost::from_usd(0.015),
                 model: ModelName::new("claude-3-opus"),
+                provider: ProviderId::new("claude"),
             },
         ];
 
@@ -1124,24 +1178,27 @@ mod tests {
                 start_time: base_time, // Starts at 19:23:45
                 end_time: base_time + chrono::Duration::hours(1),
                 tokens: TokenCounts::new(100, 50, 0, 0),
-                total_cost: 0.01,
+                total_cost: Cost::from_usd(0.01),
                 model: ModelName::new("claude-3-opus"),
+                provider: ProviderId::new("claude"),
             },
             SessionUsage {
                 session_id: SessionId::new("s2"),
                 start_time: base_time + chrono::Duration::minutes(90), // 20:53:45
                 end_time: base_time + chrono::Duration::hours(2),
                 tokens: TokenCounts::new(200, 100, 0, 0),
-                total_cost: 0.02,
+                total_cost: Cost::from_usd(0.02),
                 model: ModelName::new("claude-3-opus"),
+                provider: ProviderId::new("claude"),
             },
             SessionUsage {
                 session_id: SessionId::new("s3"),
                 start_time: base_time + chrono::Duration::hours(5) + chrono::Duration::minutes(10), // 00:33:45 next day
                 end_time: base_time + chrono::Duration::hours(6),
                 tokens: TokenCounts::new(150, 75, 0, 0),
-                total_cost: 0.015,
+                total_cost: Cost::from_usd(0.015),
                 model: ModelName::new("claude-3-opus"),
+                provider: ProviderId::new("claude"),
             },
         ];
 
@@ -1184,8 +1241,9 @@ mod tests {
                 start_time: two_hours_ago,
                 end_time: two_hours_ago + chrono::Duration::minutes(30),
                 tokens: TokenCounts::new(100, 50, 0, 0),
-                total_cost: 0.01,
+                total_cost: Cost::from_usd(0.01),
                 model: ModelName::new("claude-3-opus"),
+                provider: ProviderId::new("claude"),
             },
             // Session that starts a new block (more than 5 hours after the first)
             SessionUsage {
@@ -1197,8 +1255,9 @@ mod tests {
                     + chrono::Duration::hours(5)
                     + chrono::Duration::minutes(31),
                 tokens: TokenCounts::new(200, 100, 0, 0),
-                total_cost: 0.02,
+                total_cost: Cost::from_usd(0.02),
                 model: ModelName::new("claude-3-opus"),
+                provider: ProviderId::new("claude"),
             },
         ];
 
@@ -1225,8 +1284,9 @@ mod tests {
             start_time: six_hours_ago,
             end_time: six_hours_ago + chrono::Duration::minutes(30),
             tokens: TokenCounts::new(100, 50, 0, 0),
-            total_cost: 0.01,
+            total_cost: Cost::from_usd(0.01),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         }];
 
         let old_blocks = Aggregator::create_billing_blocks(&old_sessions);
@@ -1259,6 +1319,7 @@ mod tests {
                 total_cost: Some(0.01),
                 project: None,
                 instance_id: None,
+                provider: ProviderId::new("claude"),
             },
             // Entry 3 hours later (still in same block)
             UsageEntry {
@@ -1269,6 +1330,7 @@ mod tests {
                 total_cost: Some(0.02),
                 project: None,
                 instance_id: None,
+                provider: ProviderId::new("claude"),
             },
             // Entry 9 hours later (should create gap block and new block)
             UsageEntry {
@@ -1279,6 +1341,7 @@ mod tests {
                 total_cost: Some(0.015),
                 project: None,
                 instance_id: None,
+                provider: ProviderId::new("claude"),
             },
         ];
 
@@ -1320,6 +1383,112 @@ mod tests {
         assert_eq!(blocks[2].models_used, vec!["claude-3-sonnet"]);
     }
 
+    #[tokio::test]
+    async fn test_provider_tagged_aggregation() {
+        use crate::pricing_fetcher::PricingFetcher;
+        use futures::stream;
+
+        let pricing_fetcher = Arc::new(PricingFetcher::new(true).await);
+        let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
+        let aggregator = Aggregator::new(
+            cost_calculator,
+            TimezoneConfig::from_cli(None, true).unwrap(),
+        );
+
+        let timestamp = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let entry = |provider: &str, model: &str, cost: f64| UsageEntry {
+            session_id: SessionId::new("shared-id"),
+            timestamp: crate::types::ISOTimestamp::new(timestamp),
+            model: ModelName::new(model),
+            tokens: TokenCounts::new(100, 50, 0, 0),
+            total_cost: Some(cost),
+            project: None,
+            instance_id: None,
+            provider: ProviderId::new(provider),
+        };
+        let entries = vec![
+            entry("claude", "claude-sonnet-4", 1.0),
+            entry("codex", "gpt-5", 0.5),
+            entry("codex", "gpt-5", 0.25),
+            entry("pi", "claude-sonnet-4", 2.0),
+        ];
+
+        let daily = aggregator
+            .aggregate_daily(
+                stream::iter(entries.clone().into_iter().map(Ok)),
+                CostMode::Display,
+            )
+            .await
+            .unwrap();
+        assert_eq!(daily.len(), 1);
+        assert_eq!(daily[0].models_used, vec!["claude-sonnet-4", "gpt-5"]);
+        let breakdowns: Vec<_> = daily[0]
+            .model_breakdowns
+            .iter()
+            .map(|m| (m.provider.as_str(), m.model.as_str(), m.cost.to_usd()))
+            .collect();
+        assert_eq!(
+            breakdowns,
+            vec![
+                ("claude", "claude-sonnet-4", 1.0),
+                ("codex", "gpt-5", 0.75),
+                ("pi", "claude-sonnet-4", 2.0),
+            ]
+        );
+        assert_eq!(daily[0].model_breakdowns[1].tokens.input_tokens, 200);
+
+        // The same session id from different providers stays separate
+        let sessions = aggregator
+            .aggregate_sessions(stream::iter(entries.into_iter().map(Ok)), CostMode::Display)
+            .await
+            .unwrap();
+        let mut providers: Vec<_> = sessions.iter().map(|s| s.provider.as_str()).collect();
+        providers.sort();
+        assert_eq!(providers, vec!["claude", "codex", "pi"]);
+    }
+
+    #[tokio::test]
+    async fn test_stored_cost_providers_keep_recorded_cost() {
+        use crate::pricing_fetcher::PricingFetcher;
+        use futures::stream;
+
+        let pricing_fetcher = Arc::new(PricingFetcher::new(true).await);
+        let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
+        let aggregator = Aggregator::new(
+            cost_calculator,
+            TimezoneConfig::from_cli(None, true).unwrap(),
+        );
+
+        let timestamp = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let entry = |provider: &str| UsageEntry {
+            session_id: SessionId::new(provider),
+            timestamp: crate::types::ISOTimestamp::new(timestamp),
+            model: ModelName::new("claude-sonnet-4-5-20250929"),
+            tokens: TokenCounts::new(100_000, 0, 0, 0),
+            total_cost: Some(0.042),
+            project: None,
+            instance_id: None,
+            provider: ProviderId::new(provider),
+        };
+
+        let sessions = aggregator
+            .aggregate_sessions(
+                stream::iter(vec![Ok(entry("claude")), Ok(entry("crush"))]),
+                CostMode::Calculate,
+            )
+            .await
+            .unwrap();
+        let cost = |provider: &str| {
+            sessions
+                .iter()
+                .find(|s| s.provider.as_str() == provider)
+                .unwrap()
+                .total_cost
+        };
+        assert_eq!(cost("crush"), Cost::from_usd(0.042));
+        assert_eq!(cost("claude"), Cost::from_usd(0.3));
+    }
+
     #[tokio::test]
     async fn test_billing_blocks_active_determination() {
         use crate::pricing_fetcher::PricingFetcher;
@@ -1342,6 +1511,7 @@ mod tests {
                 total_cost: Some(0.01),
                 project: None,
                 instance_id: None,
+                provider: ProviderId::new("claude"),
             },
             UsageEntry {
                 session_id: SessionId::new("recent"),
@@ -1351,6 +1521,7 @@ mod tests {
                 total_cost: Some(0.01),
                 project: None,
                 instance_id: None,
+                provider: ProviderId::new("claude"),
             },
         ];
 
@@ -1375,6 +1546,7 @@ mod tests {
             total_cost: Some(0.01),
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         }];
 
         let stream = stream::iter(old_entries.into_iter().map(Ok));
@@ -0,0 +1,302 @@
+//! Combined reports across every provider
+//!
+//! `ccstat all <report>` loads each registered provider that has local data,
+//! aggregates its entries the same way a single-provider report would, and
+//! keeps the results grouped by provider. Formatters then render one table (or JSON
+//! document) with a provider column, a subtotal per provider, and the grand
+//! total.
+//!
+//! Providers whose data directory cannot be found are skipped, so the
+//! combined view works on machines that only use some of the tools. A
+//! provider whose data fails to load is skipped with a warning, so one
+//! broken provider does not hide the others.
+//!
+//! Each provider's entries are streamed straight into its aggregation, so
+//! memory use does not grow with the combined history.
+
+use crate::aggregation::{
+    Aggregator, DailyUsage, MonthlyUsage, ProviderUsage, SessionUsage, Totals, WeeklyUsage,
+    filter_monthly_data,
+};
+use crate::cli::Provider;
+use crate::error::{CcstatError, Result};
+use crate::filters::{MonthFilter, UsageFilter};
+use crate::provider::DynDataLoader;
+use crate::providers::registry;
+use crate::types::{CostMode, UsageEntry};
+use futures::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use tracing::{debug, warn};
+
+/// Data loader of a single provider
+pub type ProviderLoader = (Provider, Box<dyn DynDataLoader>);
+
+/// Filtered usage entries of a single provider, streamed
+pub type ProviderEntries<'a> = (
+    Provider,
+    Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + 'a>>,
+);
+
+/// Data loaders of every registered provider with local data
+///
+/// Providers are returned in registry order, limited to the filter's date
+/// range.
+pub async fn load_providers(filter: &UsageFilter) -> Vec<ProviderLoader> {
+    let mut loaders = Vec::new();
+
+    for info in registry().iter() {
+        let provider = Provider::Registered(info.name);
+        match info.load().await {
+            Ok(data_loader) => {
+                let data_loader = data_loader.with_date_range(filter.since_date, filter.until_date);
+                loaders.push((provider, data_loader));
+            }
+            Err(CcstatError::NoClaudeDirectory) => {
+                debug!("Skipping {} provider: no local data", provider);
+            }
+            Err(e) => warn!("Skipping {} provider: {}", provider, e),
+        }
+    }
+
+    loaders
+}
+
+/// Stream each provider's entries through the filter
+pub async fn provider_entries<'a>(
+    loaders: &'a [ProviderLoader],
+    filter: &UsageFilter,
+) -> Vec<ProviderEntries<'a>> {
+    let mut entries = Vec::with_capacity(loaders.len());
+    for (provider, data_loader) in loaders {
+        let stream = filter
+            .clone()
+            .filter_stream(data_loader.load_entries())
+            .await;
+        entries.push((*provider, Box::pin(stream) as Pin<Box<_>>));
+    }
+    entries
+}
+
+/// Aggregate each provider with `aggregate`
+///
+/// Providers without any usage are omitted; providers whose entries fail
+/// to load are skipped with a warning.
+async fn aggregate_by_provider<'a, T, F, Fut>(
+    entries: Vec<ProviderEntries<'a>>,
+    aggregate: F,
+    totals: fn(&[T]) -> Totals,
+) -> Vec<ProviderUsage<T>>
+where
+    F: Fn(Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + 'a>>) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+{
+    let mut result = Vec::with_capacity(entries.len());
+    for (provider, stream) in entries {
+        match aggregate(stream).await {
+            Ok(data) if data.is_empty() => {}
+            Ok(data) => {
+                debug!("Aggregated {} rows from {}", data.len(), provider);
+                result.push(ProviderUsage {
+                    provider: provider.id(),
+                    totals: totals(&data),
+                    data,
+                });
+            }
+            Err(e) => warn!("Skipping {} provider: {}", provider, e),
+        }
+    }
+    result
+}
+
+/// Aggregate each provider's entries by day
+pub async fn aggregate_daily_by_provider(
+    aggregator: &Aggregator,
+    entries: Vec<ProviderEntries<'_>>,
+    cost_mode: CostMode,
+) -> Vec<ProviderUsage<DailyUsage>> {
+    aggregate_by_provider(
+        entries,
+        |stream| aggregator.aggregate_daily(stream, cost_mode),
+        Totals::from_daily,
+    )
+    .await
+}
+
+/// Aggregate each provider's entries by session
+pub async fn aggregate_sessions_by_provider(
+    aggregator: &Aggregator,
+    entries: Vec<ProviderEntries<'_>>,
+    cost_mode: CostMode,
+) -> Vec<ProviderUsage<SessionUsage>> {
+    aggregate_by_provider(
+        entries,
+        |stream| aggregator.aggregate_sessions(stream, cost_mode),
+        Totals::from_sessions,
+    )
+    .await
+}
+
+/// Roll each provider's daily usage up into months
+///
+/// Providers left without any months after filtering are dropped.
+pub fn aggregate_monthly_by_provider(
+    daily: &[ProviderUsage<DailyUsage>],
+    month_filter: &MonthFilter,
+) -> Vec<ProviderUsage<MonthlyUsage>> {
+    daily
+        .iter()
+        .filter_map(|p| {
+            let mut data = Aggregator::aggregate_monthly(&p.data);
+            filter_monthly_data(&mut data, month_filter);
+            (!data.is_empty()).then(|| ProviderUsage {
+                provider: p.provider.clone(),
+                totals: Totals::from_monthly(&data),
+                data,
+            })
+        })
+        .collect()
+}
+
+/// Roll each provider's daily usage up into weeks
+pub fn aggregate_weekly_by_provider(
+    daily: &[ProviderUsage<DailyUsage>],
+    start_of_week: chrono::Weekday,
+) -> Vec<ProviderUsage<WeeklyUsage>> {
+    daily
+        .iter()
+        .map(|p| {
+            let data = Aggregator::aggregate_weekly(&p.data, start_of_week);
+            ProviderUsage {
+                provider: p.provider.clone(),
+                totals: Totals::from_weekly(&data),
+                data,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cost::Cost;
+    use crate::cost_calculator::CostCalculator;
+    use crate::pricing_fetcher::PricingFetcher;
+    use crate::timezone::TimezoneConfig;
+    use crate::types::{ISOTimestamp, ModelName, SessionId, TokenCounts};
+    use chrono::{TimeZone, Utc};
+    use std::sync::Arc;
+
+    fn entry(provider: Provider, session: &str, day: u32, month: u32, cost: f64) -> UsageEntry {
+        UsageEntry {
+            session_id: SessionId::new(session),
+            timestamp: ISOTimestamp::new(Utc.with_ymd_and_hms(2025, month, day, 12, 0, 0).unwrap()),
+            model: ModelName::new("claude-sonnet-4"),
+            tokens: TokenCounts::new(100, 50, 0, 0),
+            total_cost: Some(cost),
+            project: None,
+            instance_id: None,
+            provider: provider.id(),
+        }
+    }
+
+    async fn aggregator() -> Aggregator {
+        let pricing_fetcher = Arc::new(PricingFetcher::new(true).await);
+        let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
+        Aggregator::new(
+            cost_calculator,
+            TimezoneConfig::from_cli(None, true).unwrap(),
+        )
+    }
+
+    fn stream(
+        provider: &'static str,
+        entries: Vec<Result<UsageEntry>>,
+    ) -> ProviderEntries<'static> {
+        (
+            Provider::Registered(provider),
+            Box::pin(futures::stream::iter(entries)),
+        )
+    }
+
+    fn fixture() -> Vec<ProviderEntries<'static>> {
+        let claude = Provider::Registered("claude");
+        let codex = Provider::Registered("codex");
+        vec![
+            stream(
+                "claude",
+                vec![
+                    Ok(entry(claude, "c1", 10, 1, 1.0)),
+                    Ok(entry(claude, "c1", 11, 1, 2.0)),
+                ],
+            ),
+            stream(
+                "codex",
+                vec![
+                    Ok(entry(codex, "x1", 10, 1, 0.5)),
+                    Ok(entry(codex, "x2", 3, 2, 0.25)),
+                ],
+            ),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_daily_by_provider_keeps_subtotals() {
+        let daily =
+            aggregate_daily_by_provider(&aggregator().await, fixture(), CostMode::Display).await;
+
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].provider.as_str(), "claude");
+        assert_eq!(daily[0].data.len(), 2);
+        assert_eq!(daily[0].totals.total_cost, Cost::from_usd(3.0));
+        assert_eq!(daily[1].provider.as_str(), "codex");
+        assert_eq!(daily[1].totals.total_cost, Cost::from_usd(0.75));
+
+        let totals = Totals::from_providers(&daily);
+        assert_eq!(totals.total_cost, Cost::from_usd(3.75));
+        assert_eq!(totals.tokens.input_tokens, 400);
+    }
+
+    #[tokio::test]
+    async fn test_monthly_by_provider_drops_filtered_providers() {
+        let daily =
+            aggregate_daily_by_provider(&aggregator().await, fixture(), CostMode::Display).await;
+
+        let monthly =
+            aggregate_monthly_by_provider(&daily, &MonthFilter::new().with_since(2025, 2));
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly[0].provider.as_str(), "codex");
+        assert_eq!(monthly[0].data[0].month, "2025-02");
+        assert_eq!(monthly[0].totals.total_cost, Cost::from_usd(0.25));
+    }
+
+    #[tokio::test]
+    async fn test_sessions_by_provider() {
+        let sessions =
+            aggregate_sessions_by_provider(&aggregator().await, fixture(), CostMode::Display).await;
+
+        assert_eq!(sessions[0].data.len(), 1);
+        assert_eq!(sessions[1].data.len(), 2);
+        assert_eq!(
+            Totals::from_providers(&sessions).total_cost,
+            Cost::from_usd(3.75)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failing_provider_is_skipped() {
+        let mut entries = fixture();
+        entries[1] = stream(
+            "codex",
+            vec![
+                Ok(entry(Provider::Registered("codex"), "x1", 10, 1, 0.5)),
+                Err(CcstatError::InvalidArgument("broken".to_string())),
+            ],
+        );
+        let daily =
+            aggregate_daily_by_provider(&aggregator().await, entries, CostMode::Display).await;
+
+        assert_eq!(daily.len(), 1);
+        assert_eq!(daily[0].provider.as_str(), "claude");
+    }
+}
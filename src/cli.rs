@@ -7,6 +7,9 @@
 //! - `ccstat daily` is equivalent to `ccstat claude daily`
 //! - `ccstat codex daily` explicitly selects the Codex provider
 //!
+//! Providers outside ccstat are reached with `ccstat ext:<name> <report>`,
+//! which runs a `ccstat-provider-<name>` plugin (see [`crate::external`]).
+//!
 //! # Example
 //!
 //! ```bash
@@ -21,13 +24,19 @@
 //! ```
 
 use crate::error::{CcstatError, Result};
-use crate::types::CostMode;
+use crate::providers::registry;
+use crate::registry::{ProviderInfo, ReportType};
+use crate::types::{CostMode, ProviderId};
 use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
 
 /// Analyze AI coding tool usage data
 #[derive(Parser, Debug, Clone)]
 #[command(name = "ccstat")]
 #[command(version, about, long_about = None)]
+#[command(
+    after_help = "External providers: `ccstat ext:<name> <daily|monthly|weekly|session>` runs `ccstat-provider-<name>` from PATH."
+)]
 pub struct Cli {
     /// Show informational output (default is quiet mode with only warnings and errors)
     #[arg(long, short = 'v', global = true)]
@@ -53,6 +62,50 @@ pub struct Cli {
     #[arg(long, short = 'p', global = true)]
     pub project: Option<String>,
 
+    /// Read provider data from this directory instead of the default location
+    /// (repeatable; overrides the provider's environment variables)
+    #[arg(long = "data-dir", value_name = "DIR", global = true)]
+    pub data_dirs: Vec<PathBuf>,
+
+    /// Never fetch pricing over the network; use cached or embedded pricing
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Hours fetched pricing is cached before it is fetched again
+    #[arg(
+        long,
+        value_name = "HOURS",
+        default_value = "24",
+        env = "CCSTAT_PRICING_TTL",
+        global = true
+    )]
+    pub pricing_ttl: u64,
+
+    /// User pricing file with model rates, aliases, and discounts
+    /// (default: ~/.config/ccstat/pricing.toml)
+    #[arg(long, value_name = "FILE", env = "CCSTAT_PRICING_FILE", global = true)]
+    pub pricing_file: Option<PathBuf>,
+
+    /// Fail on models without exact pricing instead of guessing a match or
+    /// costing them at $0.00
+    #[arg(long, global = true)]
+    pub strict_pricing: bool,
+
+    /// Currency to display costs in (e.g. "EUR", "JPY"); costs are computed
+    /// in USD and converted at an offline exchange rate
+    #[arg(long, value_name = "CODE", env = "CCSTAT_CURRENCY", global = true)]
+    pub currency: Option<String>,
+
+    /// Exchange rate snapshot file in TOML or JSON (default: the
+    /// `[exchange_rates]` table of the pricing file)
+    #[arg(
+        long,
+        value_name = "FILE",
+        env = "CCSTAT_EXCHANGE_RATES",
+        global = true
+    )]
+    pub exchange_rates: Option<PathBuf>,
+
     /// Timezone for date grouping (e.g. "America/New_York", "Asia/Tokyo", "UTC")
     /// If not specified, uses the system's local timezone
     #[arg(long, short = 'z', global = true)]
@@ -91,24 +144,48 @@ pub struct Cli {
 // Provider
 // ---------------------------------------------------------------------------
 
-/// Supported usage data providers
+/// Usage data provider selected on the command line
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Provider {
-    Claude,
-    Codex,
-    Opencode,
-    Amp,
-    Pi,
+    /// A provider from the [registry](crate::providers::registry), by name
+    Registered(&'static str),
+    /// Every provider combined
+    All,
+}
+
+impl Provider {
+    /// The provider report shortcuts (`ccstat daily`, ...) default to
+    pub const DEFAULT: Provider = Provider::Registered("claude");
+
+    /// Look up a provider by name or alias, including `all`
+    pub fn from_name(name: &str) -> Option<Provider> {
+        if name == "all" {
+            return Some(Provider::All);
+        }
+        registry()
+            .get(name)
+            .map(|info| Provider::Registered(info.name))
+    }
+
+    /// Registry entry of a single provider
+    pub fn info(self) -> Option<&'static ProviderInfo> {
+        match self {
+            Provider::Registered(name) => registry().get(name),
+            Provider::All => None,
+        }
+    }
+
+    /// Identifier recorded on entries loaded from this provider
+    pub fn id(self) -> ProviderId {
+        ProviderId::new(self.to_string())
+    }
 }
 
 impl std::fmt::Display for Provider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Provider::Claude => write!(f, "claude"),
-            Provider::Codex => write!(f, "codex"),
-            Provider::Opencode => write!(f, "opencode"),
-            Provider::Amp => write!(f, "amp"),
-            Provider::Pi => write!(f, "pi"),
+            Provider::Registered(name) => write!(f, "{name}"),
+            Provider::All => write!(f, "all"),
         }
     }
 }
@@ -156,9 +233,9 @@ pub struct BlocksArgs {
     #[arg(long)]
     pub token_limit: Option<String>,
 
-    /// Session duration in hours for billing blocks
-    #[arg(long, default_value = "5.0")]
-    pub session_duration: f64,
+    /// Session duration in hours for billing blocks (defaults to the provider's block length)
+    #[arg(long)]
+    pub session_duration: Option<f64>,
 
     /// Maximum cost limit in USD for progress calculations (defaults to historical maximum)
     #[arg(long)]
@@ -185,6 +262,40 @@ pub struct StatuslineArgs {
     pub show_git: bool,
 }
 
+/// Arguments for the MCP server command
+#[derive(Args, Debug, Clone, Default)]
+pub struct McpArgs {
+    /// Serve streamable HTTP on this address (e.g. 127.0.0.1:8080) instead of stdio
+    #[arg(long, value_name = "ADDR")]
+    pub http: Option<std::net::SocketAddr>,
+
+    /// Bearer token required on HTTP requests
+    #[arg(long, env = "CCSTAT_MCP_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Also accept HTTP requests for, and browser origins on, this host name
+    /// (repeatable; loopback and the bind address are always accepted)
+    #[arg(long = "allow-host", value_name = "HOST")]
+    pub allow_hosts: Vec<String>,
+}
+
+/// Pricing data commands
+#[derive(Subcommand, Debug, Clone)]
+pub enum PricingCommand {
+    /// Fetch the latest pricing and update the on-disk cache
+    Refresh,
+    /// Show the pricing used for a model and where it came from
+    Show {
+        /// Model name as it appears in usage data
+        model: String,
+    },
+    /// Show how a model name is matched to a pricing entry, step by step
+    Resolve {
+        /// Model name as it appears in usage data
+        model: String,
+    },
+}
+
 /// Arguments for the watch command (hidden alias)
 #[derive(Args, Debug, Clone)]
 pub struct WatchArgs {
@@ -212,6 +323,134 @@ pub enum Report {
     Blocks(BlocksArgs),
     /// Generate statusline output
     Statusline(StatuslineArgs),
+    /// Show the latest rate-limit window usage
+    Limits,
+}
+
+impl Report {
+    /// Report type used by the provider support matrix
+    pub fn report_type(&self) -> ReportType {
+        match self {
+            Report::Daily(_) => ReportType::Daily,
+            Report::Monthly => ReportType::Monthly,
+            Report::Weekly(_) => ReportType::Weekly,
+            Report::Session(_) => ReportType::Session,
+            Report::Blocks(_) => ReportType::Blocks,
+            Report::Statusline(_) => ReportType::Statusline,
+            Report::Limits => ReportType::Limits,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Provider subcommands (generated from the registry)
+// ---------------------------------------------------------------------------
+
+/// A registered provider's subcommand with its nested report
+///
+/// One subcommand is generated per registry entry, named after the provider
+/// and accepting its aliases. Reports the provider does not support are
+/// hidden from its help.
+#[derive(Debug, Clone)]
+pub struct ProviderCommand {
+    /// Registered provider name
+    pub provider: &'static str,
+    /// Requested report
+    pub report: Report,
+}
+
+impl ProviderCommand {
+    fn augment(cmd: clap::Command, for_update: bool) -> clap::Command {
+        registry().iter().fold(cmd, |cmd, info| {
+            let sub = clap::Command::new(info.name);
+            // Augmenting sets the `Report` doc comment as the about text,
+            // so the provider's own description goes on afterwards
+            let mut sub = if for_update {
+                Report::augment_subcommands_for_update(sub)
+            } else {
+                Report::augment_subcommands(sub)
+            }
+            .about(info.description)
+            .visible_aliases(info.aliases.iter().copied())
+            .subcommand_required(true)
+            .arg_required_else_help(true);
+            let after_help: Vec<String> = info
+                .note
+                .map(str::to_string)
+                .into_iter()
+                .chain(
+                    (!info.env_vars.is_empty())
+                        .then(|| format!("Data directory override: {}", info.env_vars.join(", "))),
+                )
+                .collect();
+            if !after_help.is_empty() {
+                sub = sub.after_help(after_help.join("\n\n"));
+            }
+            let hidden: Vec<_> = sub
+                .get_subcommands()
+                .map(|report| report.get_name().to_string())
+                .filter(|name| {
+                    !info
+                        .reports
+                        .iter()
+                        .any(|report| report.as_str() == name.as_str())
+                })
+                .collect();
+            for name in hidden {
+                sub = sub.mut_subcommand(name, |report| report.hide(true));
+            }
+            cmd.subcommand(sub)
+        })
+    }
+}
+
+impl clap::FromArgMatches for ProviderCommand {
+    fn from_arg_matches(matches: &clap::ArgMatches) -> std::result::Result<Self, clap::Error> {
+        Self::from_arg_matches_mut(&mut matches.clone())
+    }
+
+    fn from_arg_matches_mut(
+        matches: &mut clap::ArgMatches,
+    ) -> std::result::Result<Self, clap::Error> {
+        let Some((name, mut sub_matches)) = matches.remove_subcommand() else {
+            return Err(clap::Error::raw(
+                clap::error::ErrorKind::MissingSubcommand,
+                "A provider subcommand is required",
+            ));
+        };
+        let Some(info) = registry().get(&name) else {
+            return Err(clap::Error::raw(
+                clap::error::ErrorKind::InvalidSubcommand,
+                format!("Unknown provider '{name}'"),
+            ));
+        };
+        Ok(ProviderCommand {
+            provider: info.name,
+            report: Report::from_arg_matches_mut(&mut sub_matches)?,
+        })
+    }
+
+    fn update_from_arg_matches(
+        &mut self,
+        matches: &clap::ArgMatches,
+    ) -> std::result::Result<(), clap::Error> {
+        *self = Self::from_arg_matches(matches)?;
+        Ok(())
+    }
+}
+
+impl Subcommand for ProviderCommand {
+    fn augment_subcommands(cmd: clap::Command) -> clap::Command {
+        Self::augment(cmd, false)
+    }
+
+    fn augment_subcommands_for_update(cmd: clap::Command) -> clap::Command {
+        Self::augment(cmd, true)
+    }
+
+    fn has_subcommand(name: &str) -> bool {
+        registry().get(name).is_some()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -220,34 +459,17 @@ pub enum Report {
 
 /// Available commands
 ///
-/// Provider subcommands (`claude`, `codex`, etc.) accept a nested report.
+/// Provider subcommands (`claude`, `codex`, etc.) are generated from the
+/// provider registry and accept a nested report.
 /// Report names used directly (`daily`, `monthly`, etc.) default to the
 /// Claude provider.
 #[derive(Subcommand, Debug, Clone)]
 pub enum Command {
     // -- Provider subcommands ------------------------------------------------
-    /// Claude Code usage data
-    Claude {
-        #[command(subcommand)]
-        report: Report,
-    },
-    /// Codex usage data
-    Codex {
-        #[command(subcommand)]
-        report: Report,
-    },
-    /// OpenCode usage data
-    Opencode {
-        #[command(subcommand)]
-        report: Report,
-    },
-    /// Amp usage data
-    Amp {
-        #[command(subcommand)]
-        report: Report,
-    },
-    /// Pi Agent usage data
-    Pi {
+    #[command(flatten)]
+    Provider(ProviderCommand),
+    /// Usage data from every provider combined
+    All {
         #[command(subcommand)]
         report: Report,
     },
@@ -267,12 +489,22 @@ pub enum Command {
     Statusline(StatuslineArgs),
 
     // -- Special commands ----------------------------------------------------
-    /// Start MCP server
-    Mcp,
+    /// Start MCP server (stdio by default, or streamable HTTP with --http)
+    Mcp(McpArgs),
+
+    /// Manage model pricing data
+    Pricing {
+        #[command(subcommand)]
+        command: PricingCommand,
+    },
 
     /// Live monitor for active billing blocks (alias for blocks --watch --active)
     #[command(hide = true)]
     Watch(WatchArgs),
+
+    /// External provider plugin: `ext:<name> <report>`
+    #[command(external_subcommand)]
+    External(Vec<String>),
 }
 
 // ---------------------------------------------------------------------------
@@ -281,63 +513,118 @@ pub enum Command {
 
 /// Resolve a top-level command into a (Provider, Report) pair.
 ///
-/// Returns `None` for special commands (Mcp, Watch) that need separate handling.
+/// Returns `None` for special commands (Mcp, Pricing, Watch) that need separate handling.
 pub fn resolve_provider_report(cmd: &Command) -> Option<(Provider, Report)> {
     match cmd.clone() {
         // Provider subcommands
-        Command::Claude { report } => Some((Provider::Claude, report)),
-        Command::Codex { report } => Some((Provider::Codex, report)),
-        Command::Opencode { report } => Some((Provider::Opencode, report)),
-        Command::Amp { report } => Some((Provider::Amp, report)),
-        Command::Pi { report } => Some((Provider::Pi, report)),
+        Command::Provider(cmd) => Some((Provider::Registered(cmd.provider), cmd.report)),
+        Command::All { report } => Some((Provider::All, report)),
 
         // Report shortcuts → Claude
-        Command::Daily(args) => Some((Provider::Claude, Report::Daily(args))),
-        Command::Monthly => Some((Provider::Claude, Report::Monthly)),
-        Command::Weekly(args) => Some((Provider::Claude, Report::Weekly(args))),
-        Command::Session(args) => Some((Provider::Claude, Report::Session(args))),
-        Command::Blocks(args) => Some((Provider::Claude, Report::Blocks(args))),
-        Command::Statusline(args) => Some((Provider::Claude, Report::Statusline(args))),
+        Command::Daily(args) => Some((Provider::DEFAULT, Report::Daily(args))),
+        Command::Monthly => Some((Provider::DEFAULT, Report::Monthly)),
+        Command::Weekly(args) => Some((Provider::DEFAULT, Report::Weekly(args))),
+        Command::Session(args) => Some((Provider::DEFAULT, Report::Session(args))),
+        Command::Blocks(args) => Some((Provider::DEFAULT, Report::Blocks(args))),
+        Command::Statusline(args) => Some((Provider::DEFAULT, Report::Statusline(args))),
 
         // Special commands
-        Command::Mcp | Command::Watch(_) => None,
+        Command::Mcp(_) | Command::Pricing { .. } | Command::Watch(_) | Command::External(_) => {
+            None
+        }
     }
 }
 
-/// Validate that a provider supports the given report type.
+/// Resolve `ccstat ext:<name> <report>` into the plugin name and report
 ///
-/// Returns an error for unsupported combinations per the provider-report matrix.
-pub fn validate_provider_report(provider: Provider, report: &Report) -> Result<()> {
-    let supported = match (&provider, report) {
-        // All providers support daily, monthly, session
-        (_, Report::Daily(_) | Report::Monthly | Report::Session(_)) => true,
+/// clap passes external subcommands through unparsed, so the full command
+/// line (`argv`) is parsed again without the `ext:<name>` token. Global
+/// options are then honored on either side of it. Only the daily, monthly,
+/// weekly, and session reports are available from plugins.
+///
+/// Any other unknown subcommand is parsed again with external subcommands
+/// disabled, so a typo gets clap's usual error and suggestion. Errors are
+/// clap errors, to be reported with [`clap::Error::exit`].
+pub fn resolve_external_command<I>(
+    argv: I,
+    external: &[String],
+) -> std::result::Result<(String, Cli, Report), clap::Error>
+where
+    I: IntoIterator<Item = String>,
+{
+    use clap::CommandFactory;
+    use clap::error::ErrorKind;
+
+    let token = external.first().map(String::as_str).unwrap_or_default();
+    let Some(name) = token.strip_prefix(crate::external::COMMAND_PREFIX) else {
+        // The derived value parser would turn external subcommands back on
+        let error = match Cli::command()
+            .allow_external_subcommands(false)
+            .external_subcommand_value_parser(None::<clap::builder::ValueParser>)
+            .try_get_matches_from(argv)
+        {
+            Err(e) => e,
+            Ok(_) => clap::Error::raw(
+                ErrorKind::InvalidSubcommand,
+                format!("unrecognized subcommand '{token}'\n"),
+            ),
+        };
+        return Err(error);
+    };
+
+    let mut removed = false;
+    let argv = argv.into_iter().filter(|arg| {
+        if !removed && arg == token {
+            removed = true;
+            return false;
+        }
+        true
+    });
+    let cli = Cli::try_parse_from(argv)?;
 
-        // Weekly: only Claude and OpenCode
-        (Provider::Claude | Provider::Opencode, Report::Weekly(_)) => true,
+    let report = match &cli.command {
+        Some(
+            cmd @ (Command::Daily(_) | Command::Monthly | Command::Weekly(_) | Command::Session(_)),
+        ) => resolve_provider_report(cmd).map(|(_, report)| report),
+        _ => None,
+    };
+    let Some(report) = report else {
+        return Err(Cli::command().error(
+            ErrorKind::MissingSubcommand,
+            format!("'{token}' needs one of the reports: daily, monthly, weekly, session"),
+        ));
+    };
 
-        // Blocks: only Claude
-        (Provider::Claude, Report::Blocks(_)) => true,
+    Ok((name.to_string(), cli, report))
+}
 
-        // Statusline: only Claude
-        (Provider::Claude, Report::Statusline(_)) => true,
+/// Reports available from the combined `all` provider
+const ALL_PROVIDERS_REPORTS: &[ReportType] = &[
+    ReportType::Daily,
+    ReportType::Monthly,
+    ReportType::Weekly,
+    ReportType::Session,
+];
 
-        // Everything else is unsupported
-        _ => false,
+/// Validate that a provider supports the given report type.
+///
+/// Returns an error for unsupported combinations per the reports each
+/// provider declares in the registry.
+pub fn validate_provider_report(provider: Provider, report: &Report) -> Result<()> {
+    let report_type = report.report_type();
+    let supported = match provider {
+        // Blocks are not combined across providers
+        Provider::All => ALL_PROVIDERS_REPORTS.contains(&report_type),
+        Provider::Registered(_) => provider
+            .info()
+            .is_some_and(|info| info.supports(report_type)),
     };
 
     if supported {
         Ok(())
     } else {
-        let report_name = match report {
-            Report::Daily(_) => "daily",
-            Report::Monthly => "monthly",
-            Report::Weekly(_) => "weekly",
-            Report::Session(_) => "session",
-            Report::Blocks(_) => "blocks",
-            Report::Statusline(_) => "statusline",
-        };
         Err(CcstatError::Config(format!(
-            "The '{report_name}' report is not supported for the '{provider}' provider"
+            "The '{report_type}' report is not supported for the '{provider}' provider"
         )))
     }
 }
@@ -348,10 +635,10 @@ pub fn is_statusline_command(cmd: &Option<Command>) -> bool {
         cmd,
         Some(
             Command::Statusline(_)
-                | Command::Claude {
+                | Command::Provider(ProviderCommand {
+                    provider: "claude",
                     report: Report::Statusline(_),
-                    ..
-                }
+                })
         )
     )
 }
@@ -451,6 +738,7 @@ pub fn parse_weekday(s: &str) -> Result<chrono::Weekday> {
 mod tests {
     use super::*;
     use chrono::Datelike;
+    use clap::CommandFactory;
 
     #[test]
     fn test_cli_parsing() {
@@ -465,6 +753,94 @@ mod tests {
             Some(Command::Daily(args)) => assert!(args.instances),
             _ => panic!("Expected Daily command"),
         }
+
+        // --data-dir is global and repeatable
+        let cli = Cli::parse_from([
+            "ccstat",
+            "codex",
+            "daily",
+            "--data-dir",
+            "/backup/one",
+            "--data-dir",
+            "/backup/two",
+        ]);
+        assert_eq!(
+            cli.data_dirs,
+            [PathBuf::from("/backup/one"), PathBuf::from("/backup/two")]
+        );
+        let cli = Cli::parse_from(["ccstat", "--data-dir", "/backup/one", "daily"]);
+        assert_eq!(cli.data_dirs, [PathBuf::from("/backup/one")]);
+        let cli = Cli::parse_from(["ccstat", "daily"]);
+        assert!(cli.data_dirs.is_empty());
+        assert!(!cli.offline);
+    }
+
+    #[test]
+    fn test_pricing_args() {
+        let cli = Cli::parse_from(["ccstat", "pricing", "refresh"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Pricing {
+                command: PricingCommand::Refresh
+            })
+        ));
+        assert!(resolve_provider_report(cli.command.as_ref().unwrap()).is_none());
+
+        let cli = Cli::parse_from(["ccstat", "daily", "--offline", "--pricing-ttl", "6"]);
+        assert!(cli.offline);
+        assert_eq!(cli.pricing_ttl, 6);
+
+        let cli = Cli::parse_from([
+            "ccstat",
+            "pricing",
+            "show",
+            "claude-sonnet-4-5",
+            "--pricing-file",
+            "/etc/ccstat/pricing.toml",
+        ]);
+        match &cli.command {
+            Some(Command::Pricing {
+                command: PricingCommand::Show { model },
+            }) => assert_eq!(model, "claude-sonnet-4-5"),
+            _ => panic!("Expected pricing show command"),
+        }
+        assert_eq!(
+            cli.pricing_file,
+            Some(PathBuf::from("/etc/ccstat/pricing.toml"))
+        );
+
+        let cli = Cli::parse_from([
+            "ccstat",
+            "pricing",
+            "resolve",
+            "us.anthropic.claude-3-opus-20240229-v1:0",
+            "--strict-pricing",
+        ]);
+        match &cli.command {
+            Some(Command::Pricing {
+                command: PricingCommand::Resolve { model },
+            }) => assert_eq!(model, "us.anthropic.claude-3-opus-20240229-v1:0"),
+            _ => panic!("Expected pricing resolve command"),
+        }
+        assert!(cli.strict_pricing);
+        assert!(!Cli::parse_from(["ccstat", "daily"]).strict_pricing);
+
+        assert!(Cli::try_parse_from(["ccstat", "pricing"]).is_err());
+        assert!(Cli::try_parse_from(["ccstat", "pricing", "show"]).is_err());
+    }
+
+    #[test]
+    fn test_currency_args() {
+        let cli = Cli::parse_from([
+            "ccstat",
+            "monthly",
+            "--currency",
+            "eur",
+            "--exchange-rates",
+            "rates.json",
+        ]);
+        assert_eq!(cli.currency.as_deref(), Some("eur"));
+        assert_eq!(cli.exchange_rates, Some(PathBuf::from("rates.json")));
     }
 
     #[test]
@@ -472,20 +848,110 @@ mod tests {
         // ccstat claude daily --instances
         let cli = Cli::parse_from(["ccstat", "claude", "daily", "--instances"]);
         match &cli.command {
-            Some(Command::Claude {
+            Some(Command::Provider(ProviderCommand {
+                provider: "claude",
                 report: Report::Daily(args),
-            }) => assert!(args.instances),
+            })) => assert!(args.instances),
             _ => panic!("Expected Claude Daily command"),
         }
 
         // ccstat codex monthly
         let cli = Cli::parse_from(["ccstat", "codex", "monthly"]);
         match &cli.command {
-            Some(Command::Codex {
+            Some(Command::Provider(ProviderCommand {
+                provider: "codex",
                 report: Report::Monthly,
-            }) => {}
+            })) => {}
             _ => panic!("Expected Codex Monthly command"),
         }
+
+        // ccstat codex limits
+        let cli = Cli::parse_from(["ccstat", "codex", "limits"]);
+        match &cli.command {
+            Some(Command::Provider(ProviderCommand {
+                provider: "codex",
+                report: Report::Limits,
+            })) => {}
+            _ => panic!("Expected Codex Limits command"),
+        }
+
+        // ccstat gemini session
+        let cli = Cli::parse_from(["ccstat", "gemini", "session"]);
+        let (provider, report) = resolve_provider_report(cli.command.as_ref().unwrap()).unwrap();
+        assert_eq!(provider, Provider::Registered("gemini"));
+        assert!(matches!(report, Report::Session(_)));
+
+        // ccstat crush monthly
+        let cli = Cli::parse_from(["ccstat", "crush", "monthly"]);
+        let (provider, _) = resolve_provider_report(cli.command.as_ref().unwrap()).unwrap();
+        assert_eq!(provider, Provider::Registered("crush"));
+        let help = Cli::command()
+            .find_subcommand_mut("crush")
+            .unwrap()
+            .render_long_help()
+            .to_string();
+        assert!(help.contains("dated at its last update"));
+        assert!(help.contains("Data directory override: CRUSH_DATA_DIR"));
+
+        // Aliases resolve to the registered name
+        let cli = Cli::parse_from(["ccstat", "gemini-cli", "daily"]);
+        let (provider, _) = resolve_provider_report(cli.command.as_ref().unwrap()).unwrap();
+        assert_eq!(provider, Provider::Registered("gemini"));
+        assert_eq!(
+            Provider::from_name("roo-code"),
+            Some(Provider::Registered("roo"))
+        );
+        assert_eq!(Provider::from_name("all"), Some(Provider::All));
+        assert!(Provider::from_name("cursor").is_none());
+
+        // ccstat roo daily
+        let cli = Cli::parse_from(["ccstat", "roo", "daily"]);
+        let (provider, _) = resolve_provider_report(cli.command.as_ref().unwrap()).unwrap();
+        assert_eq!(provider, Provider::Registered("roo"));
+
+        // ccstat all weekly
+        let cli = Cli::parse_from(["ccstat", "all", "weekly"]);
+        let (provider, report) = resolve_provider_report(cli.command.as_ref().unwrap()).unwrap();
+        assert_eq!(provider, Provider::All);
+        assert!(matches!(report, Report::Weekly(_)));
+    }
+
+    #[test]
+    fn test_resolve_external_command() {
+        let argv = |args: &[&str]| -> Vec<String> { args.iter().map(|a| a.to_string()).collect() };
+
+        // Global options are honored before and after ext:<name>
+        let args = argv(&[
+            "ccstat", "--json", "ext:acme", "monthly", "--since", "2025-01",
+        ]);
+        let cli = Cli::parse_from(args.clone());
+        let Some(Command::External(external)) = &cli.command else {
+            panic!("Expected external command");
+        };
+        assert_eq!(external[0], "ext:acme");
+        let (name, cli, report) = resolve_external_command(args, external).unwrap();
+        assert_eq!(name, "acme");
+        assert!(cli.json);
+        assert_eq!(cli.since.as_deref(), Some("2025-01"));
+        assert!(matches!(report, Report::Monthly));
+
+        // Unknown subcommands are not mistaken for plugins, and typos get a
+        // suggestion and clap's usage exit code
+        let args = argv(&["ccstat", "--json", "dialy"]);
+        let Some(Command::External(external)) = &Cli::parse_from(args.clone()).command else {
+            panic!("Expected external command");
+        };
+        let err = resolve_external_command(args, external).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::InvalidSubcommand);
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("'daily'"));
+
+        // Only the aggregate reports are available
+        let args = argv(&["ccstat", "ext:acme", "blocks"]);
+        assert!(resolve_external_command(args, &argv(&["ext:acme", "blocks"])).is_err());
+        let args = argv(&["ccstat", "ext:acme"]);
+        let err = resolve_external_command(args, &argv(&["ext:acme"])).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
     }
 
     #[test]
@@ -496,22 +962,23 @@ mod tests {
             detailed: false,
         });
         let (provider, report) = resolve_provider_report(&cmd).unwrap();
-        assert_eq!(provider, Provider::Claude);
+        assert_eq!(provider, Provider::Registered("claude"));
         assert!(matches!(report, Report::Daily(_)));
 
         // Explicit: codex daily → (Codex, Daily)
-        let cmd = Command::Codex {
+        let cmd = Command::Provider(ProviderCommand {
+            provider: "codex",
             report: Report::Daily(DailyArgs {
                 instances: false,
                 detailed: false,
             }),
-        };
+        });
         let (provider, report) = resolve_provider_report(&cmd).unwrap();
-        assert_eq!(provider, Provider::Codex);
+        assert_eq!(provider, Provider::Registered("codex"));
         assert!(matches!(report, Report::Daily(_)));
 
         // Special commands return None
-        assert!(resolve_provider_report(&Command::Mcp).is_none());
+        assert!(resolve_provider_report(&Command::Mcp(McpArgs::default())).is_none());
     }
 
     #[test]
@@ -519,7 +986,7 @@ mod tests {
         // Claude supports everything
         assert!(
             validate_provider_report(
-                Provider::Claude,
+                Provider::Registered("claude"),
                 &Report::Daily(DailyArgs {
                     instances: false,
                     detailed: false
@@ -527,10 +994,10 @@ mod tests {
             )
             .is_ok()
         );
-        assert!(validate_provider_report(Provider::Claude, &Report::Monthly).is_ok());
+        assert!(validate_provider_report(Provider::Registered("claude"), &Report::Monthly).is_ok());
         assert!(
             validate_provider_report(
-                Provider::Claude,
+                Provider::Registered("claude"),
                 &Report::Weekly(WeeklyArgs {
                     start_of_week: "sunday".into()
                 })
@@ -539,22 +1006,22 @@ mod tests {
         );
         assert!(
             validate_provider_report(
-                Provider::Claude,
+                Provider::Registered("claude"),
                 &Report::Blocks(BlocksArgs {
                     active: false,
                     recent: false,
                     token_limit: None,
-                    session_duration: 5.0,
+                    session_duration: None,
                     max_cost: None
                 })
             )
             .is_ok()
         );
 
-        // Codex does NOT support weekly, blocks, statusline
+        // Codex supports blocks but NOT weekly or statusline
         assert!(
             validate_provider_report(
-                Provider::Codex,
+                Provider::Registered("codex"),
                 &Report::Weekly(WeeklyArgs {
                     start_of_week: "sunday".into()
                 })
@@ -563,22 +1030,39 @@ mod tests {
         );
         assert!(
             validate_provider_report(
-                Provider::Codex,
+                Provider::Registered("codex"),
                 &Report::Blocks(BlocksArgs {
                     active: false,
                     recent: false,
                     token_limit: None,
-                    session_duration: 5.0,
+                    session_duration: None,
                     max_cost: None
                 })
             )
+            .is_ok()
+        );
+        assert!(
+            validate_provider_report(
+                Provider::Registered("codex"),
+                &Report::Statusline(StatuslineArgs {
+                    monthly_fee: 200.0,
+                    no_color: false,
+                    show_date: false,
+                    show_git: false,
+                })
+            )
             .is_err()
         );
 
+        // Only Codex reports rate limits
+        assert!(validate_provider_report(Provider::Registered("codex"), &Report::Limits).is_ok());
+        assert!(validate_provider_report(Provider::Registered("claude"), &Report::Limits).is_err());
+        assert!(validate_provider_report(Provider::All, &Report::Limits).is_err());
+
         // OpenCode supports weekly
         assert!(
             validate_provider_report(
-                Provider::Opencode,
+                Provider::Registered("opencode"),
                 &Report::Weekly(WeeklyArgs {
                     start_of_week: "sunday".into()
                 })
@@ -586,10 +1070,34 @@ mod tests {
             .is_ok()
         );
 
+        // The combined view supports weekly but not blocks
+        assert!(
+            validate_provider_report(
+                Provider::All,
+                &Report::Weekly(WeeklyArgs {
+                    start_of_week: "sunday".into()
+                })
+            )
+            .is_ok()
+        );
+        assert!(
+            validate_provider_report(
+                Provider::All,
+                &Report::Blocks(BlocksArgs {
+                    active: false,
+                    recent: false,
+                    token_limit: None,
+                    session_duration: None,
+                    max_cost: None
+                })
+            )
+            .is_err()
+        );
+
         // Amp does not support weekly
         assert!(
             validate_provider_report(
-                Provider::Amp,
+                Provider::Registered("amp"),
                 &Report::Weekly(WeeklyArgs {
                     start_of_week: "sunday".into()
                 })
@@ -611,14 +1119,17 @@ mod tests {
         ))));
 
         // Via provider
-        assert!(is_statusline_command(&Some(Command::Claude {
-            report: Report::Statusline(StatuslineArgs {
-                monthly_fee: 200.0,
-                no_color: false,
-                show_date: false,
-                show_git: false,
-            })
-        })));
+        assert!(is_statusline_command(&Some(Command::Provider(
+            ProviderCommand {
+                provider: "claude",
+                report: Report::Statusline(StatuslineArgs {
+                    monthly_fee: 200.0,
+                    no_color: false,
+                    show_date: false,
+                    show_git: false,
+                })
+            }
+        ))));
 
         // Not statusline
         assert!(!is_statusline_command(&Some(Command::Daily(DailyArgs {
@@ -628,6 +1139,33 @@ mod tests {
         assert!(!is_statusline_command(&None));
     }
 
+    #[test]
+    fn test_mcp_args() {
+        let cli = Cli::parse_from(["ccstat", "mcp"]);
+        match &cli.command {
+            Some(Command::Mcp(args)) => assert!(args.http.is_none()),
+            _ => panic!("Expected Mcp command"),
+        }
+
+        let cli = Cli::parse_from([
+            "ccstat",
+            "mcp",
+            "--http",
+            "0.0.0.0:8080",
+            "--token",
+            "s3cret",
+        ]);
+        match &cli.command {
+            Some(Command::Mcp(args)) => {
+                assert_eq!(args.http, Some("0.0.0.0:8080".parse().unwrap()));
+                assert_eq!(args.token.as_deref(), Some("s3cret"));
+            }
+            _ => panic!("Expected Mcp command"),
+        }
+
+        assert!(Cli::try_parse_from(["ccstat", "mcp", "--http", "not-an-address"]).is_err());
+    }
+
     #[test]
     fn test_cost_mode_parsing() {
         let cli = Cli::parse_from(["ccstat", "--mode", "calculate"]);
@@ -0,0 +1,487 @@
+//! External provider plugins
+//!
+//! `ccstat ext:<name> <report>` runs an executable named
+//! `ccstat-provider-<name>` found on `PATH` and feeds the usage entries it
+//! prints through the same filter and aggregation pipeline as the built-in
+//! providers. This lets in-house tools report usage without changes to
+//! ccstat itself.
+//!
+//! # Protocol (version 1)
+//!
+//! ccstat starts the plugin as `ccstat-provider-<name> usage` with these
+//! environment variables:
+//!
+//! - `CCSTAT_PROTOCOL_VERSION`: the protocol version ccstat speaks (`1`)
+//! - `CCSTAT_SINCE` / `CCSTAT_UNTIL`: optional `YYYY-MM-DD` bounds of the
+//!   requested range. Plugins may use them to skip reading old data; ccstat
+//!   filters the entries either way.
+//!
+//! The plugin writes newline-delimited JSON to stdout. The first line is the
+//! handshake naming the protocol version the plugin implements:
+//!
+//! ```json
+//! {"ccstat_protocol": 1}
+//! ```
+//!
+//! Every following line is one usage entry, using the field names of
+//! ccstat's own `UsageEntry` JSON:
+//!
+//! ```json
+//! {"session_id": "s-1", "timestamp": "2025-01-01T10:00:00Z", "model": "gpt-5",
+//!  "input_tokens": 1200, "output_tokens": 300, "cache_creation_tokens": 0,
+//!  "cache_read_tokens": 800, "total_cost": 0.0125, "project": "web-app"}
+//! ```
+//!
+//! `session_id`, `timestamp` (RFC 3339) and `model` are required; token
+//! counts default to zero, and `total_cost` (USD), `project` and
+//! `instance_id` are optional. Entries without `total_cost` are priced from
+//! the model name like any other provider's. The provider recorded on each
+//! entry is always the plugin name; a `provider` field, which serialized
+//! `UsageEntry`s carry, is accepted and ignored. Blank lines are ignored; anything else
+//! that does not parse is an error, as is a non-zero exit status. The
+//! plugin's stderr is passed through to the terminal.
+//!
+//! Besides the token counts above, entries may carry `reasoning_tokens`
+//! (not included in `output_tokens`), `cache_creation_5m_tokens` and
+//! `cache_creation_1h_tokens` (the parts of `cache_creation_tokens` written
+//! with a 5-minute and a 1-hour TTL), and `web_search_requests` and
+//! `web_fetch_requests` (server-side tool requests). They default to zero
+//! as well.
+
+use crate::error::{CcstatError, Result};
+use crate::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::Stream;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tracing::debug;
+
+/// Plugin protocol version implemented by this ccstat
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Command prefix selecting an external provider, as in `ccstat ext:<name>`
+pub const COMMAND_PREFIX: &str = "ext:";
+
+/// Prefix of plugin executable names
+pub const EXECUTABLE_PREFIX: &str = "ccstat-provider-";
+
+/// Usage data supplied by an external plugin executable
+pub struct ExternalProvider {
+    name: String,
+    program: PathBuf,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+}
+
+impl ExternalProvider {
+    /// Locate the plugin for `name` on `PATH`
+    pub fn new(name: &str) -> Result<Self> {
+        validate_name(name)?;
+        let executable = format!("{EXECUTABLE_PREFIX}{name}");
+        let program = find_executable(&executable).ok_or_else(|| {
+            CcstatError::Config(format!(
+                "External provider '{name}' not found: no '{executable}' executable on PATH"
+            ))
+        })?;
+        debug!("Using external provider {}", program.display());
+        Ok(Self::with_program(name, program))
+    }
+
+    /// Use a specific plugin executable
+    pub fn with_program(name: &str, program: PathBuf) -> Self {
+        Self {
+            name: name.to_string(),
+            program,
+            since: None,
+            until: None,
+        }
+    }
+
+    /// Pass the requested date range on to the plugin as a hint
+    pub fn with_date_range(mut self, since: Option<NaiveDate>, until: Option<NaiveDate>) -> Self {
+        self.since = since;
+        self.until = until;
+        self
+    }
+
+    /// Name the provider was selected by
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Run the plugin and stream the entries it reports
+    pub fn load_entries(&self) -> Pin<Box<dyn Stream<Item = Result<UsageEntry>> + Send + '_>> {
+        Box::pin(async_stream::try_stream! {
+            let mut command = tokio::process::Command::new(&self.program);
+            command
+                .arg("usage")
+                .env("CCSTAT_PROTOCOL_VERSION", PROTOCOL_VERSION.to_string())
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::inherit())
+                .kill_on_drop(true);
+            if let Some(since) = self.since {
+                command.env("CCSTAT_SINCE", since.to_string());
+            }
+            if let Some(until) = self.until {
+                command.env("CCSTAT_UNTIL", until.to_string());
+            }
+
+            let mut child = command.spawn().map_err(|e| {
+                CcstatError::Io(std::io::Error::new(
+                    e.kind(),
+                    format!("{}: {}", self.program.display(), e),
+                ))
+            })?;
+            let stdout = child.stdout.take().expect("stdout is piped");
+            let mut lines = BufReader::new(stdout).lines();
+
+            let mut line_number = 0;
+            let mut handshake_seen = false;
+            while let Some(line) = lines.next_line().await? {
+                line_number += 1;
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                if !handshake_seen {
+                    check_handshake(&line).map_err(|e| self.protocol_error(line_number, e))?;
+                    handshake_seen = true;
+                    continue;
+                }
+
+                yield parse_entry(&line, &self.name)
+                    .map_err(|e| self.protocol_error(line_number, e.to_string()))?;
+            }
+
+            let status = child.wait().await?;
+            if !status.success() {
+                Err(CcstatError::Config(format!(
+                    "External provider '{}' failed: {}",
+                    self.name, status
+                )))?;
+            }
+            if !handshake_seen {
+                Err(self.protocol_error(line_number, "no handshake line".to_string()))?;
+            }
+        })
+    }
+
+    fn protocol_error(&self, line: usize, error: String) -> CcstatError {
+        CcstatError::Parse {
+            file: self.program.clone(),
+            error: format!("line {line}: {error}"),
+        }
+    }
+}
+
+/// Plugin names become part of an executable name, so keep them plain
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(CcstatError::InvalidArgument(format!(
+            "Invalid external provider name '{name}': use letters, digits, '-' and '_'"
+        )))
+    }
+}
+
+/// Search `PATH` for an executable file
+fn find_executable(executable: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path).find_map(|dir| {
+        let candidate = dir.join(executable);
+        if is_executable(&candidate) {
+            return Some(candidate);
+        }
+        if cfg!(windows) {
+            let candidate = candidate.with_extension("exe");
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+        None
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+// ---------------------------------------------------------------------------
+// Wire format
+// ---------------------------------------------------------------------------
+
+#[derive(Deserialize)]
+struct Handshake {
+    ccstat_protocol: u32,
+}
+
+fn check_handshake(line: &str) -> std::result::Result<(), String> {
+    let handshake: Handshake = serde_json::from_str(line)
+        .map_err(|e| format!("expected handshake {{\"ccstat_protocol\": N}}: {e}"))?;
+    if handshake.ccstat_protocol == PROTOCOL_VERSION {
+        Ok(())
+    } else {
+        Err(format!(
+            "plugin speaks protocol version {}, but ccstat supports version {}",
+            handshake.ccstat_protocol, PROTOCOL_VERSION
+        ))
+    }
+}
+
+/// Parse one usage entry line written by the plugin `provider`
+fn parse_entry(line: &str, provider: &str) -> serde_json::Result<UsageEntry> {
+    serde_json::from_str::<ExternalEntry>(line).map(|entry| entry.into_usage_entry(provider))
+}
+
+/// One usage entry as written by a plugin
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExternalEntry {
+    session_id: String,
+    timestamp: DateTime<Utc>,
+    model: String,
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    #[serde(default)]
+    cache_creation_tokens: u64,
+    #[serde(default)]
+    cache_read_tokens: u64,
+    #[serde(default)]
+    reasoning_tokens: u64,
+    #[serde(default)]
+    cache_creation_5m_tokens: u64,
+    #[serde(default)]
+    cache_creation_1h_tokens: u64,
+    #[serde(default)]
+    web_search_requests: u64,
+    #[serde(default)]
+    web_fetch_requests: u64,
+    #[serde(default)]
+    total_cost: Option<f64>,
+    #[serde(default)]
+    project: Option<String>,
+    #[serde(default)]
+    instance_id: Option<String>,
+    /// Provider named by the plugin; entries always get the plugin name
+    #[serde(default, rename = "provider")]
+    _provider: Option<serde::de::IgnoredAny>,
+}
+
+impl ExternalEntry {
+    fn into_usage_entry(self, provider: &str) -> UsageEntry {
+        UsageEntry {
+            session_id: SessionId::new(self.session_id),
+            timestamp: ISOTimestamp::new(self.timestamp),
+            model: ModelName::new(self.model),
+            tokens: TokenCounts::new(
+                self.input_tokens,
+                self.output_tokens,
+                self.cache_creation_tokens,
+                self.cache_read_tokens,
+            )
+            .with_reasoning_tokens(self.reasoning_tokens)
+            .with_cache_creation_ttl(self.cache_creation_5m_tokens, self.cache_creation_1h_tokens)
+            .with_server_tool_use(self.web_search_requests, self.web_fetch_requests),
+            total_cost: self.total_cost,
+            project: self.project,
+            instance_id: self.instance_id,
+            provider: ProviderId::new(provider),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    /// Serializes tests that write and run scripts, so no script is executed
+    /// while another test's fork still holds it open for writing (ETXTBSY)
+    static SCRIPT_MUTEX: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    /// Write an executable shell script plugin
+    fn plugin(dir: &TempDir, script: &str) -> ExternalProvider {
+        let program = dir.path().join("ccstat-provider-test");
+        std::fs::write(&program, format!("#!/bin/sh\n{script}")).unwrap();
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+        ExternalProvider::with_program("test", program)
+    }
+
+    async fn collect(provider: &ExternalProvider) -> Vec<Result<UsageEntry>> {
+        provider.load_entries().collect().await
+    }
+
+    #[tokio::test]
+    async fn test_reads_entries() {
+        let _lock = SCRIPT_MUTEX.lock().await;
+        let dir = TempDir::new().unwrap();
+        let provider = plugin(
+            &dir,
+            r#"echo '{"ccstat_protocol": 1}'
+echo ''
+echo '{"session_id":"s-1","timestamp":"2025-01-01T10:00:00Z","model":"gpt-5","input_tokens":1200,"output_tokens":300,"cache_read_tokens":800,"total_cost":0.0125,"project":"web-app"}'
+echo '{"session_id":"s-2","timestamp":"2025-01-02T10:00:00+02:00","model":"in-house-model","input_tokens":10}'
+echo '{"session_id":"s-3","timestamp":"2025-01-03T10:00:00Z","model":"claude-sonnet-4","cache_creation_tokens":500,"cache_creation_5m_tokens":200,"cache_creation_1h_tokens":300,"reasoning_tokens":40,"web_search_requests":2,"web_fetch_requests":1}'
+"#,
+        );
+
+        let entries: Vec<_> = collect(&provider)
+            .await
+            .into_iter()
+            .map(|e| e.unwrap())
+            .collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].session_id.as_str(), "s-1");
+        assert_eq!(entries[0].tokens.cache_read_tokens, 800);
+        assert_eq!(entries[0].total_cost, Some(0.0125));
+        assert_eq!(entries[0].project.as_deref(), Some("web-app"));
+        assert_eq!(entries[0].provider.as_str(), "test");
+        assert_eq!(entries[1].tokens.output_tokens, 0);
+        assert_eq!(entries[1].total_cost, None);
+        assert_eq!(
+            entries[1].timestamp.inner().to_rfc3339(),
+            "2025-01-02T08:00:00+00:00"
+        );
+        let tokens = &entries[2].tokens;
+        assert_eq!(tokens.cache_creation_tokens, 500);
+        assert_eq!(tokens.cache_creation_5m_tokens, 200);
+        assert_eq!(tokens.cache_creation_1h_tokens, 300);
+        assert_eq!(tokens.reasoning_tokens, 40);
+        assert_eq!(tokens.web_search_requests, 2);
+        assert_eq!(tokens.web_fetch_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_passes_protocol_environment() {
+        let _lock = SCRIPT_MUTEX.lock().await;
+        let dir = TempDir::new().unwrap();
+        // Echo the environment back as the session id
+        let provider = plugin(
+            &dir,
+            r#"echo '{"ccstat_protocol": 1}'
+echo "{\"session_id\":\"$1 v$CCSTAT_PROTOCOL_VERSION $CCSTAT_SINCE..$CCSTAT_UNTIL\",\"timestamp\":\"2025-01-01T10:00:00Z\",\"model\":\"m\"}"
+"#,
+        )
+        .with_date_range(NaiveDate::from_ymd_opt(2025, 1, 1), None);
+
+        let entries = collect(&provider).await;
+        assert_eq!(
+            entries[0].as_ref().unwrap().session_id.as_str(),
+            "usage v1 2025-01-01.."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_other_protocol_versions() {
+        let _lock = SCRIPT_MUTEX.lock().await;
+        let dir = TempDir::new().unwrap();
+        let provider = plugin(&dir, r#"echo '{"ccstat_protocol": 2}'"#);
+
+        let entries = collect(&provider).await;
+        let error = entries[0].as_ref().unwrap_err().to_string();
+        assert!(error.contains("protocol version 2"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_missing_handshake_and_bad_lines() {
+        let _lock = SCRIPT_MUTEX.lock().await;
+        let dir = TempDir::new().unwrap();
+        let provider = plugin(&dir, "true");
+        let entries = collect(&provider).await;
+        assert!(
+            entries[0]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("no handshake")
+        );
+
+        let provider = plugin(
+            &dir,
+            r#"echo '{"ccstat_protocol": 1}'
+echo '{"session_id":"s-1","model":"m"}'"#,
+        );
+        let entries = collect(&provider).await;
+        assert!(
+            entries[0]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("line 2")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_plugin() {
+        let _lock = SCRIPT_MUTEX.lock().await;
+        let dir = TempDir::new().unwrap();
+        let provider = plugin(&dir, "echo '{\"ccstat_protocol\": 1}'\nexit 3");
+
+        let entries = collect(&provider).await;
+        assert_eq!(entries.len(), 1);
+        assert!(
+            entries[0]
+                .as_ref()
+                .unwrap_err()
+                .to_string()
+                .contains("failed")
+        );
+    }
+
+    #[test]
+    fn test_parses_serialized_usage_entries() {
+        let entry = UsageEntry {
+            session_id: SessionId::new("s-1"),
+            timestamp: ISOTimestamp::new("2025-01-01T10:00:00Z".parse().unwrap()),
+            model: ModelName::new("claude-sonnet-4"),
+            tokens: TokenCounts::new(1200, 300, 500, 800)
+                .with_reasoning_tokens(40)
+                .with_cache_creation_ttl(200, 300)
+                .with_server_tool_use(2, 1),
+            total_cost: Some(0.0125),
+            project: Some("web-app".to_string()),
+            instance_id: Some("i-1".to_string()),
+            provider: ProviderId::new("claude"),
+        };
+        let line = serde_json::to_string(&entry).unwrap();
+
+        let parsed = parse_entry(&line, "test").unwrap();
+        assert_eq!(parsed.provider.as_str(), "test");
+        let expected = UsageEntry {
+            provider: ProviderId::new("test"),
+            ..entry
+        };
+        assert_eq!(
+            serde_json::to_value(&parsed).unwrap(),
+            serde_json::to_value(&expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("in-house_agent2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("../bin/sh").is_err());
+    }
+}
@@ -36,18 +36,24 @@
 //! ```
 
 // Re-export modules from ccstat-core
+pub use ccstat_core::cost;
+pub use ccstat_core::currency;
 pub use ccstat_core::error;
 pub use ccstat_core::filters;
 pub use ccstat_core::memory_pool;
 pub use ccstat_core::model_formatter;
 pub use ccstat_core::provider;
+pub use ccstat_core::registry;
 pub use ccstat_core::string_pool;
 pub use ccstat_core::timezone;
 pub use ccstat_core::types;
 
 // Re-export modules from ccstat-pricing
 pub use ccstat_pricing::cost_calculator;
+pub use ccstat_pricing::exchange_rates;
+pub use ccstat_pricing::pricing_cache;
 pub use ccstat_pricing::pricing_fetcher;
+pub use ccstat_pricing::user_pricing;
 
 // Re-export modules from ccstat-terminal
 pub use ccstat_terminal::blocks_monitor;
@@ -56,14 +62,23 @@ pub use ccstat_terminal::output;
 // Re-export modules from providers
 pub use ccstat_provider_amp as amp_provider;
 pub use ccstat_provider_claude::data_loader;
+pub use ccstat_provider_cline as cline_provider;
 pub use ccstat_provider_codex as codex_provider;
+pub use ccstat_provider_crush as crush_provider;
+pub use ccstat_provider_gemini as gemini_provider;
 pub use ccstat_provider_opencode as opencode_provider;
 pub use ccstat_provider_pi as pi_provider;
+pub use ccstat_provider_qwen as qwen_provider;
+pub use ccstat_provider_roo as roo_provider;
 
 // Local modules (not yet extracted)
 pub mod aggregation;
+pub mod all_providers;
 pub mod cli;
+pub mod external;
 pub mod live_monitor;
+pub mod mcp;
+pub mod providers;
 pub mod statusline;
 
 // Test utilities module (only compiled for tests)
@@ -71,6 +86,7 @@ pub mod statusline;
 pub mod test_utils;
 
 // Re-export commonly used types
+pub use cost::Cost;
 pub use error::{CcstatError, Result};
 pub use types::{CostMode, DailyDate, ISOTimestamp, ModelName, SessionId, TokenCounts};
 
@@ -101,7 +117,7 @@ mod tests {
         // Verify module paths are valid
         let _ = crate::filters::UsageFilter::new();
         let _ = crate::output::TableFormatter::new(false);
-        let _ = crate::output::JsonFormatter;
+        let _ = crate::output::JsonFormatter::new();
         let _ = crate::types::CostMode::Auto;
     }
 
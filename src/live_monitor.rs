@@ -1,8 +1,9 @@
 //! Live monitoring functionality for ccstat
 //!
 //! This module provides file watching and periodic updates for real-time
-//! usage monitoring. It watches for changes in JSONL files and refreshes
-//! the display at specified intervals.
+//! usage monitoring. It watches a provider's data files for changes and
+//! refreshes the display at specified intervals. Any [`DynDataLoader`]
+//! can back the monitor.
 
 #[cfg(test)]
 use crate::timezone::TimezoneConfig;
@@ -12,16 +13,18 @@ use crate::{
         create_and_filter_billing_blocks, filter_monthly_data,
     },
     blocks_monitor::{BlocksMonitor, DEFAULT_MAX_COST},
-    data_loader::DataLoader,
+    currency::Currency,
     error::{CcstatError, Result},
     filters::{MonthFilter, UsageFilter},
     output::get_formatter,
+    provider::DynDataLoader,
     types::{CostMode, UsageEntry},
 };
 use chrono::Local;
 use futures::StreamExt;
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::{
+    path::PathBuf,
     sync::{
         Arc, Mutex,
         atomic::{AtomicBool, Ordering},
@@ -41,6 +44,67 @@ const WATCHER_SHUTDOWN_TIMEOUT: Duration = Duration::from_millis(200); // 2x pol
 /// This is an empirical value based on typical usage patterns
 const APPROX_MAX_TOKENS_PER_BLOCK: f64 = 10_000_000.0;
 
+/// File extensions of provider data files (JSONL logs and per-message JSON)
+pub const DATA_FILE_EXTENSIONS: &[&str] = &["jsonl", "json"];
+
+/// Watch data directories recursively for data file changes
+///
+/// `on_change` is called from the watcher's own thread with the paths whose
+/// extension is one of `extensions` touched by each create/modify/remove
+/// event. Directories that do not exist are skipped. The returned watcher
+/// stops when dropped.
+pub fn watch_file_changes<F>(
+    dirs: &[PathBuf],
+    extensions: &'static [&'static str],
+    on_change: F,
+) -> Result<RecommendedWatcher>
+where
+    F: Fn(Vec<PathBuf>) + Send + 'static,
+{
+    let mut watcher = RecommendedWatcher::new(
+        move |result: notify::Result<Event>| {
+            if let Ok(event) = result
+                && matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                )
+            {
+                let changed: Vec<PathBuf> = event
+                    .paths
+                    .into_iter()
+                    .filter(|path| {
+                        path.extension()
+                            .and_then(|s| s.to_str())
+                            .is_some_and(|ext| extensions.contains(&ext))
+                    })
+                    .collect();
+                if !changed.is_empty() {
+                    on_change(changed);
+                }
+            }
+        },
+        Config::default(),
+    )
+    .map_err(|e| {
+        CcstatError::Io(std::io::Error::other(format!(
+            "Failed to create file watcher: {e}"
+        )))
+    })?;
+
+    for dir in dirs {
+        if dir.exists() {
+            watcher.watch(dir, RecursiveMode::Recursive).map_err(|e| {
+                CcstatError::Io(std::io::Error::other(format!(
+                    "Failed to watch directory {}: {e}",
+                    dir.display()
+                )))
+            })?;
+        }
+    }
+
+    Ok(watcher)
+}
+
 /// Command type for live monitoring
 #[derive(Debug, Clone)]
 pub enum CommandType {
@@ -62,8 +126,8 @@ pub enum CommandType {
 }
 
 /// Live monitoring state
-pub struct LiveMonitor {
-    data_loader: Arc<DataLoader>,
+pub struct LiveMonitor<L: DynDataLoader + ?Sized> {
+    data_loader: Arc<L>,
     aggregator: Arc<Aggregator>,
     filter: UsageFilter,
     month_filter: Option<MonthFilter>,
@@ -72,6 +136,8 @@ pub struct LiveMonitor {
     command_type: CommandType,
     interval_secs: u64,
     full_model_names: bool,
+    /// Currency costs are displayed in
+    currency: Currency,
     /// Historical maximum cost tracked across refresh cycles.
     /// Uses `Arc<Mutex>` for thread-safety. While production code is single-threaded,
     /// test code spawns concurrent tasks that require Send+Sync traits.
@@ -104,11 +170,11 @@ pub struct PreparedData {
     pub totals: Totals,
 }
 
-impl LiveMonitor {
+impl<L: DynDataLoader + ?Sized> LiveMonitor<L> {
     /// Create a new live monitor
     #[allow(clippy::too_many_arguments)]
     pub fn new(
-        data_loader: Arc<DataLoader>,
+        data_loader: Arc<L>,
         aggregator: Arc<Aggregator>,
         filter: UsageFilter,
         month_filter: Option<MonthFilter>,
@@ -128,6 +194,7 @@ impl LiveMonitor {
             command_type,
             interval_secs,
             full_model_names,
+            currency: Currency::usd(),
             historical_max_cost: Arc::new(Mutex::new(0.0)),
             user_max_cost: None,
             should_recalc_max: Arc::new(AtomicBool::new(true)), // Initially needs calculation
@@ -140,6 +207,12 @@ impl LiveMonitor {
         self
     }
 
+    /// Display costs in `currency`
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
     /// Start the live monitoring loop
     pub async fn run(self) -> Result<()> {
         // Track if we need to refresh
@@ -156,43 +229,11 @@ impl LiveMonitor {
 
         // Create watcher in a separate task
         let mut watcher_handle = tokio::task::spawn_blocking(move || -> Result<()> {
-            let mut watcher = RecommendedWatcher::new(
-                move |result: notify::Result<Event>| {
-                    if let Ok(event) = result
-                        && matches!(
-                            event.kind,
-                            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
-                        )
-                    {
-                        // Check if any path is a JSONL file
-                        for path in &event.paths {
-                            if path.extension().and_then(|s| s.to_str()) == Some("jsonl") {
-                                should_refresh_watcher.store(true, Ordering::Release);
-                                let _ = tx.blocking_send(());
-                                break;
-                            }
-                        }
-                    }
-                },
-                Config::default(),
-            )
-            .map_err(|e| {
-                CcstatError::Io(std::io::Error::other(format!(
-                    "Failed to create file watcher: {e}"
-                )))
-            })?;
-
-            // Watch all data directories
-            for dir in watched_dirs {
-                if dir.exists() {
-                    watcher.watch(&dir, RecursiveMode::Recursive).map_err(|e| {
-                        CcstatError::Io(std::io::Error::other(format!(
-                            "Failed to watch directory {}: {e}",
-                            dir.display()
-                        )))
-                    })?;
-                }
-            }
+            let watcher =
+                watch_file_changes(&watched_dirs, DATA_FILE_EXTENSIONS, move |_changed| {
+                    should_refresh_watcher.store(true, Ordering::Release);
+                    let _ = tx.blocking_send(());
+                })?;
 
             // Keep the watcher alive until we're told to stop
             while !should_stop_watcher.load(Ordering::Acquire) {
@@ -299,7 +340,7 @@ impl LiveMonitor {
     /// Prepare data for display by loading, filtering and aggregating
     pub async fn prepare_data(&self) -> Result<PreparedData> {
         // Load and aggregate data
-        let entries = self.data_loader.load_usage_entries_parallel();
+        let entries = self.data_loader.load_entries();
 
         // Apply filters and collect entries
         let filtered_entries: Vec<UsageEntry> = entries
@@ -423,7 +464,7 @@ impl LiveMonitor {
                 if self.user_max_cost.is_none() && self.should_recalc_max.load(Ordering::Acquire) {
                     // First, get ALL blocks (unfiltered) to calculate true historical maximum
                     let all_blocks_params = BillingBlockParams {
-                        data_loader: &self.data_loader,
+                        data_loader: self.data_loader.as_ref(),
                         aggregator: &self.aggregator,
                         cost_mode: self.cost_mode,
                         session_duration_hours: *session_duration,
@@ -442,7 +483,9 @@ impl LiveMonitor {
                         let true_max = all_blocks
                             .iter()
                             .map(|b| b.total_cost)
-                            .fold(0.0_f64, f64::max);
+                            .max()
+                            .unwrap_or_default()
+                            .to_usd();
 
                         // Update historical maximum with the true max
                         if let Ok(mut historical_max) = self.historical_max_cost.lock() {
@@ -459,7 +502,7 @@ impl LiveMonitor {
 
                 // Now get the filtered blocks for display
                 let params = BillingBlockParams {
-                    data_loader: &self.data_loader,
+                    data_loader: self.data_loader.as_ref(),
                     aggregator: &self.aggregator,
                     cost_mode: self.cost_mode,
                     session_duration_hours: *session_duration,
@@ -529,7 +572,11 @@ impl LiveMonitor {
         }
 
         // Generate output
-        let formatter = get_formatter(self.json_output, self.full_model_names);
+        let formatter = get_formatter(
+            self.json_output,
+            self.full_model_names,
+            self.currency.clone(),
+        );
 
         if !self.json_output {
             // Add active session indicators for table output (relevant for daily and session views)
@@ -624,7 +671,8 @@ impl LiveMonitor {
                             let monitor = BlocksMonitor::new(
                                 self.aggregator.timezone_config().tz,
                                 Some(max_cost),
-                            );
+                            )
+                            .with_currency(self.currency.clone());
                             println!(
                                 "{}",
                                 monitor.render_active_block(active_block, chrono::Utc::now())
@@ -659,8 +707,10 @@ impl LiveMonitor {
 mod tests {
     use super::*;
     use crate::cost_calculator::CostCalculator;
+    use crate::data_loader::DataLoader;
     use crate::pricing_fetcher::PricingFetcher;
-    use crate::types::{ISOTimestamp, ModelName, SessionId, TokenCounts};
+    use crate::provider::ProviderDataLoader;
+    use crate::types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts};
     use chrono::{TimeZone, Utc};
 
     /// Helper function to create a mock DataLoader for testing
@@ -1022,6 +1072,7 @@ mod tests {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         let old_entry = UsageEntry {
@@ -1032,6 +1083,7 @@ mod tests {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         // Test that recent entry is considered active
@@ -1353,6 +1405,7 @@ mod tests {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         let old_entry = UsageEntry {
@@ -1363,6 +1416,7 @@ mod tests {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         let boundary_entry = UsageEntry {
@@ -1373,6 +1427,7 @@ mod tests {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         };
 
         // Test active session detection
@@ -1559,4 +1614,60 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_blocks_from_non_claude_provider() {
+        use crate::test_utils::{ENV_MUTEX, EnvVarGuard};
+        use std::io::Write;
+
+        let _lock = ENV_MUTEX.lock().await;
+        let dir = tempfile::TempDir::new().unwrap();
+        let sessions_dir = dir.path().join("sessions");
+        std::fs::create_dir_all(&sessions_dir).unwrap();
+        let mut file = std::fs::File::create(sessions_dir.join("rollout.jsonl")).unwrap();
+        writeln!(file, r#"{{"type":"turn_context","model_id":"gpt-5"}}"#).unwrap();
+        for (minutes_ago, input, output) in [(40, 100, 50), (10, 300, 150)] {
+            let ts = (Utc::now() - chrono::Duration::minutes(minutes_ago)).to_rfc3339();
+            writeln!(
+                file,
+                r#"{{"type":"event_msg","timestamp":"{ts}","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":{input},"output_tokens":{output},"total_tokens":{}}}}}}}}}"#,
+                input + output
+            )
+            .unwrap();
+        }
+
+        let mut guard = EnvVarGuard::new();
+        guard.set("CODEX_HOME", dir.path().to_str().unwrap());
+        let data_loader = Arc::new(ccstat_provider_codex::DataLoader::new().await.unwrap());
+        assert_eq!(DynDataLoader::paths(data_loader.as_ref()), [sessions_dir]);
+
+        let pricing_fetcher = Arc::new(PricingFetcher::new(true).await);
+        let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
+        let aggregator = Arc::new(Aggregator::new(
+            cost_calculator,
+            TimezoneConfig::from_cli(None, true).unwrap(),
+        ));
+        let monitor = LiveMonitor::new(
+            data_loader,
+            aggregator,
+            UsageFilter::new(),
+            None,
+            CostMode::Auto,
+            false,
+            CommandType::Blocks {
+                active: true,
+                recent: false,
+                token_limit: None,
+                session_duration: ccstat_provider_codex::DataLoader::DEFAULT_BLOCK_HOURS,
+            },
+            5,
+            false,
+        );
+
+        let blocks = monitor.prepare_data().await.unwrap().blocks_data.unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].is_active);
+        assert_eq!(blocks[0].tokens.input_tokens, 300);
+        assert_eq!(blocks[0].tokens.output_tokens, 150);
+    }
 }
@@ -5,25 +5,44 @@ use ccstat::{
         Aggregator, BillingBlockParams, Totals, create_and_filter_billing_blocks,
         filter_monthly_data,
     },
+    all_providers::{
+        aggregate_daily_by_provider, aggregate_monthly_by_provider, aggregate_sessions_by_provider,
+        aggregate_weekly_by_provider, load_providers, provider_entries,
+    },
     cli::{
-        BlocksArgs, Cli, Command, Provider, Report, WeeklyArgs, is_statusline_command,
-        parse_date_filter, parse_weekday, resolve_provider_report, validate_provider_report,
+        BlocksArgs, Cli, Command, McpArgs, PricingCommand, Provider, Report, WeeklyArgs,
+        is_statusline_command, parse_date_filter, parse_weekday, resolve_external_command,
+        resolve_provider_report, validate_provider_report,
     },
     cost_calculator::CostCalculator,
+    currency::Currency,
     data_loader::DataLoader,
     error::{CcstatError, Result},
+    exchange_rates::ExchangeRates,
+    external::ExternalProvider,
     filters::{MonthFilter, UsageFilter},
     live_monitor::{CommandType, LiveMonitor},
+    mcp::{McpReportHandler, watch_resources},
     output::get_formatter,
-    pricing_fetcher::PricingFetcher,
-    provider::ProviderDataLoader,
+    pricing_cache::PricingCache,
+    pricing_fetcher::{PricingFetcher, PricingSource},
+    provider::{DynDataLoader, ProviderDataLoader},
+    registry::ProviderInfo,
     timezone::TimezoneConfig,
+    types::{ModelName, ModelPricing, UsageEntry},
+    user_pricing::UserPricing,
 };
+use ccstat_mcp::HttpConfig;
 use chrono::Datelike;
 use clap::Parser;
+use futures::Stream;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use std::time::Duration;
+use tracing::{info, warn};
+use tracing_subscriber::{
+    fmt::writer::BoxMakeWriter, layer::SubscriberExt, util::SubscriberInitExt,
+};
 
 /// Approximate maximum tokens for a 5-hour billing block
 const APPROX_MAX_TOKENS_PER_BLOCK: f64 = 10_000_000.0;
@@ -43,12 +62,87 @@ fn create_aggregator_with_timezone(
     Ok(Aggregator::new(cost_calculator, tz_config).with_progress(show_progress))
 }
 
-async fn init_data_loader(show_progress: bool, intern: bool, arena: bool) -> Result<DataLoader> {
-    Ok(DataLoader::new()
-        .await?
+async fn init_data_loader(cli: &Cli, show_progress: bool) -> Result<DataLoader> {
+    let data_loader = match data_roots(cli)? {
+        Some(roots) => DataLoader::from_roots(roots),
+        None => DataLoader::new().await?,
+    };
+    Ok(data_loader
         .with_progress(show_progress)
-        .with_interning(intern)
-        .with_arena(arena))
+        .with_interning(cli.intern)
+        .with_arena(cli.arena))
+}
+
+/// Pricing fetcher honoring `--offline`, `--pricing-file`,
+/// `--strict-pricing`, and the on-disk pricing cache
+async fn init_pricing_fetcher(cli: &Cli) -> Result<Arc<PricingFetcher>> {
+    let mut pricing_fetcher = PricingFetcher::new(cli.offline)
+        .await
+        .with_strict_pricing(cli.strict_pricing);
+    if let Some(path) = PricingCache::default_path() {
+        let ttl = Duration::from_secs(cli.pricing_ttl.saturating_mul(60 * 60));
+        pricing_fetcher = pricing_fetcher.with_disk_cache(PricingCache::new(path).with_ttl(ttl));
+    }
+    if let Some(user_pricing) = load_user_pricing(cli)? {
+        pricing_fetcher = pricing_fetcher.with_user_pricing(user_pricing);
+    }
+    Ok(Arc::new(pricing_fetcher))
+}
+
+/// User pricing file given with `--pricing-file`, or the default one if it
+/// exists
+fn load_user_pricing(cli: &Cli) -> Result<Option<UserPricing>> {
+    match &cli.pricing_file {
+        Some(path) => Ok(Some(UserPricing::load(path)?)),
+        None => UserPricing::load_default(),
+    }
+}
+
+/// Currency selected with `--currency`, at the rate from `--exchange-rates`
+/// or the pricing file's `[exchange_rates]` table
+fn display_currency(cli: &Cli) -> Result<Currency> {
+    let code = match cli.currency.as_deref() {
+        Some(code) if !code.eq_ignore_ascii_case("USD") => code,
+        _ => return Ok(Currency::usd()),
+    };
+    let rates = match &cli.exchange_rates {
+        Some(path) => ExchangeRates::load(path)?,
+        None => load_user_pricing(cli)?
+            .and_then(|user_pricing| user_pricing.exchange_rates().cloned())
+            .ok_or_else(|| {
+                CcstatError::Config(format!(
+                    "No exchange rates to convert costs to {}; pass --exchange-rates \
+                     or add an [exchange_rates] table to the pricing file",
+                    code.to_ascii_uppercase()
+                ))
+            })?,
+    };
+    rates.currency(code)
+}
+
+/// Data directories given with `--data-dir`, if any
+fn data_roots(cli: &Cli) -> Result<Option<Vec<PathBuf>>> {
+    if cli.data_dirs.is_empty() {
+        return Ok(None);
+    }
+    if let Some(missing) = cli.data_dirs.iter().find(|dir| !dir.is_dir()) {
+        return Err(CcstatError::Config(format!(
+            "Data directory not found: {}",
+            missing.display()
+        )));
+    }
+    Ok(Some(cli.data_dirs.clone()))
+}
+
+/// Reject `--data-dir` for commands that read from more than one provider
+fn reject_data_dirs(cli: &Cli, command: &str) -> Result<()> {
+    if cli.data_dirs.is_empty() {
+        Ok(())
+    } else {
+        Err(CcstatError::Config(format!(
+            "--data-dir cannot be used with '{command}'; select a single provider"
+        )))
+    }
 }
 
 fn build_usage_filter(cli: &Cli, aggregator: &Aggregator) -> Result<UsageFilter> {
@@ -66,6 +160,19 @@ fn build_usage_filter(cli: &Cli, aggregator: &Aggregator) -> Result<UsageFilter>
     Ok(filter)
 }
 
+fn build_month_filter(cli: &Cli) -> Result<MonthFilter> {
+    let mut month_filter = MonthFilter::new();
+    if let Some(since_str) = &cli.since {
+        let since_date = parse_date_filter(since_str)?;
+        month_filter = month_filter.with_since(since_date.year(), since_date.month());
+    }
+    if let Some(until_str) = &cli.until {
+        let until_date = parse_date_filter(until_str)?;
+        month_filter = month_filter.with_until(until_date.year(), until_date.month());
+    }
+    Ok(month_filter)
+}
+
 fn show_progress(cli: &Cli) -> bool {
     !cli.json && !cli.watch && is_terminal::is_terminal(std::io::stdout())
 }
@@ -83,9 +190,15 @@ async fn main() -> Result<()> {
         let default_level = if cli.verbose { "ccstat=info" } else { "warn" };
         let filter = tracing_subscriber::EnvFilter::try_from_default_env()
             .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(default_level));
+        // The MCP stdio transport owns stdout, so logs must go to stderr
+        let writer = if matches!(cli.command, Some(Command::Mcp(_))) {
+            BoxMakeWriter::new(std::io::stderr)
+        } else {
+            BoxMakeWriter::new(std::io::stdout)
+        };
         tracing_subscriber::registry()
             .with(filter)
-            .with(tracing_subscriber::fmt::layer())
+            .with(tracing_subscriber::fmt::layer().with_writer(writer))
             .init();
     }
 
@@ -103,30 +216,45 @@ async fn main() -> Result<()> {
             info!("Running live billing block monitor");
             let mut cli_with_watch = cli.clone();
             cli_with_watch.watch = true;
+            let data_loader = init_data_loader(&cli, false).await?;
             handle_blocks_command(
                 &cli_with_watch,
                 &BlocksArgs {
                     active: true,
                     recent: false,
                     token_limit: None,
-                    session_duration: 5.0,
+                    session_duration: None,
                     max_cost: args.max_cost,
                 },
+                Arc::new(data_loader),
             )
             .await?;
         }
 
-        // Stub: MCP server
-        Some(Command::Mcp) => {
-            return Err(CcstatError::Config(
-                "MCP server is not yet implemented. Track progress at https://github.com/hydai/ccstat/issues".into(),
-            ));
+        // MCP server (stdio or streamable HTTP)
+        Some(Command::Mcp(args)) => {
+            reject_data_dirs(&cli, "mcp")?;
+            handle_mcp_command(&cli, args).await?;
+        }
+
+        // Pricing data maintenance
+        Some(Command::Pricing { command }) => {
+            reject_data_dirs(&cli, "pricing")?;
+            handle_pricing_command(&cli, command).await?;
+        }
+
+        // External provider plugin: ext:<name> <report>
+        Some(Command::External(args)) => {
+            let (name, cli, report) =
+                resolve_external_command(std::env::args(), args).unwrap_or_else(|e| e.exit());
+            reject_data_dirs(&cli, "ext:")?;
+            handle_external_command(&cli, &name, &report).await?;
         }
 
         // Provider/report commands (includes both explicit provider and shortcuts)
         Some(cmd) => {
-            let (provider, report) =
-                resolve_provider_report(cmd).expect("Watch and Mcp are handled above");
+            let (provider, report) = resolve_provider_report(cmd)
+                .expect("Watch, Mcp, Pricing, and external providers are handled above");
             validate_provider_report(provider, &report)?;
 
             dispatch_provider_report(&cli, provider, &report).await?;
@@ -141,26 +269,18 @@ async fn main() -> Result<()> {
 // ---------------------------------------------------------------------------
 
 /// Route a report to the appropriate provider-specific handler
+///
+/// Claude reports and Codex rate limits have dedicated handlers; every other
+/// registered provider goes through [`dispatch_provider_with_loader`].
 async fn dispatch_provider_report(cli: &Cli, provider: Provider, report: &Report) -> Result<()> {
-    match provider {
-        Provider::Claude => dispatch_claude_report(cli, report).await,
-        Provider::Codex => {
-            dispatch_provider_with_loader::<ccstat_provider_codex::DataLoader>(cli, report, "codex")
-                .await
-        }
-        Provider::Opencode => {
-            dispatch_provider_with_loader::<ccstat_provider_opencode::DataLoader>(
-                cli, report, "opencode",
-            )
-            .await
-        }
-        Provider::Amp => {
-            dispatch_provider_with_loader::<ccstat_provider_amp::DataLoader>(cli, report, "amp")
-                .await
-        }
-        Provider::Pi => {
-            dispatch_provider_with_loader::<ccstat_provider_pi::DataLoader>(cli, report, "pi").await
-        }
+    let Some(info) = provider.info() else {
+        reject_data_dirs(cli, "all")?;
+        return dispatch_all_providers(cli, report).await;
+    };
+    match (info.name, report) {
+        ("claude", _) => dispatch_claude_report(cli, report).await,
+        ("codex", Report::Limits) => handle_codex_limits_command(cli).await,
+        _ => dispatch_provider_with_loader(cli, report, info).await,
     }
 }
 
@@ -170,42 +290,85 @@ async fn dispatch_claude_report(cli: &Cli, report: &Report) -> Result<()> {
         Report::Monthly => handle_monthly_command(cli).await,
         Report::Weekly(args) => handle_weekly_command(cli, args).await,
         Report::Session(_) => handle_session_command(cli).await,
-        Report::Blocks(args) => handle_blocks_command(cli, args).await,
+        Report::Blocks(args) => {
+            let data_loader = init_data_loader(cli, show_progress(cli)).await?;
+            handle_blocks_command(cli, args, Arc::new(data_loader)).await
+        }
         Report::Statusline(args) => {
             ccstat::statusline::run(
                 args.monthly_fee,
                 args.no_color,
                 args.show_date,
                 args.show_git,
+                data_roots(cli)?,
+                cli.pricing_file.clone(),
+                display_currency(cli)?,
             )
             .await
         }
+        Report::Limits => Err(CcstatError::Config(
+            "The 'limits' report is not supported for the 'claude' provider".to_string(),
+        )),
     }
 }
 
 /// Generic dispatch for non-Claude providers.
 ///
-/// Creates the provider's data loader, feeds entries into the shared
-/// aggregation pipeline, and formats output. Watch mode is not supported
-/// for non-Claude providers.
-async fn dispatch_provider_with_loader<T: ProviderDataLoader>(
+/// Creates the provider's data loader from the registry, feeds entries into
+/// the shared aggregation pipeline, and formats output. Billing blocks and
+/// watch mode go through the same code paths Claude uses.
+async fn dispatch_provider_with_loader(
     cli: &Cli,
     report: &Report,
-    provider_name: &str,
+    info: &ProviderInfo,
 ) -> Result<()> {
+    let provider_name = info.name;
     info!("Running {} provider report", provider_name);
 
+    let data_loader = match data_roots(cli)? {
+        Some(roots) => info.load_from(roots).await?,
+        None => info.load().await?,
+    };
+    if let Report::Blocks(args) = report {
+        return handle_blocks_command(cli, args, data_loader.into()).await;
+    }
+    if cli.watch {
+        return watch_provider_report(cli, report, data_loader, provider_name).await;
+    }
+
     let sp = show_progress(cli);
-    let data_loader = T::new().await?;
-    let pricing_fetcher = Arc::new(PricingFetcher::new(false).await);
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let currency = display_currency(cli)?;
     let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
     let aggregator =
         create_aggregator_with_timezone(cost_calculator, sp, cli.timezone.as_deref(), cli.utc)?;
     let filter = build_usage_filter(cli, &aggregator)?;
 
+    let data_loader = data_loader.with_date_range(filter.since_date, filter.until_date);
     let entries = data_loader.load_entries();
     let filtered_entries = filter.filter_stream(entries).await;
+    print_entries_report(
+        cli,
+        report,
+        &aggregator,
+        filtered_entries,
+        provider_name,
+        currency,
+    )
+    .await
+}
 
+/// Aggregate filtered entries into the requested report and print it
+///
+/// Shared by the non-Claude providers and external plugins.
+async fn print_entries_report(
+    cli: &Cli,
+    report: &Report,
+    aggregator: &Aggregator,
+    filtered_entries: impl Stream<Item = Result<UsageEntry>>,
+    provider_name: &str,
+    currency: Currency,
+) -> Result<()> {
     match report {
         Report::Daily(args) => {
             if args.instances {
@@ -213,7 +376,7 @@ async fn dispatch_provider_with_loader<T: ProviderDataLoader>(
                     .aggregate_daily_by_instance(filtered_entries, cli.mode)
                     .await?;
                 let totals = Totals::from_daily_instances(&instance_data);
-                let formatter = get_formatter(cli.json, cli.full_model_names);
+                let formatter = get_formatter(cli.json, cli.full_model_names, currency);
                 println!(
                     "{}",
                     formatter.format_daily_by_instance(&instance_data, &totals)
@@ -223,7 +386,7 @@ async fn dispatch_provider_with_loader<T: ProviderDataLoader>(
                     .aggregate_daily_detailed(filtered_entries, cli.mode, args.detailed)
                     .await?;
                 let totals = Totals::from_daily(&daily_data);
-                let formatter = get_formatter(cli.json, cli.full_model_names);
+                let formatter = get_formatter(cli.json, cli.full_model_names, currency);
                 println!("{}", formatter.format_daily(&daily_data, &totals));
             }
         }
@@ -232,18 +395,10 @@ async fn dispatch_provider_with_loader<T: ProviderDataLoader>(
                 .aggregate_daily(filtered_entries, cli.mode)
                 .await?;
             let mut monthly_data = Aggregator::aggregate_monthly(&daily_data);
-            let mut month_filter = MonthFilter::new();
-            if let Some(since_str) = &cli.since {
-                let since_date = parse_date_filter(since_str)?;
-                month_filter = month_filter.with_since(since_date.year(), since_date.month());
-            }
-            if let Some(until_str) = &cli.until {
-                let until_date = parse_date_filter(until_str)?;
-                month_filter = month_filter.with_until(until_date.year(), until_date.month());
-            }
+            let month_filter = build_month_filter(cli)?;
             filter_monthly_data(&mut monthly_data, &month_filter);
             let totals = Totals::from_monthly(&monthly_data);
-            let formatter = get_formatter(cli.json, cli.full_model_names);
+            let formatter = get_formatter(cli.json, cli.full_model_names, currency);
             println!("{}", formatter.format_monthly(&monthly_data, &totals));
         }
         Report::Weekly(args) => {
@@ -253,7 +408,7 @@ async fn dispatch_provider_with_loader<T: ProviderDataLoader>(
                 .await?;
             let weekly_data = Aggregator::aggregate_weekly(&daily_data, start_of_week);
             let totals = Totals::from_weekly(&weekly_data);
-            let formatter = get_formatter(cli.json, cli.full_model_names);
+            let formatter = get_formatter(cli.json, cli.full_model_names, currency);
             println!("{}", formatter.format_weekly(&weekly_data, &totals));
         }
         Report::Session(_) => {
@@ -261,7 +416,7 @@ async fn dispatch_provider_with_loader<T: ProviderDataLoader>(
                 .aggregate_sessions(filtered_entries, cli.mode)
                 .await?;
             let totals = Totals::from_sessions(&session_data);
-            let formatter = get_formatter(cli.json, cli.full_model_names);
+            let formatter = get_formatter(cli.json, cli.full_model_names, currency);
             println!(
                 "{}",
                 formatter.format_sessions(&session_data, &totals, &aggregator.timezone_config().tz)
@@ -278,6 +433,195 @@ async fn dispatch_provider_with_loader<T: ProviderDataLoader>(
     Ok(())
 }
 
+/// Live monitoring for a non-Claude provider's (non-blocks) report
+async fn watch_provider_report(
+    cli: &Cli,
+    report: &Report,
+    data_loader: Box<dyn DynDataLoader>,
+    provider_name: &str,
+) -> Result<()> {
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let currency = display_currency(cli)?;
+    let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
+    let aggregator = Arc::new(create_aggregator_with_timezone(
+        cost_calculator,
+        false,
+        cli.timezone.as_deref(),
+        cli.utc,
+    )?);
+    let filter = build_usage_filter(cli, &aggregator)?;
+
+    // Monthly and weekly views filter by month after aggregation, like Claude's
+    let (filter, month_filter, command_type) = match report {
+        Report::Daily(args) => (
+            filter,
+            None,
+            CommandType::Daily {
+                instances: args.instances,
+                detailed: args.detailed,
+            },
+        ),
+        Report::Monthly => (
+            UsageFilter::new(),
+            Some(build_month_filter(cli)?),
+            CommandType::Monthly,
+        ),
+        Report::Weekly(args) => (
+            UsageFilter::new(),
+            Some(build_month_filter(cli)?),
+            CommandType::Weekly {
+                start_of_week: parse_weekday(&args.start_of_week)?,
+            },
+        ),
+        Report::Session(_) => (filter, None, CommandType::Session),
+        Report::Blocks(_) | Report::Statusline(_) | Report::Limits => {
+            return Err(CcstatError::Config(format!(
+                "Watch mode is not supported for this report with the {} provider",
+                provider_name
+            )));
+        }
+    };
+
+    info!("Starting live monitoring mode");
+    let data_loader = data_loader.with_date_range(filter.since_date, filter.until_date);
+    let monitor = LiveMonitor::new(
+        Arc::<dyn DynDataLoader>::from(data_loader),
+        aggregator,
+        filter,
+        month_filter,
+        cli.mode,
+        cli.json,
+        command_type,
+        cli.interval,
+        cli.full_model_names,
+    )
+    .with_currency(currency);
+    monitor.run().await
+}
+
+/// Latest Codex rate-limit window usage
+async fn handle_codex_limits_command(cli: &Cli) -> Result<()> {
+    info!("Running Codex rate-limit report");
+
+    let currency = display_currency(cli)?;
+    let data_loader = match data_roots(cli)? {
+        Some(roots) => ccstat_provider_codex::DataLoader::from_roots(roots),
+        None => ccstat_provider_codex::DataLoader::new().await?,
+    };
+    let snapshot = data_loader.load_rate_limits().await?;
+    let tz_config = TimezoneConfig::from_cli(cli.timezone.as_deref(), cli.utc)?;
+    let formatter = get_formatter(cli.json, cli.full_model_names, currency);
+    println!(
+        "{}",
+        formatter.format_rate_limits(snapshot.as_ref(), &tz_config.tz)
+    );
+    Ok(())
+}
+
+/// Report from an external provider plugin
+///
+/// The plugin's entries go through the same filter and aggregation as the
+/// built-in providers. Watch mode is not supported.
+async fn handle_external_command(cli: &Cli, name: &str, report: &Report) -> Result<()> {
+    info!("Running external provider '{}'", name);
+
+    if cli.watch {
+        return Err(CcstatError::Config(
+            "Watch mode is not supported for external providers".to_string(),
+        ));
+    }
+
+    let provider = ExternalProvider::new(name)?;
+    let sp = show_progress(cli);
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let currency = display_currency(cli)?;
+    let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
+    let aggregator =
+        create_aggregator_with_timezone(cost_calculator, sp, cli.timezone.as_deref(), cli.utc)?;
+    let filter = build_usage_filter(cli, &aggregator)?;
+
+    let provider = provider.with_date_range(filter.since_date, filter.until_date);
+    let filtered_entries = filter.filter_stream(provider.load_entries()).await;
+    print_entries_report(cli, report, &aggregator, filtered_entries, name, currency).await
+}
+
+/// Combined dispatch across every provider.
+///
+/// Each provider is aggregated separately so the output can show a
+/// provider column and per-provider subtotals. Watch mode is not supported.
+async fn dispatch_all_providers(cli: &Cli, report: &Report) -> Result<()> {
+    info!("Running combined report for all providers");
+
+    if let Report::Daily(args) = report
+        && (args.instances || args.detailed)
+    {
+        return Err(CcstatError::InvalidArgument(
+            "--instances and --detailed are not supported for the 'all' provider".to_string(),
+        ));
+    }
+
+    let sp = show_progress(cli);
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let currency = display_currency(cli)?;
+    let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
+    let aggregator =
+        create_aggregator_with_timezone(cost_calculator, sp, cli.timezone.as_deref(), cli.utc)?;
+    let filter = build_usage_filter(cli, &aggregator)?;
+    let loaders = load_providers(&filter).await;
+    let entries = provider_entries(&loaders, &filter).await;
+    let formatter = get_formatter(cli.json, cli.full_model_names, currency);
+
+    match report {
+        Report::Daily(_) => {
+            let daily_data = aggregate_daily_by_provider(&aggregator, entries, cli.mode).await;
+            let totals = Totals::from_providers(&daily_data);
+            println!(
+                "{}",
+                formatter.format_daily_by_provider(&daily_data, &totals)
+            );
+        }
+        Report::Monthly => {
+            let daily_data = aggregate_daily_by_provider(&aggregator, entries, cli.mode).await;
+            let month_filter = build_month_filter(cli)?;
+            let monthly_data = aggregate_monthly_by_provider(&daily_data, &month_filter);
+            let totals = Totals::from_providers(&monthly_data);
+            println!(
+                "{}",
+                formatter.format_monthly_by_provider(&monthly_data, &totals)
+            );
+        }
+        Report::Weekly(args) => {
+            let start_of_week = parse_weekday(&args.start_of_week)?;
+            let daily_data = aggregate_daily_by_provider(&aggregator, entries, cli.mode).await;
+            let weekly_data = aggregate_weekly_by_provider(&daily_data, start_of_week);
+            let totals = Totals::from_providers(&weekly_data);
+            println!(
+                "{}",
+                formatter.format_weekly_by_provider(&weekly_data, &totals)
+            );
+        }
+        Report::Session(_) => {
+            let session_data = aggregate_sessions_by_provider(&aggregator, entries, cli.mode).await;
+            let totals = Totals::from_providers(&session_data);
+            println!(
+                "{}",
+                formatter.format_sessions_by_provider(
+                    &session_data,
+                    &totals,
+                    &aggregator.timezone_config().tz
+                )
+            );
+        }
+        _ => {
+            return Err(CcstatError::Config(
+                "Report type not supported for all providers".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Command handlers
 // ---------------------------------------------------------------------------
@@ -286,8 +630,9 @@ async fn handle_daily_command(cli: &Cli, instances: bool, detailed: bool) -> Res
     info!("Running daily usage report");
 
     let sp = show_progress(cli);
-    let data_loader = Arc::new(init_data_loader(sp, cli.intern, cli.arena).await?);
-    let pricing_fetcher = Arc::new(PricingFetcher::new(false).await);
+    let data_loader = Arc::new(init_data_loader(cli, sp).await?);
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let currency = display_currency(cli)?;
     let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
     let aggregator = Arc::new(create_aggregator_with_timezone(
         cost_calculator,
@@ -312,7 +657,8 @@ async fn handle_daily_command(cli: &Cli, instances: bool, detailed: bool) -> Res
             },
             cli.interval,
             cli.full_model_names,
-        );
+        )
+        .with_currency(currency);
         monitor.run().await
     } else if instances {
         let entries = Box::pin(data_loader.load_usage_entries_parallel());
@@ -321,7 +667,7 @@ async fn handle_daily_command(cli: &Cli, instances: bool, detailed: bool) -> Res
             .aggregate_daily_by_instance(filtered_entries, cli.mode)
             .await?;
         let totals = Totals::from_daily_instances(&instance_data);
-        let formatter = get_formatter(cli.json, cli.full_model_names);
+        let formatter = get_formatter(cli.json, cli.full_model_names, currency);
         println!(
             "{}",
             formatter.format_daily_by_instance(&instance_data, &totals)
@@ -334,7 +680,7 @@ async fn handle_daily_command(cli: &Cli, instances: bool, detailed: bool) -> Res
             .aggregate_daily_detailed(filtered_entries, cli.mode, detailed)
             .await?;
         let totals = Totals::from_daily(&daily_data);
-        let formatter = get_formatter(cli.json, cli.full_model_names);
+        let formatter = get_formatter(cli.json, cli.full_model_names, currency);
         println!("{}", formatter.format_daily(&daily_data, &totals));
         Ok(())
     }
@@ -344,8 +690,9 @@ async fn handle_monthly_command(cli: &Cli) -> Result<()> {
     info!("Running monthly usage report");
 
     let sp = show_progress(cli);
-    let data_loader = Arc::new(init_data_loader(sp, cli.intern, cli.arena).await?);
-    let pricing_fetcher = Arc::new(PricingFetcher::new(false).await);
+    let data_loader = Arc::new(init_data_loader(cli, sp).await?);
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let currency = display_currency(cli)?;
     let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
     let aggregator = Arc::new(create_aggregator_with_timezone(
         cost_calculator,
@@ -354,15 +701,7 @@ async fn handle_monthly_command(cli: &Cli) -> Result<()> {
         cli.utc,
     )?);
 
-    let mut month_filter = MonthFilter::new();
-    if let Some(since_str) = &cli.since {
-        let since_date = parse_date_filter(since_str)?;
-        month_filter = month_filter.with_since(since_date.year(), since_date.month());
-    }
-    if let Some(until_str) = &cli.until {
-        let until_date = parse_date_filter(until_str)?;
-        month_filter = month_filter.with_until(until_date.year(), until_date.month());
-    }
+    let month_filter = build_month_filter(cli)?;
 
     if cli.watch {
         info!("Starting live monitoring mode");
@@ -377,7 +716,8 @@ async fn handle_monthly_command(cli: &Cli) -> Result<()> {
             CommandType::Monthly,
             cli.interval,
             cli.full_model_names,
-        );
+        )
+        .with_currency(currency);
         monitor.run().await
     } else {
         let entries = Box::pin(data_loader.load_usage_entries_parallel());
@@ -385,7 +725,7 @@ async fn handle_monthly_command(cli: &Cli) -> Result<()> {
         let mut monthly_data = Aggregator::aggregate_monthly(&daily_data);
         filter_monthly_data(&mut monthly_data, &month_filter);
         let totals = Totals::from_monthly(&monthly_data);
-        let formatter = get_formatter(cli.json, cli.full_model_names);
+        let formatter = get_formatter(cli.json, cli.full_model_names, currency);
         println!("{}", formatter.format_monthly(&monthly_data, &totals));
         Ok(())
     }
@@ -397,8 +737,9 @@ async fn handle_weekly_command(cli: &Cli, args: &WeeklyArgs) -> Result<()> {
     let start_of_week = parse_weekday(&args.start_of_week)?;
 
     let sp = show_progress(cli);
-    let data_loader = Arc::new(init_data_loader(sp, cli.intern, cli.arena).await?);
-    let pricing_fetcher = Arc::new(PricingFetcher::new(false).await);
+    let data_loader = Arc::new(init_data_loader(cli, sp).await?);
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let currency = display_currency(cli)?;
     let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
     let aggregator = Arc::new(create_aggregator_with_timezone(
         cost_calculator,
@@ -407,15 +748,7 @@ async fn handle_weekly_command(cli: &Cli, args: &WeeklyArgs) -> Result<()> {
         cli.utc,
     )?);
 
-    let mut month_filter = MonthFilter::new();
-    if let Some(since_str) = &cli.since {
-        let since_date = parse_date_filter(since_str)?;
-        month_filter = month_filter.with_since(since_date.year(), since_date.month());
-    }
-    if let Some(until_str) = &cli.until {
-        let until_date = parse_date_filter(until_str)?;
-        month_filter = month_filter.with_until(until_date.year(), until_date.month());
-    }
+    let month_filter = build_month_filter(cli)?;
 
     if cli.watch {
         info!("Starting live monitoring mode");
@@ -430,7 +763,8 @@ async fn handle_weekly_command(cli: &Cli, args: &WeeklyArgs) -> Result<()> {
             CommandType::Weekly { start_of_week },
             cli.interval,
             cli.full_model_names,
-        );
+        )
+        .with_currency(currency);
         monitor.run().await
     } else {
         let entries = Box::pin(data_loader.load_usage_entries_parallel());
@@ -447,7 +781,7 @@ async fn handle_weekly_command(cli: &Cli, args: &WeeklyArgs) -> Result<()> {
         });
 
         let totals = Totals::from_weekly(&weekly_data);
-        let formatter = get_formatter(cli.json, cli.full_model_names);
+        let formatter = get_formatter(cli.json, cli.full_model_names, currency);
         println!("{}", formatter.format_weekly(&weekly_data, &totals));
         Ok(())
     }
@@ -457,8 +791,9 @@ async fn handle_session_command(cli: &Cli) -> Result<()> {
     info!("Running session usage report");
 
     let sp = show_progress(cli);
-    let data_loader = Arc::new(init_data_loader(sp, cli.intern, cli.arena).await?);
-    let pricing_fetcher = Arc::new(PricingFetcher::new(false).await);
+    let data_loader = Arc::new(init_data_loader(cli, sp).await?);
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let currency = display_currency(cli)?;
     let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
     let aggregator = Arc::new(create_aggregator_with_timezone(
         cost_calculator,
@@ -480,7 +815,8 @@ async fn handle_session_command(cli: &Cli) -> Result<()> {
             CommandType::Session,
             cli.interval,
             cli.full_model_names,
-        );
+        )
+        .with_currency(currency);
         monitor.run().await
     } else {
         let entries = Box::pin(data_loader.load_usage_entries_parallel());
@@ -489,7 +825,7 @@ async fn handle_session_command(cli: &Cli) -> Result<()> {
             .aggregate_sessions(filtered_entries, cli.mode)
             .await?;
         let totals = Totals::from_sessions(&session_data);
-        let formatter = get_formatter(cli.json, cli.full_model_names);
+        let formatter = get_formatter(cli.json, cli.full_model_names, currency);
         println!(
             "{}",
             formatter.format_sessions(&session_data, &totals, &aggregator.timezone_config().tz)
@@ -498,12 +834,262 @@ async fn handle_session_command(cli: &Cli) -> Result<()> {
     }
 }
 
-async fn handle_blocks_command(cli: &Cli, args: &BlocksArgs) -> Result<()> {
+async fn handle_mcp_command(cli: &Cli, args: &McpArgs) -> Result<()> {
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
+    let handler = Arc::new(McpReportHandler::new(cost_calculator));
+    let server = ccstat_mcp::McpServer::new(handler);
+
+    // Keep the watcher alive for the lifetime of the server
+    let _watcher = match DataLoader::new().await {
+        Ok(data_loader) => match watch_resources(server.clone(), data_loader.paths()) {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                warn!("Resource change notifications disabled: {}", e);
+                None
+            }
+        },
+        Err(e) => {
+            info!("Resource change notifications disabled: {}", e);
+            None
+        }
+    };
+
+    match args.http {
+        Some(bind) => {
+            info!("Starting MCP server on http://{}", bind);
+            let config = HttpConfig::new(bind)
+                .with_bearer_token(args.token.clone())
+                .with_allowed_hosts(args.allow_hosts.clone());
+            server.serve_http(config).await
+        }
+        None => {
+            info!("Starting MCP server on stdio");
+            server.serve_stdio().await
+        }
+    }
+}
+
+async fn handle_pricing_command(cli: &Cli, command: &PricingCommand) -> Result<()> {
+    match command {
+        PricingCommand::Refresh => {
+            if cli.offline {
+                return Err(CcstatError::InvalidArgument(
+                    "'pricing refresh' fetches over the network and cannot be used with --offline"
+                        .to_string(),
+                ));
+            }
+            let pricing_fetcher = init_pricing_fetcher(cli).await?;
+            let models = pricing_fetcher.refresh().await?;
+            match PricingCache::default_path() {
+                Some(path) => println!(
+                    "Fetched pricing for {} models, cached at {}",
+                    models,
+                    path.display()
+                ),
+                None => println!(
+                    "Fetched pricing for {} models (no cache directory available)",
+                    models
+                ),
+            }
+            Ok(())
+        }
+        PricingCommand::Show { model } => {
+            let pricing_fetcher = init_pricing_fetcher(cli).await?;
+            let Some(lookup) = pricing_fetcher.lookup(model).await? else {
+                return Err(CcstatError::UnknownModel(ModelName::new(model)));
+            };
+            let pricing_file = pricing_fetcher
+                .user_pricing()
+                .and_then(|user_pricing| user_pricing.path());
+
+            if cli.json {
+                let output = serde_json::json!({
+                    "model": model,
+                    "alias": lookup.alias,
+                    "key": lookup.key,
+                    "match": lookup.match_kind.to_string(),
+                    "source": lookup.source.to_string(),
+                    "pricing_file": pricing_file,
+                    "discount_percent": lookup.discount_percent,
+                    "pricing": lookup.pricing,
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+                return Ok(());
+            }
+
+            println!("Model:     {model}");
+            if let Some(alias) = &lookup.alias {
+                println!("Alias of:  {alias}");
+            }
+            println!("Matched:   {} ({} match)", lookup.key, lookup.match_kind);
+            match (lookup.source, pricing_file) {
+                (PricingSource::UserFile, Some(path)) => {
+                    println!("Source:    {} ({})", lookup.source, path.display())
+                }
+                (source, _) => println!("Source:    {source}"),
+            }
+            if let Some(percent) = lookup.discount_percent {
+                println!("Discount:  {percent}%");
+            }
+            if let Some(date) = lookup.pricing.effective_from {
+                println!("Since:     {date}");
+            }
+            println!();
+            print_pricing_rates(&lookup.pricing);
+
+            let mut periods: Vec<_> = lookup.pricing.rate_periods.iter().collect();
+            periods.sort_by_key(|period| period.effective_from);
+            let ends = periods
+                .iter()
+                .skip(1)
+                .map(|period| Some(period.effective_from))
+                .chain([lookup.pricing.effective_from]);
+            for (period, until) in periods.iter().zip(ends) {
+                println!();
+                match until {
+                    Some(until) => println!("From {} until {}:", period.effective_from, until),
+                    None => println!("From {}:", period.effective_from),
+                }
+                print_pricing_rates(&period.rates);
+            }
+            Ok(())
+        }
+        PricingCommand::Resolve { model } => {
+            let pricing_fetcher = init_pricing_fetcher(cli).await?;
+            let resolution = pricing_fetcher.resolve(model).await?;
+
+            if cli.json {
+                let steps: Vec<_> = resolution
+                    .steps
+                    .iter()
+                    .map(|step| {
+                        serde_json::json!({
+                            "rule": step.rule,
+                            "name": step.name,
+                            "matched": step.matched,
+                        })
+                    })
+                    .collect();
+                let output = serde_json::json!({
+                    "model": model,
+                    "alias": resolution.alias,
+                    "steps": steps,
+                    "key": resolution.matched.as_ref().map(|matched| &matched.key),
+                    "match": resolution.matched.as_ref().map(|matched| matched.kind.to_string()),
+                    "exact": resolution.matched.as_ref().map(|matched| matched.kind.is_exact()),
+                    "source": resolution.source.map(|source| source.to_string()),
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+                return Ok(());
+            }
+
+            println!("Model:     {model}");
+            if let Some(alias) = &resolution.alias {
+                println!("Alias of:  {alias} (user pricing file)");
+            }
+            println!();
+            for (index, step) in resolution.steps.iter().enumerate() {
+                let outcome = match &step.matched {
+                    Some(key) if *key == step.name => "match".to_string(),
+                    Some(key) => format!("starts with {key}"),
+                    None => "no match".to_string(),
+                };
+                println!(
+                    "{:>3}. {:<24}{:<48}{outcome}",
+                    index + 1,
+                    step.rule,
+                    step.name
+                );
+            }
+            println!();
+            match (&resolution.matched, resolution.source) {
+                (Some(matched), Some(source)) => {
+                    println!("Matched:   {} ({} match)", matched.key, matched.kind);
+                    println!("Source:    {source}");
+                    if !matched.kind.is_exact() {
+                        println!("Note:      prefix matches are rejected with --strict-pricing");
+                    }
+                }
+                _ => println!("No pricing found"),
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Print a model's rates per million tokens
+fn print_pricing_rates(pricing: &ModelPricing) {
+    let rates = [
+        ("Input", pricing.input_cost_per_token),
+        ("Output", pricing.output_cost_per_token),
+        ("Reasoning", pricing.output_cost_per_reasoning_token),
+        ("Cache write", pricing.cache_creation_input_token_cost),
+        (
+            "Cache write (1h)",
+            pricing.cache_creation_input_token_cost_above_1hr,
+        ),
+        ("Cache read", pricing.cache_read_input_token_cost),
+        (
+            "Input >200k",
+            pricing.input_cost_per_token_above_200k_tokens,
+        ),
+        (
+            "Output >200k",
+            pricing.output_cost_per_token_above_200k_tokens,
+        ),
+        (
+            "Cache write >200k",
+            pricing.cache_creation_input_token_cost_above_200k_tokens,
+        ),
+        (
+            "Cache write (1h) >200k",
+            pricing.cache_creation_input_token_cost_above_1hr_above_200k_tokens,
+        ),
+        (
+            "Cache read >200k",
+            pricing.cache_read_input_token_cost_above_200k_tokens,
+        ),
+    ];
+    for (label, rate) in rates {
+        if let Some(rate) = rate {
+            println!("{label:<24}{} / 1M tokens", format_rate(rate * 1_000_000.0));
+        }
+    }
+    if let Some(rate) = pricing
+        .search_context_cost_per_query
+        .as_ref()
+        .and_then(|cost| cost.per_request())
+    {
+        println!("{:<24}{} / request", "Web search", format_rate(rate));
+    }
+}
+
+/// Format a USD amount with at least two and at most six decimals
+fn format_rate(amount: f64) -> String {
+    let formatted = format!("{amount:.6}");
+    let trimmed = formatted.trim_end_matches('0');
+    let decimals = trimmed.len() - trimmed.find('.').map_or(trimmed.len(), |dot| dot + 1);
+    format!("${trimmed}{}", "0".repeat(2usize.saturating_sub(decimals)))
+}
+
+/// Billing blocks report for any provider
+///
+/// Blocks default to the provider's own block length unless
+/// `--session-duration` is given.
+async fn handle_blocks_command(
+    cli: &Cli,
+    args: &BlocksArgs,
+    data_loader: Arc<dyn DynDataLoader>,
+) -> Result<()> {
     info!("Running billing blocks report");
 
     let sp = show_progress(cli);
-    let data_loader = Arc::new(init_data_loader(sp, cli.intern, cli.arena).await?);
-    let pricing_fetcher = Arc::new(PricingFetcher::new(false).await);
+    let session_duration = args
+        .session_duration
+        .unwrap_or(data_loader.default_block_hours());
+    let pricing_fetcher = init_pricing_fetcher(cli).await?;
+    let currency = display_currency(cli)?;
     let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
     let aggregator = Arc::new(create_aggregator_with_timezone(
         cost_calculator,
@@ -526,21 +1112,22 @@ async fn handle_blocks_command(cli: &Cli, args: &BlocksArgs) -> Result<()> {
                 active: args.active,
                 recent: args.recent,
                 token_limit: args.token_limit.clone(),
-                session_duration: args.session_duration,
+                session_duration,
             },
             cli.interval,
             cli.full_model_names,
         )
-        .with_max_cost(args.max_cost);
+        .with_max_cost(args.max_cost)
+        .with_currency(currency);
         monitor.run().await
     } else {
         let since_date = filter.since_date;
         let until_date = filter.until_date;
         let params = BillingBlockParams {
-            data_loader: &data_loader,
+            data_loader: data_loader.as_ref(),
             aggregator: &aggregator,
             cost_mode: cli.mode,
-            session_duration_hours: args.session_duration,
+            session_duration_hours: session_duration,
             project: cli.project.as_deref(),
             since_date,
             until_date,
@@ -550,7 +1137,7 @@ async fn handle_blocks_command(cli: &Cli, args: &BlocksArgs) -> Result<()> {
             approx_max_tokens: APPROX_MAX_TOKENS_PER_BLOCK,
         };
         let blocks = create_and_filter_billing_blocks(params).await?;
-        let formatter = get_formatter(cli.json, cli.full_model_names);
+        let formatter = get_formatter(cli.json, cli.full_model_names, currency);
         println!(
             "{}",
             formatter.format_blocks(&blocks, &aggregator.timezone_config().tz)
@@ -0,0 +1,485 @@
+//! MCP report handler
+//!
+//! Connects the `ccstat-mcp` server to the aggregation pipeline. Each tool
+//! call loads the requested provider's entries, aggregates them the same way
+//! the CLI does, and returns the output of [`JsonFormatter`], so MCP clients
+//! see exactly what `ccstat <report> --json` would print. Resources are
+//! served the same way from Claude data, and [`watch_resources`] tells
+//! subscribed clients when the underlying JSONL files change.
+
+use crate::aggregation::{
+    Aggregator, BillingBlockParams, Totals, create_and_filter_billing_blocks, filter_monthly_data,
+};
+use crate::all_providers::{
+    aggregate_daily_by_provider, aggregate_monthly_by_provider, aggregate_sessions_by_provider,
+    aggregate_weekly_by_provider, load_providers, provider_entries,
+};
+use crate::cli::{
+    BlocksArgs, DailyArgs, Provider, Report, SessionArgs, WeeklyArgs, parse_date_filter,
+    parse_weekday, validate_provider_report,
+};
+use crate::cost_calculator::CostCalculator;
+use crate::data_loader::DataLoader;
+use crate::error::{CcstatError, Result};
+use crate::filters::{MonthFilter, UsageFilter};
+use crate::live_monitor::watch_file_changes;
+use crate::output::{JsonFormatter, OutputFormatter};
+use crate::provider::DynDataLoader;
+use crate::providers::registry;
+use crate::registry::ProviderInfo;
+use crate::timezone::TimezoneConfig;
+use crate::types::{CostMode, UsageEntry};
+use async_trait::async_trait;
+use ccstat_mcp::{McpServer, ReportHandler, ReportKind, ReportRequest, Resource};
+use chrono::Datelike;
+use futures::{Stream, StreamExt};
+use notify::RecommendedWatcher;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Approximate maximum tokens for a 5-hour billing block
+const APPROX_MAX_TOKENS_PER_BLOCK: f64 = 10_000_000.0;
+
+/// How long to let JSONL writes settle before announcing resource updates
+const RESOURCE_UPDATE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Runs report tool calls against local usage data
+///
+/// The cost calculator (and with it the pricing cache) is shared across
+/// calls, so pricing data is only fetched once per server process.
+pub struct McpReportHandler {
+    cost_calculator: Arc<CostCalculator>,
+}
+
+impl McpReportHandler {
+    /// Create a handler that prices entries with the given calculator
+    pub fn new(cost_calculator: Arc<CostCalculator>) -> Self {
+        Self { cost_calculator }
+    }
+}
+
+#[async_trait]
+impl ReportHandler for McpReportHandler {
+    fn providers(&self) -> Vec<String> {
+        registry()
+            .iter()
+            .map(|info| info.name.to_string())
+            .chain(std::iter::once(Provider::All.to_string()))
+            .collect()
+    }
+
+    async fn run_report(&self, request: ReportRequest) -> Result<String> {
+        let provider = parse_provider(&request.provider)?;
+        validate_provider_report(provider, &cli_report(&request))?;
+        info!(
+            "MCP tool call: {} {}",
+            request.provider,
+            request.report.tool_name()
+        );
+
+        let tz_config = TimezoneConfig::from_cli(request.timezone.as_deref(), request.utc)?;
+        let aggregator = Aggregator::new(self.cost_calculator.clone(), tz_config);
+
+        match provider.info() {
+            Some(info) => run_with_loader(info, &request, &aggregator).await,
+            None => render_all_report(&request, &aggregator).await,
+        }
+    }
+
+    async fn read_resource(&self, resource: Resource) -> Result<String> {
+        match resource {
+            Resource::ActiveBlocks => {
+                let args = json!({ "active": true });
+                let request = ReportRequest::from_arguments(
+                    ReportKind::Blocks,
+                    Some(&args),
+                    &self.providers(),
+                )?;
+                self.run_report(request).await
+            }
+            Resource::DailyToday => {
+                let today = chrono::Local::now().date_naive().to_string();
+                let args = json!({ "since": today, "until": today });
+                let request = ReportRequest::from_arguments(
+                    ReportKind::Daily,
+                    Some(&args),
+                    &self.providers(),
+                )?;
+                self.run_report(request).await
+            }
+            Resource::Session(session_id) => {
+                let aggregator =
+                    Aggregator::new(self.cost_calculator.clone(), TimezoneConfig::default());
+                let data_loader = DataLoader::new().await?;
+                let entries = data_loader.load_usage_entries_parallel().filter(|entry| {
+                    let keep = match entry {
+                        Ok(entry) => entry.session_id.as_str() == session_id,
+                        Err(_) => true,
+                    };
+                    futures::future::ready(keep)
+                });
+                let session_data = aggregator
+                    .aggregate_sessions(Box::pin(entries), CostMode::Auto)
+                    .await?;
+                if session_data.is_empty() {
+                    return Err(CcstatError::InvalidArgument(format!(
+                        "Session '{session_id}' not found"
+                    )));
+                }
+                let totals = Totals::from_sessions(&session_data);
+                Ok(JsonFormatter::new().format_sessions(
+                    &session_data,
+                    &totals,
+                    &aggregator.timezone_config().tz,
+                ))
+            }
+        }
+    }
+}
+
+/// Announce resource updates whenever Claude JSONL files change
+///
+/// Every change refreshes the active block and today's usage; changed
+/// session files also refresh their `ccstat://session/{id}` resource. The
+/// returned watcher must be kept alive for as long as updates are wanted.
+pub fn watch_resources(server: McpServer, dirs: &[PathBuf]) -> Result<RecommendedWatcher> {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Vec<PathBuf>>();
+    let watcher = watch_file_changes(dirs, &["jsonl"], move |changed| {
+        let _ = tx.send(changed);
+    })?;
+
+    tokio::spawn(async move {
+        while let Some(mut changed) = rx.recv().await {
+            // Let writes settle and coalesce bursts of events into one update
+            tokio::time::sleep(RESOURCE_UPDATE_DEBOUNCE).await;
+            while let Ok(more) = rx.try_recv() {
+                changed.extend(more);
+            }
+            for uri in updated_resource_uris(&changed) {
+                debug!("Resource updated: {}", uri);
+                server.notify_resource_updated(&uri);
+            }
+        }
+    });
+
+    Ok(watcher)
+}
+
+/// Resource URIs affected by changes to the given JSONL files
+fn updated_resource_uris(changed: &[PathBuf]) -> BTreeSet<String> {
+    let mut uris = BTreeSet::from([Resource::ActiveBlocks.uri(), Resource::DailyToday.uri()]);
+    for path in changed {
+        // Claude names each session's JSONL file after the session id
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            uris.insert(Resource::Session(stem.to_string()).uri());
+        }
+    }
+    uris
+}
+
+fn parse_provider(name: &str) -> Result<Provider> {
+    Provider::from_name(name)
+        .ok_or_else(|| CcstatError::InvalidArgument(format!("Unknown provider '{name}'")))
+}
+
+/// Map a tool request onto the CLI report so the provider matrix is shared
+fn cli_report(request: &ReportRequest) -> Report {
+    match request.report {
+        ReportKind::Daily => Report::Daily(DailyArgs {
+            instances: false,
+            detailed: false,
+        }),
+        ReportKind::Monthly => Report::Monthly,
+        ReportKind::Weekly => Report::Weekly(WeeklyArgs {
+            start_of_week: request
+                .start_of_week
+                .clone()
+                .unwrap_or_else(|| "sunday".to_string()),
+        }),
+        ReportKind::Session => Report::Session(SessionArgs {}),
+        ReportKind::Blocks => Report::Blocks(BlocksArgs {
+            active: request.active,
+            recent: request.recent,
+            token_limit: None,
+            session_duration: request.session_duration,
+            max_cost: None,
+        }),
+    }
+}
+
+async fn run_with_loader(
+    info: &ProviderInfo,
+    request: &ReportRequest,
+    aggregator: &Aggregator,
+) -> Result<String> {
+    let data_loader = info.load().await?;
+    if request.report == ReportKind::Blocks {
+        return render_blocks(request, data_loader.as_ref(), aggregator).await;
+    }
+    let filter = build_usage_filter(request, aggregator)?;
+    let data_loader = data_loader.with_date_range(filter.since_date, filter.until_date);
+    render_report(request, aggregator, data_loader.load_entries()).await
+}
+
+fn build_usage_filter(request: &ReportRequest, aggregator: &Aggregator) -> Result<UsageFilter> {
+    let mut filter = UsageFilter::new();
+    if let Some(since_str) = &request.since {
+        filter = filter.with_since(parse_date_filter(since_str)?);
+    }
+    if let Some(until_str) = &request.until {
+        filter = filter.with_until(parse_date_filter(until_str)?);
+    }
+    if let Some(project_name) = &request.project {
+        filter = filter.with_project(project_name.clone());
+    }
+    filter = filter.with_timezone(aggregator.timezone_config().tz);
+    Ok(filter)
+}
+
+fn build_month_filter(request: &ReportRequest) -> Result<MonthFilter> {
+    let mut month_filter = MonthFilter::new();
+    if let Some(since_str) = &request.since {
+        let since_date = parse_date_filter(since_str)?;
+        month_filter = month_filter.with_since(since_date.year(), since_date.month());
+    }
+    if let Some(until_str) = &request.until {
+        let until_date = parse_date_filter(until_str)?;
+        month_filter = month_filter.with_until(until_date.year(), until_date.month());
+    }
+    Ok(month_filter)
+}
+
+/// Aggregate an entry stream into the requested (non-blocks) report
+async fn render_report(
+    request: &ReportRequest,
+    aggregator: &Aggregator,
+    entries: impl Stream<Item = Result<UsageEntry>> + Send,
+) -> Result<String> {
+    let filter = build_usage_filter(request, aggregator)?;
+    let filtered_entries = filter.filter_stream(entries).await;
+    let formatter = JsonFormatter::new();
+
+    match request.report {
+        ReportKind::Daily => {
+            let daily_data = aggregator
+                .aggregate_daily(filtered_entries, request.mode)
+                .await?;
+            let totals = Totals::from_daily(&daily_data);
+            Ok(formatter.format_daily(&daily_data, &totals))
+        }
+        ReportKind::Monthly => {
+            let daily_data = aggregator
+                .aggregate_daily(filtered_entries, request.mode)
+                .await?;
+            let mut monthly_data = Aggregator::aggregate_monthly(&daily_data);
+            filter_monthly_data(&mut monthly_data, &build_month_filter(request)?);
+            let totals = Totals::from_monthly(&monthly_data);
+            Ok(formatter.format_monthly(&monthly_data, &totals))
+        }
+        ReportKind::Weekly => {
+            let start_of_week =
+                parse_weekday(request.start_of_week.as_deref().unwrap_or("sunday"))?;
+            let daily_data = aggregator
+                .aggregate_daily(filtered_entries, request.mode)
+                .await?;
+            let weekly_data = Aggregator::aggregate_weekly(&daily_data, start_of_week);
+            let totals = Totals::from_weekly(&weekly_data);
+            Ok(formatter.format_weekly(&weekly_data, &totals))
+        }
+        ReportKind::Session => {
+            let session_data = aggregator
+                .aggregate_sessions(filtered_entries, request.mode)
+                .await?;
+            let totals = Totals::from_sessions(&session_data);
+            Ok(formatter.format_sessions(&session_data, &totals, &aggregator.timezone_config().tz))
+        }
+        ReportKind::Blocks => Err(CcstatError::Config(format!(
+            "Report type not supported for {} provider",
+            request.provider
+        ))),
+    }
+}
+
+/// Aggregate every provider into the requested (non-blocks) combined report
+async fn render_all_report(request: &ReportRequest, aggregator: &Aggregator) -> Result<String> {
+    let filter = build_usage_filter(request, aggregator)?;
+    let loaders = load_providers(&filter).await;
+    let entries = provider_entries(&loaders, &filter).await;
+    let formatter = JsonFormatter::new();
+
+    match request.report {
+        ReportKind::Daily => {
+            let daily_data = aggregate_daily_by_provider(aggregator, entries, request.mode).await;
+            let totals = Totals::from_providers(&daily_data);
+            Ok(formatter.format_daily_by_provider(&daily_data, &totals))
+        }
+        ReportKind::Monthly => {
+            let daily_data = aggregate_daily_by_provider(aggregator, entries, request.mode).await;
+            let monthly_data =
+                aggregate_monthly_by_provider(&daily_data, &build_month_filter(request)?);
+            let totals = Totals::from_providers(&monthly_data);
+            Ok(formatter.format_monthly_by_provider(&monthly_data, &totals))
+        }
+        ReportKind::Weekly => {
+            let start_of_week =
+                parse_weekday(request.start_of_week.as_deref().unwrap_or("sunday"))?;
+            let daily_data = aggregate_daily_by_provider(aggregator, entries, request.mode).await;
+            let weekly_data = aggregate_weekly_by_provider(&daily_data, start_of_week);
+            let totals = Totals::from_providers(&weekly_data);
+            Ok(formatter.format_weekly_by_provider(&weekly_data, &totals))
+        }
+        ReportKind::Session => {
+            let session_data =
+                aggregate_sessions_by_provider(aggregator, entries, request.mode).await;
+            let totals = Totals::from_providers(&session_data);
+            Ok(formatter.format_sessions_by_provider(
+                &session_data,
+                &totals,
+                &aggregator.timezone_config().tz,
+            ))
+        }
+        ReportKind::Blocks => Err(CcstatError::Config(
+            "Report type not supported for all providers".to_string(),
+        )),
+    }
+}
+
+async fn render_blocks(
+    request: &ReportRequest,
+    data_loader: &dyn DynDataLoader,
+    aggregator: &Aggregator,
+) -> Result<String> {
+    let since_date = request
+        .since
+        .as_deref()
+        .map(parse_date_filter)
+        .transpose()?;
+    let until_date = request
+        .until
+        .as_deref()
+        .map(parse_date_filter)
+        .transpose()?;
+    let params = BillingBlockParams {
+        data_loader,
+        aggregator,
+        cost_mode: request.mode,
+        session_duration_hours: request
+            .session_duration
+            .unwrap_or(data_loader.default_block_hours()),
+        project: request.project.as_deref(),
+        since_date,
+        until_date,
+        active: request.active,
+        recent: request.recent,
+        token_limit: None,
+        approx_max_tokens: APPROX_MAX_TOKENS_PER_BLOCK,
+    };
+    let blocks = create_and_filter_billing_blocks(params).await?;
+    Ok(JsonFormatter::new().format_blocks(&blocks, &aggregator.timezone_config().tz))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pricing_fetcher::PricingFetcher;
+    use crate::test_utils::{ENV_MUTEX, EnvVarGuard};
+    use serde_json::{Value, json};
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_pi_fixture(dir: &TempDir) {
+        let project_dir = dir.path().join("sessions").join("demo");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        let mut file = std::fs::File::create(project_dir.join("sess1.jsonl")).unwrap();
+        for day in ["2025-01-10", "2025-01-11", "2025-02-01"] {
+            writeln!(
+                file,
+                r#"{{"timestamp":"{day}T12:00:00Z","message":{{"role":"assistant","model":"claude-opus-4","usage":{{"input":100,"output":50,"cacheRead":0,"cacheWrite":0,"cost":{{"total":0.5}}}}}}}}"#
+            )
+            .unwrap();
+        }
+    }
+
+    async fn handler() -> McpReportHandler {
+        let pricing_fetcher = Arc::new(PricingFetcher::new(true).await);
+        McpReportHandler::new(Arc::new(CostCalculator::new(pricing_fetcher)))
+    }
+
+    async fn run(args: Value, report: ReportKind) -> Result<Value> {
+        let handler = handler().await;
+        let request = ReportRequest::from_arguments(report, Some(&args), &handler.providers())?;
+        let output = handler.run_report(request).await?;
+        Ok(serde_json::from_str(&output).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_daily_tool_filters_provider_data() {
+        let _lock = ENV_MUTEX.lock().await;
+        let dir = TempDir::new().unwrap();
+        write_pi_fixture(&dir);
+        let mut env_guard = EnvVarGuard::new();
+        env_guard.set("PI_AGENT_DIR", dir.path().to_str().unwrap());
+
+        let output = run(
+            json!({"provider": "pi", "since": "2025-01-01", "until": "2025-01-31", "utc": true, "mode": "display"}),
+            ReportKind::Daily,
+        )
+        .await
+        .unwrap();
+        let days = output["daily"].as_array().unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(output["totals"]["total_cost"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_blocks_tool_for_non_claude_provider() {
+        let _lock = ENV_MUTEX.lock().await;
+        let dir = TempDir::new().unwrap();
+        write_pi_fixture(&dir);
+        let mut env_guard = EnvVarGuard::new();
+        env_guard.set("PI_AGENT_DIR", dir.path().to_str().unwrap());
+
+        let output = run(
+            json!({"provider": "pi", "until": "2025-01-31", "utc": true, "mode": "display"}),
+            ReportKind::Blocks,
+        )
+        .await
+        .unwrap();
+        let blocks: Vec<&Value> = output["blocks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|b| b["is_gap"] == false)
+            .collect();
+        assert_eq!(blocks.len(), 2);
+        assert!(blocks.iter().all(|b| b["session_count"] == 1));
+    }
+
+    #[test]
+    fn test_updated_resource_uris() {
+        let uris = updated_resource_uris(&[
+            PathBuf::from("/data/projects/demo/abc-123.jsonl"),
+            PathBuf::from("/data/projects/demo/abc-123.jsonl"),
+        ]);
+        assert_eq!(
+            uris.into_iter().collect::<Vec<_>>(),
+            [
+                "ccstat://blocks/active",
+                "ccstat://daily/today",
+                "ccstat://session/abc-123"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_provider_report() {
+        let err = run(json!({"provider": "all"}), ReportKind::Blocks)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not supported"));
+    }
+}
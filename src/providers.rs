@@ -0,0 +1,52 @@
+//! Built-in provider registry
+//!
+//! Every provider crate bundled with ccstat registers itself here. The CLI
+//! subcommands, the report support matrix, `ccstat all`, and the MCP
+//! `provider` argument are all derived from this registry, so adding a
+//! provider only takes a new line in [`registry`].
+
+use crate::registry::ProviderRegistry;
+use once_cell::sync::Lazy;
+
+static REGISTRY: Lazy<ProviderRegistry> = Lazy::new(|| {
+    let mut registry = ProviderRegistry::new();
+    registry.register(ccstat_provider_claude::provider_info());
+    registry.register(ccstat_provider_codex::provider_info());
+    registry.register(ccstat_provider_opencode::provider_info());
+    registry.register(ccstat_provider_amp::provider_info());
+    registry.register(ccstat_provider_pi::provider_info());
+    registry.register(ccstat_provider_gemini::provider_info());
+    registry.register(ccstat_provider_qwen::provider_info());
+    registry.register(ccstat_provider_crush::provider_info());
+    registry.register(ccstat_provider_cline::provider_info());
+    registry.register(ccstat_provider_roo::provider_info());
+    registry
+});
+
+/// Registry of the built-in providers, in display order
+pub fn registry() -> &'static ProviderRegistry {
+    &REGISTRY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::ReportType;
+
+    #[test]
+    fn test_builtin_registry() {
+        let registry = registry();
+        assert_eq!(registry.len(), 10);
+        assert_eq!(registry.iter().next().unwrap().name, "claude");
+        assert_eq!(registry.get("claude-code").unwrap().name, "claude");
+
+        // Every provider supports the core reports
+        for info in registry.iter() {
+            for report in [ReportType::Daily, ReportType::Monthly, ReportType::Session] {
+                assert!(info.supports(report), "{} lacks {}", info.name, report);
+            }
+        }
+        assert!(registry.get("codex").unwrap().supports(ReportType::Limits));
+        assert_eq!(registry.get("gemini").unwrap().default_block_hours, 24.0);
+    }
+}
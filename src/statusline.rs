@@ -5,15 +5,20 @@
 //! and outputs a formatted status line with current model, session cost,
 //! daily cost percentage, and remaining time in the billing block.
 
+use crate::cost::Cost;
 use crate::cost_calculator::CostCalculator;
+use crate::currency::Currency;
 use crate::data_loader::DataLoader;
 use crate::error::Result;
+use crate::pricing_cache::PricingCache;
 use crate::pricing_fetcher::PricingFetcher;
 use crate::types::{CostMode, SessionId};
+use crate::user_pricing::UserPricing;
 use chrono::{Datelike, Duration, Local, TimeZone, Timelike, Utc};
 use colored::*;
 use futures::stream::StreamExt;
 use serde::Deserialize;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::io::{self, AsyncReadExt};
 use tokio::process::Command;
@@ -155,6 +160,7 @@ pub struct StatuslineHandler {
     show_date: bool,
     show_git: bool,
     colors: ColorConfig,
+    currency: Currency,
 }
 
 impl StatuslineHandler {
@@ -165,12 +171,24 @@ impl StatuslineHandler {
         show_date: bool,
         show_git: bool,
     ) -> Result<Self> {
+        let data_loader = DataLoader::new().await?;
+        Ok(Self::with_data_loader(data_loader, monthly_fee, no_color, show_date, show_git).await)
+    }
+
+    /// Create a statusline handler reading from an existing data loader
+    pub async fn with_data_loader(
+        data_loader: DataLoader,
+        monthly_fee: f64,
+        no_color: bool,
+        show_date: bool,
+        show_git: bool,
+    ) -> Self {
         // Disable progress and quiet mode for statusline
-        let data_loader = Arc::new(DataLoader::new().await?.with_progress(false));
-        let pricing_fetcher = Arc::new(PricingFetcher::new(true).await); // offline mode
+        let data_loader = Arc::new(data_loader.with_progress(false));
+        let pricing_fetcher = Arc::new(offline_pricing_fetcher().await);
         let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
 
-        Ok(Self {
+        Self {
             data_loader,
             cost_calculator,
             monthly_fee,
@@ -178,7 +196,23 @@ impl StatuslineHandler {
             show_date,
             show_git,
             colors: ColorConfig::new(),
-        })
+            currency: Currency::usd(),
+        }
+    }
+
+    /// Price usage with a user pricing file
+    pub async fn with_user_pricing(mut self, user_pricing: UserPricing) -> Self {
+        let pricing_fetcher = offline_pricing_fetcher()
+            .await
+            .with_user_pricing(user_pricing);
+        self.cost_calculator = Arc::new(CostCalculator::new(Arc::new(pricing_fetcher)));
+        self
+    }
+
+    /// Display costs in `currency`
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
     }
 
     /// Read and parse JSON input from stdin
@@ -225,8 +259,8 @@ impl StatuslineHandler {
         // by only loading JSONL files that have been modified today or later.
 
         let today = Local::now().date_naive();
-        let mut session_cost = 0.0;
-        let mut daily_cost = 0.0;
+        let mut session_cost = Cost::ZERO;
+        let mut daily_cost = Cost::ZERO;
         let mut session_start_time: Option<chrono::DateTime<Utc>> = None;
 
         // Only load entries from files modified since start of today (in UTC)
@@ -259,6 +293,7 @@ impl StatuslineHandler {
                     .calculate_with_mode(
                         &entry.tokens,
                         &entry.model,
+                        *entry.timestamp.inner(),
                         entry.total_cost,
                         CostMode::Auto,
                     )
@@ -266,7 +301,7 @@ impl StatuslineHandler {
                 {
                     Ok(c) => c,
                     Err(crate::error::CcstatError::UnknownModel(_)) => {
-                        entry.total_cost.unwrap_or(0.0)
+                        entry.total_cost.map(Cost::from_usd).unwrap_or_default()
                     }
                     Err(e) => return Err(e),
                 };
@@ -293,7 +328,7 @@ impl StatuslineHandler {
         let days_in_month = Self::days_in_month(now.year(), now.month());
         let daily_budget = self.monthly_fee / days_in_month as f64;
         let daily_percentage = if daily_budget > 0.0 {
-            (daily_cost / daily_budget) * 100.0
+            (daily_cost.to_usd() / daily_budget) * 100.0
         } else {
             0.0
         };
@@ -397,14 +432,14 @@ impl StatuslineHandler {
     }
 
     /// Format cost with color
-    fn format_cost(&self, cost: f64) -> String {
-        let text = if cost > 0.0 {
-            format!("${:.2}", cost)
+    fn format_cost(&self, cost: Cost) -> String {
+        let text = if cost > Cost::ZERO {
+            self.currency.format(cost.to_usd())
         } else {
             "no session".to_string()
         };
 
-        let color = if cost > 0.0 {
+        let color = if cost > Cost::ZERO {
             self.colors.cost
         } else {
             self.colors.muted
@@ -538,7 +573,18 @@ impl StatuslineHandler {
 }
 
 /// Run the statusline handler
-pub async fn run(monthly_fee: f64, no_color: bool, show_date: bool, show_git: bool) -> Result<()> {
+///
+/// Usage data is read from `data_roots` when given, otherwise from the
+/// default Claude directories.
+pub async fn run(
+    monthly_fee: f64,
+    no_color: bool,
+    show_date: bool,
+    show_git: bool,
+    data_roots: Option<Vec<PathBuf>>,
+    pricing_file: Option<PathBuf>,
+    currency: Currency,
+) -> Result<()> {
     // Disable colors if requested
     if no_color {
         colored::control::set_override(false);
@@ -548,7 +594,29 @@ pub async fn run(monthly_fee: f64, no_color: bool, show_date: bool, show_git: bo
     let input = StatuslineHandler::read_input().await?;
 
     // Create handler
-    let handler = StatuslineHandler::new(monthly_fee, no_color, show_date, show_git).await?;
+    let handler = match data_roots {
+        Some(roots) => {
+            StatuslineHandler::with_data_loader(
+                DataLoader::from_roots(roots),
+                monthly_fee,
+                no_color,
+                show_date,
+                show_git,
+            )
+            .await
+        }
+        None => StatuslineHandler::new(monthly_fee, no_color, show_date, show_git).await?,
+    };
+    // An unreadable default pricing file must not break the statusline
+    let user_pricing = match pricing_file {
+        Some(path) => Some(UserPricing::load(&path)?),
+        None => UserPricing::load_default().unwrap_or_default(),
+    };
+    let handler = match user_pricing {
+        Some(user_pricing) => handler.with_user_pricing(user_pricing).await,
+        None => handler,
+    }
+    .with_currency(currency);
 
     // Generate and print statusline
     let output = handler.generate(input).await?;
@@ -557,6 +625,15 @@ pub async fn run(monthly_fee: f64, no_color: bool, show_date: bool, show_git: bo
     Ok(())
 }
 
+/// Pricing fetcher that never fetches, reusing cached pricing from earlier runs
+async fn offline_pricing_fetcher() -> PricingFetcher {
+    let pricing_fetcher = PricingFetcher::new(true).await;
+    match PricingCache::default_path() {
+        Some(path) => pricing_fetcher.with_disk_cache(PricingCache::new(path)),
+        None => pricing_fetcher,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -814,20 +891,24 @@ mod tests {
         };
 
         // Test with positive cost
-        let cost_text = handler.format_cost(12.34);
+        let cost_text = handler.format_cost(Cost::from_usd(12.34));
         assert_eq!(cost_text, "$12.34");
 
         // Test with zero cost
-        let zero_text = handler.format_cost(0.0);
+        let zero_text = handler.format_cost(Cost::ZERO);
         assert_eq!(zero_text, "no session");
 
         // Test with small cost
-        let small_text = handler.format_cost(0.01);
+        let small_text = handler.format_cost(Cost::from_usd(0.01));
         assert_eq!(small_text, "$0.01");
 
         // Test with large cost
-        let large_text = handler.format_cost(999.99);
+        let large_text = handler.format_cost(Cost::from_usd(999.99));
         assert_eq!(large_text, "$999.99");
+
+        // Test with a display currency
+        let handler = handler.with_currency(Currency::new("EUR", 0.5));
+        assert_eq!(handler.format_cost(Cost::from_usd(12.34)), "€6.17");
     }
 
     #[tokio::test]
@@ -6,8 +6,8 @@
 mod common;
 
 use ccstat::{
-    aggregation::Aggregator, cli::parse_date_filter, cost_calculator::CostCalculator,
-    data_loader::DataLoader, filters::UsageFilter, output::get_formatter,
+    Cost, aggregation::Aggregator, cli::parse_date_filter, cost_calculator::CostCalculator,
+    currency::Currency, data_loader::DataLoader, filters::UsageFilter, output::get_formatter,
     pricing_fetcher::PricingFetcher, timezone::TimezoneConfig, types::CostMode,
 };
 use chrono::{Datelike, NaiveDate};
@@ -356,12 +356,12 @@ async fn test_cost_modes() {
                     // In display mode, cost should be from the JSONL if available
                     if *day.date.inner() == NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() {
                         // This day has costUSD in the test data
-                        assert!(day.total_cost > 0.0);
+                        assert!(day.total_cost > Cost::ZERO);
                     }
                 }
                 CostMode::Calculate | CostMode::Auto => {
                     // These modes should calculate costs
-                    assert!(day.total_cost >= 0.0);
+                    assert!(day.total_cost >= Cost::ZERO);
                 }
             }
         }
@@ -428,14 +428,14 @@ async fn test_output_formatters() {
         .unwrap();
 
     // Test table formatter
-    let table_formatter = get_formatter(false, false);
+    let table_formatter = get_formatter(false, false, Currency::usd());
     let totals = ccstat::aggregation::Totals::from_daily(&daily_data);
     let table_output = table_formatter.format_daily(&daily_data, &totals);
     assert!(!table_output.is_empty());
     assert!(table_output.contains("Date")); // Table should have headers
 
     // Test JSON formatter
-    let json_formatter = get_formatter(true, false);
+    let json_formatter = get_formatter(true, false, Currency::usd());
     let json_output = json_formatter.format_daily(&daily_data, &totals);
     assert!(!json_output.is_empty());
     assert!(json_output.contains("\"date\"")); // JSON should have date field
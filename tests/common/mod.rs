@@ -7,7 +7,7 @@ use ccstat::{
     cost_calculator::CostCalculator,
     data_loader::DataLoader,
     pricing_fetcher::PricingFetcher,
-    types::{ISOTimestamp, ModelName, SessionId, TokenCounts, UsageEntry},
+    types::{ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry},
 };
 use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
 use once_cell::sync::Lazy;
@@ -189,6 +189,7 @@ impl UsageEntryBuilder {
             total_cost: self.total_cost,
             project: self.project,
             instance_id: self.instance_id,
+            provider: ProviderId::new("claude"),
         }
     }
 
@@ -207,6 +208,8 @@ impl UsageEntryBuilder {
                     output_tokens: self.output_tokens,
                     cache_creation_input_tokens: self.cache_creation_tokens,
                     cache_read_input_tokens: self.cache_read_tokens,
+                    cache_creation: None,
+                    server_tool_use: None,
                 },
                 id: None,
                 content: None,
@@ -8,7 +8,9 @@ mod common;
 use ccstat::{
     aggregation::Aggregator,
     cli::parse_date_filter,
+    cost::Cost,
     cost_calculator::CostCalculator,
+    currency::Currency,
     data_loader::DataLoader,
     error::CcstatError,
     filters::UsageFilter,
@@ -201,17 +203,17 @@ async fn test_cost_calculation_workflow() {
             match mode {
                 CostMode::Display => {
                     // Should use costUSD from the data
-                    assert!(day.total_cost > 0.0);
+                    assert!(day.total_cost > Cost::ZERO);
                 }
                 CostMode::Calculate | CostMode::Auto => {
                     // Should calculate based on token counts
-                    assert!(day.total_cost >= 0.0);
+                    assert!(day.total_cost >= Cost::ZERO);
                 }
             }
         }
 
         // Test that costs increase over the month (due to increasing usage)
-        let first_week_cost: f64 = daily_data
+        let first_week_cost: Cost = daily_data
             .iter()
             .filter(|d| {
                 let date = d.date.inner();
@@ -221,7 +223,7 @@ async fn test_cost_calculation_workflow() {
             .map(|d| d.total_cost)
             .sum();
 
-        let last_week_cost: f64 = daily_data
+        let last_week_cost: Cost = daily_data
             .iter()
             .filter(|d| {
                 let date = d.date.inner();
@@ -309,7 +311,7 @@ async fn test_output_format_workflow() {
     let totals = ccstat::aggregation::Totals::from_daily(&daily_data);
 
     // Test table formatter
-    let table_formatter = get_formatter(false, false);
+    let table_formatter = get_formatter(false, false, Currency::usd());
     let table_output = table_formatter.format_daily(&daily_data, &totals);
 
     // Verify table output contains expected elements
@@ -320,7 +322,7 @@ async fn test_output_format_workflow() {
     assert!(table_output.contains("2024-01")); // Should have January dates
 
     // Test JSON formatter
-    let json_formatter = get_formatter(true, false);
+    let json_formatter = get_formatter(true, false, Currency::usd());
     let json_output = json_formatter.format_daily(&daily_data, &totals);
 
     // Verify JSON is valid and contains expected fields
@@ -332,7 +334,7 @@ async fn test_output_format_workflow() {
     assert!(parsed["totals"]["total_cost"].is_number());
 
     // Test with full model names
-    let full_name_formatter = get_formatter(false, true);
+    let full_name_formatter = get_formatter(false, true, Currency::usd());
     let full_name_output = full_name_formatter.format_daily(&daily_data, &totals);
 
     // Should contain full model names
@@ -378,7 +380,7 @@ async fn test_instance_grouping_workflow() {
 
     // Verify totals calculation
     let totals = ccstat::aggregation::Totals::from_daily_instances(&instance_data);
-    assert!(totals.total_cost > 0.0);
+    assert!(totals.total_cost > Cost::ZERO);
     assert!(totals.tokens.input_tokens > 0);
 }
 
@@ -1,12 +1,13 @@
 //! Integration tests for ccstat
 
 use ccstat::{
+    Cost,
     aggregation::Aggregator,
     cost_calculator::CostCalculator,
     filters::{MonthFilter, UsageFilter},
     pricing_fetcher::PricingFetcher,
     timezone::TimezoneConfig,
-    types::{CostMode, ISOTimestamp, ModelName, SessionId, TokenCounts, UsageEntry},
+    types::{CostMode, ISOTimestamp, ModelName, ProviderId, SessionId, TokenCounts, UsageEntry},
 };
 use chrono::{DateTime, NaiveDate, Utc};
 use futures::{StreamExt, stream};
@@ -30,6 +31,7 @@ fn create_test_entry(
         total_cost: None,
         project: None,
         instance_id: None,
+        provider: ProviderId::new("claude"),
     }
 }
 
@@ -110,16 +112,18 @@ async fn test_token_limit_warnings() {
             start_time: chrono::Utc::now() - chrono::Duration::hours(4),
             end_time: chrono::Utc::now() - chrono::Duration::hours(3),
             tokens: TokenCounts::new(1_000_000, 500_000, 100_000, 50_000),
-            total_cost: 25.0,
+            total_cost: Cost::from_usd(25.0),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         },
         SessionUsage {
             session_id: SessionId::new("s2"),
             start_time: chrono::Utc::now() - chrono::Duration::hours(2),
             end_time: chrono::Utc::now() - chrono::Duration::hours(1),
             tokens: TokenCounts::new(7_000_000, 2_000_000, 200_000, 100_000),
-            total_cost: 150.0,
+            total_cost: Cost::from_usd(150.0),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         },
     ];
 
@@ -160,6 +164,7 @@ async fn test_cost_calculation_modes() {
             total_cost: Some(0.05), // Pre-calculated cost
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
         UsageEntry {
             session_id: SessionId::new("s2"),
@@ -169,6 +174,7 @@ async fn test_cost_calculation_modes() {
             total_cost: None, // No pre-calculated cost
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
     ];
 
@@ -199,6 +205,7 @@ async fn test_cost_calculation_modes() {
         total_cost: Some(0.05), // This one has pre-calculated cost
         project: None,
         instance_id: None,
+        provider: ProviderId::new("claude"),
     }];
     let entries_stream = stream::iter(display_entries.into_iter().map(Ok));
     let daily_data_display = aggregator
@@ -212,6 +219,61 @@ async fn test_cost_calculation_modes() {
     assert!(!daily_data_display.is_empty());
 }
 
+#[tokio::test]
+async fn test_report_totals_agree() {
+    use ccstat::aggregation::Totals;
+
+    // Costs that do not add up exactly as floats, spread over two months,
+    // with sessions crossing day boundaries
+    let entries: Vec<UsageEntry> = (0..3000u32)
+        .map(|i| {
+            let mut entry = create_test_entry(
+                &format!("s{}", i % 37),
+                &format!(
+                    "2024-{:02}-{:02}T{:02}:00:00Z",
+                    1 + i % 2,
+                    1 + i % 28,
+                    i % 24
+                ),
+                100,
+                50,
+            );
+            entry.total_cost = Some(0.1 + f64::from(i) * 0.000_013_7);
+            entry
+        })
+        .collect();
+    let expected: Cost = entries
+        .iter()
+        .map(|entry| Cost::from_usd(entry.total_cost.unwrap()))
+        .sum();
+
+    let pricing_fetcher = Arc::new(PricingFetcher::new(true).await);
+    let cost_calculator = Arc::new(CostCalculator::new(pricing_fetcher));
+    let aggregator = Aggregator::new(cost_calculator, TimezoneConfig::default());
+
+    let daily = aggregator
+        .aggregate_daily(
+            stream::iter(entries.clone().into_iter().map(Ok)),
+            CostMode::Display,
+        )
+        .await
+        .unwrap();
+    let sessions = aggregator
+        .aggregate_sessions(
+            stream::iter(entries.into_iter().rev().map(Ok)),
+            CostMode::Display,
+        )
+        .await
+        .unwrap();
+    let monthly = Aggregator::aggregate_monthly(&daily);
+    let weekly = Aggregator::aggregate_weekly(&daily, chrono::Weekday::Mon);
+
+    assert_eq!(Totals::from_daily(&daily).total_cost, expected);
+    assert_eq!(Totals::from_monthly(&monthly).total_cost, expected);
+    assert_eq!(Totals::from_weekly(&weekly).total_cost, expected);
+    assert_eq!(Totals::from_sessions(&sessions).total_cost, expected);
+}
+
 #[tokio::test]
 async fn test_project_filtering() {
     let entries = vec![
@@ -223,6 +285,7 @@ async fn test_project_filtering() {
             total_cost: None,
             project: Some("project-a".to_string()),
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
         UsageEntry {
             session_id: SessionId::new("s2"),
@@ -232,6 +295,7 @@ async fn test_project_filtering() {
             total_cost: None,
             project: Some("project-b".to_string()),
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
         UsageEntry {
             session_id: SessionId::new("s3"),
@@ -241,6 +305,7 @@ async fn test_project_filtering() {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
     ];
 
@@ -279,6 +344,7 @@ async fn test_instance_grouping() {
             total_cost: None,
             project: None,
             instance_id: Some("instance-a".to_string()),
+            provider: ProviderId::new("claude"),
         },
         UsageEntry {
             session_id: SessionId::new("s2"),
@@ -292,6 +358,7 @@ async fn test_instance_grouping() {
             total_cost: None,
             project: None,
             instance_id: Some("instance-b".to_string()),
+            provider: ProviderId::new("claude"),
         },
         UsageEntry {
             session_id: SessionId::new("s3"),
@@ -304,7 +371,8 @@ async fn test_instance_grouping() {
             tokens: TokenCounts::new(300, 150, 0, 0),
             total_cost: None,
             project: None,
-            instance_id: None, // Will default to "default"
+            instance_id: None,
+            provider: ProviderId::new("claude"), // Will default to "default"
         },
     ];
 
@@ -376,6 +444,7 @@ async fn test_session_duration_calculation() {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
         UsageEntry {
             session_id: SessionId::new("session-1"),
@@ -385,6 +454,7 @@ async fn test_session_duration_calculation() {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
         UsageEntry {
             session_id: SessionId::new("session-1"),
@@ -394,6 +464,7 @@ async fn test_session_duration_calculation() {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
     ];
 
@@ -435,6 +506,7 @@ async fn test_mixed_models_handling() {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
         UsageEntry {
             session_id: SessionId::new("s2"),
@@ -448,6 +520,7 @@ async fn test_mixed_models_handling() {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
         UsageEntry {
             session_id: SessionId::new("s3"),
@@ -461,6 +534,7 @@ async fn test_mixed_models_handling() {
             total_cost: None,
             project: None,
             instance_id: None,
+            provider: ProviderId::new("claude"),
         },
     ];
 
@@ -494,6 +568,7 @@ async fn test_cache_tokens_handling() {
         total_cost: None,
         project: None,
         instance_id: None,
+        provider: ProviderId::new("claude"),
     }];
 
     let pricing_fetcher = Arc::new(PricingFetcher::new(true).await);
@@ -525,24 +600,27 @@ async fn test_billing_block_edge_cases() {
             start_time: now - chrono::Duration::hours(5),
             end_time: now - chrono::Duration::hours(4),
             tokens: TokenCounts::new(1_000_000, 500_000, 0, 0),
-            total_cost: 25.0,
+            total_cost: Cost::from_usd(25.0),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         },
         SessionUsage {
             session_id: SessionId::new("s2"),
             start_time: now - chrono::Duration::hours(3),
             end_time: now - chrono::Duration::hours(2),
             tokens: TokenCounts::new(2_000_000, 1_000_000, 0, 0),
-            total_cost: 50.0,
+            total_cost: Cost::from_usd(50.0),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         },
         SessionUsage {
             session_id: SessionId::new("s3"),
             start_time: now - chrono::Duration::minutes(30),
             end_time: now,
             tokens: TokenCounts::new(500_000, 250_000, 0, 0),
-            total_cost: 12.5,
+            total_cost: Cost::from_usd(12.5),
             model: ModelName::new("claude-3-opus"),
+            provider: ProviderId::new("claude"),
         },
     ];
 
@@ -585,3 +663,13 @@ async fn test_error_handling() {
         _ => panic!("Expected InvalidDate error"),
     }
 }
+
+#[test]
+fn test_embedded_pricing_copies_match() {
+    // The repository root keeps a copy of the pricing compiled into ccstat
+    assert_eq!(
+        include_str!("../embedded/pricing.json"),
+        include_str!("../crates/ccstat-pricing/embedded/pricing.json"),
+        "embedded/pricing.json is out of sync with crates/ccstat-pricing/embedded/pricing.json"
+    );
+}
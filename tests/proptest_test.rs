@@ -1,9 +1,12 @@
 //! Property-based tests for ccstat using proptest
 
 use ccstat::{
+    Cost,
     cost_calculator::CostCalculator,
     timezone::TimezoneConfig,
-    types::{ISOTimestamp, ModelName, ModelPricing, SessionId, TokenCounts, UsageEntry},
+    types::{
+        ISOTimestamp, ModelName, ModelPricing, ProviderId, SessionId, TokenCounts, UsageEntry,
+    },
 };
 use chrono::{Datelike, TimeZone, Utc};
 use proptest::prelude::*;
@@ -34,6 +37,7 @@ prop_compose! {
             output_cost_per_token: output_cost,
             cache_creation_input_token_cost: cache_creation_cost,
             cache_read_input_token_cost: cache_read_cost,
+            ..Default::default()
         }
     }
 }
@@ -89,6 +93,7 @@ prop_compose! {
             total_cost,
             project,
             instance_id,
+            provider: ProviderId::new("claude"),
         }
     }
 }
@@ -100,7 +105,7 @@ proptest! {
         pricing in arb_model_pricing(),
     ) {
         let cost = CostCalculator::calculate_from_pricing(&tokens, &pricing);
-        prop_assert!(cost >= 0.0);
+        prop_assert!(cost >= Cost::ZERO);
     }
 
     #[test]